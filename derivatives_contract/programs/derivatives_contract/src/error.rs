@@ -43,6 +43,78 @@ pub enum CustomError {
     #[msg("Trader has no active status.")]
     InvalidTraderStatus,
 
+    // contract
+    #[msg("Contract registry has no room for more open contracts.")]
+    ContractRegistryFull,
+    #[msg("Contract is not open.")]
+    ContractNotOpen,
+    #[msg("Invalid contract type.")]
+    InvalidContractType,
+    #[msg("Invalid escrow account.")]
+    InvalidEscrowAccount,
+    #[msg("Invalid token mint.")]
+    InvalidTokenMint,
+    #[msg("Invalid contract registry.")]
+    InvalidContractRegistry,
+
+    // expiry
+    #[msg("Expiry date must be in the future.")]
+    InvalidExpiryDate,
+    #[msg("Contract has expired.")]
+    ContractExpired,
+    #[msg("Contract has not expired.")]
+    ContractNotExpired,
+    #[msg("Settlement grace period has passed.")]
+    SettlementWindowClosed,
+    #[msg("Contract can not be cancelled during its settlement grace period.")]
+    SettlementGracePeriodNotOver,
+    #[msg("Only the contract owner can cancel the contract before expiry.")]
+    InvalidCancelAuthority,
+
+    // options
+    #[msg("Option collateral is already locked.")]
+    CollateralAlreadyLocked,
+    #[msg("Option collateral is not locked.")]
+    CollateralNotLocked,
+    #[msg("Option premium is already paid.")]
+    PremiumAlreadyPaid,
+    #[msg("Option premium is not paid.")]
+    PremiumNotPaid,
+    #[msg("Option can not be exercised at this time.")]
+    OptionNotExercisable,
+    #[msg("Option has not expired.")]
+    OptionNotExpired,
+
+    // swaps
+    #[msg("Invalid swap payment schedule.")]
+    InvalidPaymentSchedule,
+    #[msg("Swap payment period has not ended.")]
+    PeriodNotEnded,
+    #[msg("Invalid rate oracle.")]
+    InvalidRateOracle,
+
+    // oracle
+    #[msg("Invalid price oracle.")]
+    InvalidPriceOracle,
+    #[msg("Invalid oracle price.")]
+    InvalidOraclePrice,
+    #[msg("Oracle price is stale.")]
+    StaleOraclePrice,
+    #[msg("Oracle price confidence interval is too wide.")]
+    OracleConfidenceTooWide,
+    #[msg("Option is out of the money.")]
+    OptionOutOfTheMoney,
+
+    // margin
+    #[msg("Signer is not a counterparty of the contract.")]
+    InvalidCounterparty,
+    #[msg("Invalid margin account.")]
+    InvalidMarginAccount,
+    #[msg("Insufficient margin.")]
+    InsufficientMargin,
+    #[msg("Margin is locked while the contract is open.")]
+    MarginLocked,
+
     // account
     #[msg("Account is not initialized.")]
     AccountNotInitialized,
@@ -0,0 +1,75 @@
+// Transfers out of the per-contract escrows.
+//
+// escrow_tokens is an associated token account owned by the contract pda and escrow_funds a system
+// account pda derived from the contract, both are signed for with the bumps stored on the contract.
+
+use {
+    crate::state::derivative_contract::DerivativeContract,
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+/// Transfers amount of the underlying asset from the contract's escrow_tokens to to.
+pub fn transfer_escrow_tokens<'info>(
+    derivative_contract: &Account<'info, DerivativeContract>,
+    escrow_tokens: &Account<'info, TokenAccount>,
+    mint_token: &Account<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    let contract_id = derivative_contract.contract_id.to_le_bytes();
+    let seeds = &[
+        b"derivative-contract",
+        derivative_contract.owner.as_ref(),
+        contract_id.as_ref(),
+        &[derivative_contract.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: escrow_tokens.to_account_info(),
+                mint: mint_token.to_account_info(),
+                to,
+                authority: derivative_contract.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        mint_token.decimals,
+    )
+}
+
+/// Transfers amount of lamports from the contract's escrow_funds to to.
+pub fn transfer_escrow_funds<'info>(
+    derivative_contract: &Account<'info, DerivativeContract>,
+    escrow_funds: &SystemAccount<'info>,
+    to: AccountInfo<'info>,
+    sys_program: &Program<'info, System>,
+    amount: u64,
+) -> Result<()> {
+    let derivative_contract_key = derivative_contract.key();
+    let seeds = &[
+        b"escrow-funds",
+        derivative_contract_key.as_ref(),
+        &[derivative_contract.escrow_funds_bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    system_program::transfer(
+        CpiContext::new_with_signer(
+            sys_program.to_account_info(),
+            system_program::Transfer {
+                from: escrow_funds.to_account_info(),
+                to,
+            },
+            signer,
+        ),
+        amount,
+    )
+}
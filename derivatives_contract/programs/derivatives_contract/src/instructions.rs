@@ -1,19 +1,43 @@
 // admin instructions
 pub mod init;
+pub mod init_rate_oracle;
+pub mod update_rate_oracle;
+
+// mock oracle instructions, for localnet only
+#[cfg(feature = "mock-oracle")]
+pub mod init_mock_oracle;
+#[cfg(feature = "mock-oracle")]
+pub mod set_mock_oracle_price;
 
 // public instructions
+pub mod cancel_contract;
 pub mod create_futures_contract;
 pub mod create_options_contract;
 pub mod create_swap_contract;
 pub mod create_token;
 pub mod deposit_asset;
 pub mod deposit_funds;
+pub mod deposit_margin;
+pub mod exercise_option;
+pub mod expire_option;
+pub mod expire_swap_contract;
+pub mod init_margin_account;
+pub mod lock_option_collateral;
+pub mod purchase_option;
 pub mod settle_futures_contract;
+pub mod settle_swap_period;
 pub mod transfer_token;
+pub mod withdraw_margin;
 
 // bring everything in scope
 pub use {
-    create_futures_contract::*, create_options_contract::*, create_swap_contract::*,
-    create_token::*, deposit_asset::*, deposit_funds::*, init::*, settle_futures_contract::*,
-    transfer_token::*,
+    cancel_contract::*, create_futures_contract::*, create_options_contract::*,
+    create_swap_contract::*, create_token::*, deposit_asset::*, deposit_funds::*,
+    deposit_margin::*, exercise_option::*, expire_option::*, expire_swap_contract::*, init::*,
+    init_margin_account::*, init_rate_oracle::*, lock_option_collateral::*, purchase_option::*,
+    settle_futures_contract::*, settle_swap_period::*, transfer_token::*, update_rate_oracle::*,
+    withdraw_margin::*,
 };
+
+#[cfg(feature = "mock-oracle")]
+pub use {init_mock_oracle::*, set_mock_oracle_price::*};
@@ -0,0 +1,132 @@
+//! CancelContract instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        escrow,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{
+                ContractStatus, ContractType, DerivativeContract, SETTLEMENT_GRACE_PERIOD,
+            },
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+// The admin can cancel a futures or options contract before expiry, an option only until its
+// premium is paid. Anyone can cancel a futures contract left unsettled after its grace period.
+#[derive(Accounts)]
+pub struct CancelContract<'info> {
+    #[account(mut,
+        constraint = contract_registry.owner == derivative_contract.owner @ CustomError::InvalidContractRegistry
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type != ContractType::Swaps @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
+    )]
+    pub escrow_funds: SystemAccount<'info>,
+    #[account(mut,
+        constraint = Some(seller_tokens.owner) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = Some(seller.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller: SystemAccount<'info>,
+    #[account(mut,
+        constraint = Some(buyer.key()) == derivative_contract.buyer @ CustomError::InvalidBuyer
+    )]
+    pub buyer: SystemAccount<'info>,
+    #[account(address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
+    pub mint_token: Account<'info, Mint>,
+    // the admin before expiry, anyone once the settlement grace period has passed
+    pub authority: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn cancel_contract(ctx: Context<CancelContract>) -> Result<()> {
+    msg!("Validate inputs");
+    let accounts = &ctx.accounts;
+    let derivative_contract = &accounts.derivative_contract;
+    let now = Clock::get()?.unix_timestamp;
+
+    if now < derivative_contract.expiry_date {
+        if accounts.authority.key() != derivative_contract.owner {
+            return Err(CustomError::InvalidCancelAuthority.into());
+        }
+
+        // a paid option belongs to the buyer until it is exercised or expires
+        if derivative_contract.premium_paid {
+            return Err(CustomError::PremiumAlreadyPaid.into());
+        }
+    } else {
+        // expired options are closed through expire_option
+        if derivative_contract.contract_type == ContractType::Options {
+            return Err(CustomError::ContractExpired.into());
+        }
+
+        let settlement_deadline = derivative_contract
+            .expiry_date
+            .checked_add(SETTLEMENT_GRACE_PERIOD)
+            .ok_or(CustomError::InvalidArithmeticOperation)?;
+        if now <= settlement_deadline {
+            return Err(CustomError::SettlementGracePeriodNotOver.into());
+        }
+    }
+
+    // the seller deposited the underlying asset
+    if derivative_contract.asset_deposited > 0 {
+        escrow::transfer_escrow_tokens(
+            derivative_contract,
+            &accounts.escrow_tokens,
+            &accounts.mint_token,
+            accounts.seller_tokens.to_account_info(),
+            &accounts.token_program,
+            derivative_contract.asset_deposited,
+        )?;
+    }
+
+    // the buyer deposited the funds of a futures contract, the seller the collateral of a put
+    if derivative_contract.funds_deposited > 0 {
+        let depositor = if derivative_contract.contract_type == ContractType::Futures {
+            accounts.buyer.to_account_info()
+        } else {
+            accounts.seller.to_account_info()
+        };
+
+        escrow::transfer_escrow_funds(
+            derivative_contract,
+            &accounts.escrow_funds,
+            depositor,
+            &accounts.system_program,
+            derivative_contract.funds_deposited,
+        )?;
+    }
+
+    let derivative_contract_key = ctx.accounts.derivative_contract.key();
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    derivative_contract.asset_deposited = 0;
+    derivative_contract.funds_deposited = 0;
+    derivative_contract.collateral_locked = false;
+    derivative_contract.status = ContractStatus::Cancelled;
+
+    // cancelled contracts are no longer listed as open
+    ctx.accounts
+        .contract_registry
+        .open_contracts
+        .retain(|x| *x != derivative_contract_key);
+
+    Ok(())
+}
@@ -3,43 +3,139 @@
 use {
     crate::{
         error::CustomError,
-        state::{derivative_contract::ContractType, derivative_contract::DerivativeContract},
+        state::{
+            contract_registry::{ContractRegistry, MAX_OPEN_CONTRACTS},
+            derivative_contract::{ContractStatus, ContractType, DerivativeContract},
+        },
     },
     anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
 };
 
+// Shared by create_futures_contract, create_options_contract and create_swap_contract
 #[derive(Accounts)]
-#[instruction(params: CreateFuturesContractParams)]
-pub struct CreateFuturesContract<'info> {
+pub struct CreateContract<'info> {
     #[account(mut,has_one = owner,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
+        constraint = contract_registry.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DerivativeContract::INIT_SPACE,
+        seeds = [
+            b"derivative-contract",
+            owner.key().as_ref(),
+            contract_registry.next_contract_id.to_le_bytes().as_ref()
+        ],
+        bump
     )]
     pub derivative_contract: Account<'info, DerivativeContract>,
+    pub underlying_mint: Account<'info, Mint>,
+    // underlying asset escrow of the contract, the contract pda is its authority
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = derivative_contract
+    )]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    // sol escrow of the contract, created by the first deposit
+    #[account(seeds = [b"escrow-funds", derivative_contract.key().as_ref()], bump)]
+    pub escrow_funds: SystemAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
+impl<'info> CreateContract<'info> {
+    // Assigns the next contract id to the new contract, sets up its escrows and lists it in
+    // the contract registry
+    pub fn open(
+        &mut self,
+        bumps: &CreateContractBumps,
+        contract_type: ContractType,
+        expiry_date: i64,
+        underlying_asset: Pubkey,
+        buyer: Pubkey,
+        seller: Pubkey,
+    ) -> Result<()> {
+        if underlying_asset != self.underlying_mint.key() {
+            return Err(CustomError::InvalidTokenMint.into());
+        }
+
+        if expiry_date <= Clock::get()?.unix_timestamp {
+            return Err(CustomError::InvalidExpiryDate.into());
+        }
+
+        if buyer == seller {
+            return Err(CustomError::InvalidBuyer.into());
+        }
+
+        let contract_registry = &mut self.contract_registry;
+        if contract_registry.open_contracts.len() >= MAX_OPEN_CONTRACTS {
+            return Err(CustomError::ContractRegistryFull.into());
+        }
+
+        let contract = &mut self.derivative_contract;
+        contract.owner = self.owner.key();
+        contract.contract_id = contract_registry.next_contract_id;
+        contract.contract_type = contract_type;
+        contract.status = ContractStatus::Open;
+        contract.expiry_date = expiry_date;
+        contract.underlying_asset = underlying_asset;
+        contract.buyer = Some(buyer);
+        contract.seller = Some(seller);
+        contract.escrow_tokens = self.escrow_tokens.key();
+        contract.escrow_funds = self.escrow_funds.key();
+        contract.bump = bumps.derivative_contract;
+        contract.escrow_funds_bump = bumps.escrow_funds;
+        contract.decimals = contract_registry.decimals;
+        contract.is_initialized = true;
+
+        contract_registry.next_contract_id = contract_registry
+            .next_contract_id
+            .checked_add(1)
+            .ok_or(CustomError::InvalidArithmeticOperation)?;
+        contract_registry.open_contracts.push(contract.key());
+
+        Ok(())
+    }
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateFuturesContractParams {
     pub expiry_date: i64,
     pub underlying_asset: Pubkey,
-    pub price: u64,
+    pub price: u64,           // contract price, lamports per whole token
+    pub price_oracle: Pubkey, // feed of the underlying asset's mark price in SOL
     pub buyer: Pubkey,
     pub seller: Pubkey,
 }
 
 pub fn create_futures_contract(
-    ctx: Context<CreateFuturesContract>,
+    ctx: Context<CreateContract>,
     params: &CreateFuturesContractParams,
 ) -> Result<()> {
+    ctx.accounts.open(
+        &ctx.bumps,
+        ContractType::Futures,
+        params.expiry_date,
+        params.underlying_asset,
+        params.buyer,
+        params.seller,
+    )?;
+
     let contract = &mut ctx.accounts.derivative_contract;
-    contract.contract_type = ContractType::Futures;
-    contract.expiry_date = params.expiry_date;
-    contract.underlying_asset = params.underlying_asset;
     contract.price = params.price;
-    contract.buyer = Some(params.buyer);
-    contract.seller = Some(params.seller);
+    contract.price_oracle = Some(params.price_oracle);
     Ok(())
 }
@@ -3,48 +3,50 @@
 use {
     crate::{
         error::CustomError,
-        state::{
-            derivative_contract::ContractType, derivative_contract::DerivativeContract,
-            derivative_contract::OptionType,
-        },
+        instructions::create_futures_contract::CreateContract,
+        state::derivative_contract::{ContractType, OptionStyle, OptionType},
     },
     anchor_lang::prelude::*,
 };
 
-#[derive(Accounts)]
-#[instruction(params: CreateOptionsContractParams)]
-pub struct CreateOptionsContract<'info> {
-    #[account(mut,has_one = owner,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
-    )]
-    pub derivative_contract: Account<'info, DerivativeContract>,
-    // mut makes it changeble (mutable)
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateOptionsContractParams {
     pub expiry_date: i64,
     pub underlying_asset: Pubkey,
-    pub strike_price: u64,
+    pub strike_price: u64, // lamports per whole token of the underlying asset
     pub option_type: OptionType,
+    pub option_style: OptionStyle,
+    pub premium: u64,         // lamports the buyer pays the seller for the option
+    pub contract_size: u64,   // underlying asset covered by the option, in base units
+    pub price_oracle: Pubkey, // feed of the underlying asset's mark price in SOL
     pub buyer: Pubkey,
     pub seller: Pubkey,
 }
 
 pub fn create_options_contract(
-    ctx: Context<CreateOptionsContract>,
+    ctx: Context<CreateContract>,
     params: &CreateOptionsContractParams,
 ) -> Result<()> {
+    msg!("Validate inputs");
+    if params.strike_price == 0 || params.contract_size == 0 {
+        return Err(CustomError::InvalidAmount.into());
+    }
+
+    ctx.accounts.open(
+        &ctx.bumps,
+        ContractType::Options,
+        params.expiry_date,
+        params.underlying_asset,
+        params.buyer,
+        params.seller,
+    )?;
+
     let contract = &mut ctx.accounts.derivative_contract;
-    contract.contract_type = ContractType::Options;
-    contract.expiry_date = params.expiry_date;
-    contract.underlying_asset = params.underlying_asset;
     contract.price = params.strike_price;
+    contract.price_oracle = Some(params.price_oracle);
     contract.option_type = Some(params.option_type);
-    contract.buyer = Some(params.buyer);
-    contract.seller = Some(params.seller);
+    contract.option_style = Some(params.option_style);
+    contract.premium = Some(params.premium);
+    contract.contract_size = Some(params.contract_size);
     Ok(())
 }
@@ -2,44 +2,54 @@
 
 use {
     crate::{
-        error::CustomError,
-        state::{derivative_contract::ContractType, derivative_contract::DerivativeContract},
+        error::CustomError, instructions::create_futures_contract::CreateContract,
+        state::derivative_contract::ContractType,
     },
     anchor_lang::prelude::*,
 };
 
-#[derive(Accounts)]
-#[instruction(params: CreateSwapContractParams)]
-pub struct CreateSwapContract<'info> {
-    #[account(mut,has_one = owner,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
-    )]
-    pub derivative_contract: Account<'info, DerivativeContract>,
-    // mut makes it changeble (mutable)
-    #[account(mut)]
-    pub owner: Signer<'info>,
-    pub system_program: Program<'info, System>,
-}
-
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateSwapContractParams {
-    pub notional_amount: u64,
-    pub fixed_rate: u64,
-    pub floating_rate: u64,
+    pub expiry_date: i64,
+    pub underlying_asset: Pubkey,
+    pub notional_amount: u64,        // lamports
+    pub fixed_rate: u64,             // basis points per year, paid by the buyer
+    pub floating_rate: u64,          // basis points per year, paid by the seller
+    pub period_length: i64,          // seconds between payments
+    pub num_periods: u16,            // payments, the last one falls on expiry_date
+    pub rate_oracle: Option<Pubkey>, // oracle the floating rate is read from, None keeps it fixed
     pub buyer: Pubkey,
     pub seller: Pubkey,
 }
 
 pub fn create_swap_contract(
-    ctx: Context<CreateSwapContract>,
+    ctx: Context<CreateContract>,
     params: &CreateSwapContractParams,
 ) -> Result<()> {
+    msg!("Validate inputs");
+    if params.notional_amount == 0 {
+        return Err(CustomError::InvalidAmount.into());
+    }
+
+    if params.period_length <= 0 || params.num_periods == 0 {
+        return Err(CustomError::InvalidPaymentSchedule.into());
+    }
+
+    ctx.accounts.open(
+        &ctx.bumps,
+        ContractType::Swaps,
+        params.expiry_date,
+        params.underlying_asset,
+        params.buyer,
+        params.seller,
+    )?;
+
     let contract = &mut ctx.accounts.derivative_contract;
-    contract.contract_type = ContractType::Swaps;
     contract.notional_amount = Some(params.notional_amount);
     contract.fixed_rate = Some(params.fixed_rate);
     contract.floating_rate = Some(params.floating_rate);
-    contract.buyer = Some(params.buyer);
-    contract.seller = Some(params.seller);
+    contract.period_length = Some(params.period_length);
+    contract.num_periods = Some(params.num_periods);
+    contract.rate_oracle = params.rate_oracle;
     Ok(())
 }
@@ -1,26 +1,32 @@
 //! DepositAsset instruction handler
 
 use {
-    crate::{error::CustomError, state::derivative_contract::DerivativeContract},
+    crate::{
+        error::CustomError,
+        state::derivative_contract::{ContractStatus, ContractType, DerivativeContract},
+    },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
     },
 };
 
 #[derive(Accounts)]
 #[instruction(params: DepositAssetParams)]
 pub struct DepositAsset<'info> {
+    // options lock their collateral through lock_option_collateral, swaps post margin
     #[account(mut,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Futures @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
     )]
     pub derivative_contract: Account<'info, DerivativeContract>,
     #[account(mut)]
     pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
     pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut, address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
     pub mint_token: Account<'info, Mint>,
     // mut makes it changeble (mutable)
     #[account(mut)]
@@ -32,7 +38,7 @@ pub struct DepositAsset<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct DepositAssetParams {
-    pub amount: u32,
+    pub amount: u64, // base units of the underlying asset
 }
 
 pub fn deposit_asset(ctx: Context<DepositAsset>, params: &DepositAssetParams) -> Result<()> {
@@ -44,11 +50,16 @@ pub fn deposit_asset(ctx: Context<DepositAsset>, params: &DepositAssetParams) ->
     let sender = &ctx.accounts.owner;
     let sender_tokens = &ctx.accounts.sender_tokens;
     let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let mint_token = &ctx.accounts.mint_token;
     let token_program = &ctx.accounts.token_program;
     let derivative_contract = &mut ctx.accounts.derivative_contract;
-    let decimals = derivative_contract.decimals as u64;
     let amount_ = params.amount;
 
+    // deposits close at expiry
+    if Clock::get()?.unix_timestamp >= derivative_contract.expiry_date {
+        return Err(CustomError::ContractExpired.into());
+    }
+
     let seller = match derivative_contract.seller {
         Some(seller) => seller,
         None => return Err(CustomError::SellerNotFound.into()),
@@ -58,28 +69,25 @@ pub fn deposit_asset(ctx: Context<DepositAsset>, params: &DepositAssetParams) ->
         return Err(CustomError::InvalidSeller.into());
     }
 
-    let base: u32 = 10;
-    let exponent = derivative_contract.decimals as u32;
-
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let amount_ = (amount_ as u64)
-        .checked_mul(result as u64)
-        .ok_or(CustomError::InvalidArithmeticOperation)?;
-
     // token
-    transfer(
+    transfer_checked(
         CpiContext::new(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: sender_tokens.to_account_info(),
+                mint: mint_token.to_account_info(),
                 to: recipient_tokens.to_account_info(),
                 authority: sender.to_account_info(),
             },
         ),
         amount_,
+        mint_token.decimals,
     )?;
 
+    derivative_contract.asset_deposited = derivative_contract
+        .asset_deposited
+        .checked_add(amount_)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+
     Ok(())
 }
@@ -3,31 +3,26 @@
 use {
     crate::{
         error::CustomError,
-        state::{deposit_base::DepositBase, derivative_contract::DerivativeContract},
+        state::derivative_contract::{ContractStatus, ContractType, DerivativeContract},
     },
     anchor_lang::{prelude::*, system_program},
-    anchor_spl::{
-        associated_token::AssociatedToken,
-        token::{transfer, Mint, Token, TokenAccount, Transfer},
-    },
 };
 
 #[derive(Accounts)]
 #[instruction(params: DepositFundsParams)]
 pub struct DepositFunds<'info> {
+    // options lock their collateral through lock_option_collateral, swaps post margin
     #[account(mut,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Futures @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
     )]
     pub derivative_contract: Account<'info, DerivativeContract>,
     #[account(mut,
-        constraint = deposit_account.is_initialized @ CustomError::AccountNotInitialized
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
     )]
-    pub deposit_account: Account<'info, DepositBase>,
-    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump = deposit_account.admin_auth_bump)]
-    /// CHECK: no need to check this.
-    pub pda_auth: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump = deposit_account.admin_treasury_vault_bump.unwrap())]
-    pub treasury_vault: SystemAccount<'info>,
+    pub escrow_funds: SystemAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -50,6 +45,11 @@ pub fn deposit_funds(ctx: Context<DepositFunds>, params: &DepositFundsParams) ->
     let amount_ = params.amount;
     let sys_program = &ctx.accounts.system_program;
 
+    // deposits close at expiry
+    if Clock::get()?.unix_timestamp >= derivative_contract.expiry_date {
+        return Err(CustomError::ContractExpired.into());
+    }
+
     let buyer = match derivative_contract.buyer {
         Some(buyer) => buyer,
         None => return Err(CustomError::BuyerNotFound.into()),
@@ -61,12 +61,17 @@ pub fn deposit_funds(ctx: Context<DepositFunds>, params: &DepositFundsParams) ->
 
     let cpi_accounts = system_program::Transfer {
         from: sender.to_account_info(),
-        to: ctx.accounts.treasury_vault.to_account_info(),
+        to: ctx.accounts.escrow_funds.to_account_info(),
     };
 
     let cpi = CpiContext::new(sys_program.to_account_info(), cpi_accounts);
 
     system_program::transfer(cpi, amount_)?;
 
+    derivative_contract.funds_deposited = derivative_contract
+        .funds_deposited
+        .checked_add(amount_)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+
     Ok(())
 }
@@ -0,0 +1,48 @@
+//! DepositMargin instruction handler
+
+use {
+    crate::{error::CustomError, state::margin_account::MarginAccount},
+    anchor_lang::{prelude::*, system_program},
+};
+
+#[derive(Accounts)]
+#[instruction(params: DepositMarginParams)]
+pub struct DepositMargin<'info> {
+    #[account(mut,has_one = owner,
+        constraint = margin_account.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub margin_account: Account<'info, MarginAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DepositMarginParams {
+    pub amount: u64, // lamports
+}
+
+pub fn deposit_margin(ctx: Context<DepositMargin>, params: &DepositMarginParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(CustomError::InvalidAmount.into());
+    }
+
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.owner.to_account_info(),
+        to: ctx.accounts.margin_account.to_account_info(),
+    };
+
+    let cpi = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+
+    system_program::transfer(cpi, params.amount)?;
+
+    let margin_account = &mut ctx.accounts.margin_account;
+    margin_account.balance = margin_account
+        .balance
+        .checked_add(params.amount)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+    Ok(())
+}
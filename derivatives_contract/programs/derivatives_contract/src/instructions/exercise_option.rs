@@ -0,0 +1,166 @@
+//! ExerciseOption instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        escrow, options, oracle,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{ContractStatus, ContractType, DerivativeContract, OptionType},
+        },
+    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct ExerciseOption<'info> {
+    #[account(mut,
+        constraint = contract_registry.owner == derivative_contract.owner @ CustomError::InvalidContractRegistry
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Options @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen,
+        constraint = Some(owner.key()) == derivative_contract.buyer @ CustomError::InvalidBuyer
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
+    )]
+    pub escrow_funds: SystemAccount<'info>,
+    // buyer's underlying asset, receives a call and delivers a put
+    #[account(mut)]
+    pub owner_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = Some(seller_tokens.owner) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = Some(seller.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller: SystemAccount<'info>,
+    #[account(address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
+    pub mint_token: Account<'info, Mint>,
+    #[account(
+        constraint = Some(price_oracle.key()) == derivative_contract.price_oracle @ CustomError::InvalidPriceOracle
+    )]
+    /// CHECK: Pyth price account or MockOracle, its owner is checked when the price is read
+    pub price_oracle: UncheckedAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
+    msg!("Validate inputs");
+    let derivative_contract = &ctx.accounts.derivative_contract;
+    if !derivative_contract.premium_paid {
+        return Err(CustomError::PremiumNotPaid.into());
+    }
+
+    let option_type = derivative_contract
+        .option_type
+        .ok_or(CustomError::InvalidContractType)?;
+    let option_style = derivative_contract
+        .option_style
+        .ok_or(CustomError::InvalidContractType)?;
+    let contract_size = derivative_contract
+        .contract_size
+        .ok_or(CustomError::InvalidContractType)?;
+
+    let now = Clock::get()?.unix_timestamp;
+    if !options::can_exercise(option_style, derivative_contract.expiry_date, now) {
+        return Err(CustomError::OptionNotExercisable.into());
+    }
+
+    let accounts = &ctx.accounts;
+    let contract_registry = &accounts.contract_registry;
+    let mark_price = oracle::mark_price(
+        &accounts.price_oracle,
+        now,
+        contract_registry.max_oracle_staleness,
+        contract_registry.max_oracle_confidence_bps,
+    )?;
+
+    // exercising out of the money would only lose the buyer money
+    if !options::in_the_money(option_type, derivative_contract.price, mark_price) {
+        return Err(CustomError::OptionOutOfTheMoney.into());
+    }
+
+    let strike_amount = options::strike_amount(
+        derivative_contract.price,
+        contract_size,
+        accounts.mint_token.decimals,
+    )
+    .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+    match option_type {
+        // buyer pays the strike to the seller and receives the locked underlying asset
+        OptionType::Call => {
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.owner.to_account_info(),
+                        to: accounts.seller.to_account_info(),
+                    },
+                ),
+                strike_amount,
+            )?;
+
+            escrow::transfer_escrow_tokens(
+                derivative_contract,
+                &accounts.escrow_tokens,
+                &accounts.mint_token,
+                accounts.owner_tokens.to_account_info(),
+                &accounts.token_program,
+                derivative_contract.asset_deposited,
+            )?;
+        }
+        // buyer delivers the underlying asset to the seller and receives the locked strike
+        OptionType::Put => {
+            transfer_checked(
+                CpiContext::new(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.owner_tokens.to_account_info(),
+                        mint: accounts.mint_token.to_account_info(),
+                        to: accounts.seller_tokens.to_account_info(),
+                        authority: accounts.owner.to_account_info(),
+                    },
+                ),
+                contract_size,
+                accounts.mint_token.decimals,
+            )?;
+
+            escrow::transfer_escrow_funds(
+                derivative_contract,
+                &accounts.escrow_funds,
+                accounts.owner.to_account_info(),
+                &accounts.system_program,
+                derivative_contract.funds_deposited,
+            )?;
+        }
+    }
+
+    let derivative_contract_key = ctx.accounts.derivative_contract.key();
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    derivative_contract.asset_deposited = 0;
+    derivative_contract.funds_deposited = 0;
+    derivative_contract.status = ContractStatus::Exercised;
+
+    // exercised options are no longer listed as open
+    ctx.accounts
+        .contract_registry
+        .open_contracts
+        .retain(|x| *x != derivative_contract_key);
+
+    Ok(())
+}
@@ -0,0 +1,100 @@
+//! ExpireOption instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        escrow, options,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{ContractStatus, ContractType, DerivativeContract},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{Mint, Token, TokenAccount},
+};
+
+// Anyone can expire an option once it can no longer be exercised, the collateral only ever goes
+// back to the seller
+#[derive(Accounts)]
+pub struct ExpireOption<'info> {
+    #[account(mut,
+        constraint = contract_registry.owner == derivative_contract.owner @ CustomError::InvalidContractRegistry
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Options @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
+    )]
+    pub escrow_funds: SystemAccount<'info>,
+    #[account(mut,
+        constraint = Some(seller_tokens.owner) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = Some(seller.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller: SystemAccount<'info>,
+    #[account(address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
+    pub mint_token: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+    msg!("Validate inputs");
+    let accounts = &ctx.accounts;
+    let derivative_contract = &accounts.derivative_contract;
+    let option_style = derivative_contract
+        .option_style
+        .ok_or(CustomError::InvalidContractType)?;
+
+    let deadline = options::exercise_deadline(option_style, derivative_contract.expiry_date)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+    if Clock::get()?.unix_timestamp <= deadline {
+        return Err(CustomError::OptionNotExpired.into());
+    }
+
+    // return the collateral to the seller
+    if derivative_contract.asset_deposited > 0 {
+        escrow::transfer_escrow_tokens(
+            derivative_contract,
+            &accounts.escrow_tokens,
+            &accounts.mint_token,
+            accounts.seller_tokens.to_account_info(),
+            &accounts.token_program,
+            derivative_contract.asset_deposited,
+        )?;
+    }
+
+    if derivative_contract.funds_deposited > 0 {
+        escrow::transfer_escrow_funds(
+            derivative_contract,
+            &accounts.escrow_funds,
+            accounts.seller.to_account_info(),
+            &accounts.system_program,
+            derivative_contract.funds_deposited,
+        )?;
+    }
+
+    let derivative_contract_key = ctx.accounts.derivative_contract.key();
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    derivative_contract.asset_deposited = 0;
+    derivative_contract.funds_deposited = 0;
+    derivative_contract.status = ContractStatus::Expired;
+
+    // expired options are no longer listed as open
+    ctx.accounts
+        .contract_registry
+        .open_contracts
+        .retain(|x| *x != derivative_contract_key);
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! ExpireSwapContract instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{
+                ContractStatus, ContractType, DerivativeContract, SETTLEMENT_GRACE_PERIOD,
+            },
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+// Anyone can expire a swap whose payments were not all settled by the end of its grace period,
+// the margin accounts are then released to their owners through withdraw_margin
+#[derive(Accounts)]
+pub struct ExpireSwapContract<'info> {
+    #[account(mut,
+        constraint = contract_registry.owner == derivative_contract.owner @ CustomError::InvalidContractRegistry
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Swaps @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+}
+
+pub fn expire_swap_contract(ctx: Context<ExpireSwapContract>) -> Result<()> {
+    msg!("Validate inputs");
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+
+    let settlement_deadline = derivative_contract
+        .expiry_date
+        .checked_add(SETTLEMENT_GRACE_PERIOD)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+    if Clock::get()?.unix_timestamp <= settlement_deadline {
+        return Err(CustomError::SettlementGracePeriodNotOver.into());
+    }
+
+    derivative_contract.status = ContractStatus::Expired;
+
+    // expired swaps are no longer listed as open
+    let derivative_contract_key = derivative_contract.key();
+    ctx.accounts
+        .contract_registry
+        .open_contracts
+        .retain(|x| *x != derivative_contract_key);
+
+    Ok(())
+}
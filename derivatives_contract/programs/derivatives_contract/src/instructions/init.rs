@@ -1,10 +1,7 @@
 //! Init instruction handler
 
 use {
-    crate::{
-        error::CustomError,
-        state::{deposit_base::DepositBase, derivative_contract::DerivativeContract},
-    },
+    crate::{error::CustomError, state::contract_registry::ContractRegistry},
     anchor_lang::prelude::*,
 };
 
@@ -16,23 +13,12 @@ pub struct Init<'info> {
     #[account(
         init,
         payer = owner,
-        space = 8 + DerivativeContract::INIT_SPACE,
-        constraint = !derivative_contract.is_initialized @ CustomError::AccountAlreadyInitialized,
-        seeds = [b"derivative-contract", owner.key().as_ref()],
+        space = 8 + ContractRegistry::INIT_SPACE,
+        constraint = !contract_registry.is_initialized @ CustomError::AccountAlreadyInitialized,
+        seeds = [b"contract-registry", owner.key().as_ref()],
         bump
     )]
-    pub derivative_contract: Account<'info, DerivativeContract>,
-    // init means to create account
-    // bump to use unique address for account
-    #[account(init, payer = owner, space = 8 + DepositBase::INIT_SPACE,
-        constraint = !deposit_account.is_initialized @ CustomError::AccountAlreadyInitialized
-    )]
-    pub deposit_account: Account<'info, DepositBase>,
-    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
-    /// CHECK: no need to check this.
-    pub pda_auth: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
-    pub treasury_vault: SystemAccount<'info>,
+    pub contract_registry: Account<'info, ContractRegistry>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -41,24 +27,27 @@ pub struct Init<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct InitParams {
-    decimals: u8, // decimals for the token mint
+    decimals: u8,                   // decimals for the token mint
+    max_oracle_staleness: i64,      // seconds a price feed may go without an update
+    max_oracle_confidence_bps: u16, // widest confidence interval accepted, in basis points of the price
 }
 
 pub fn init(ctx: Context<Init>, params: &InitParams) -> Result<()> {
-    let derivative_contract = &mut ctx.accounts.derivative_contract;
-    let deposit_account = &mut ctx.accounts.deposit_account;
+    msg!("Validate inputs");
+    if params.max_oracle_staleness <= 0 {
+        return Err(CustomError::InvalidNumeric.into());
+    }
 
-    // derivative contract
-    derivative_contract.owner = *ctx.accounts.owner.key;
-    derivative_contract.decimals = params.decimals;
-    derivative_contract.is_initialized = true;
+    let contract_registry = &mut ctx.accounts.contract_registry;
 
-    // deposit account
+    // contract registry
     // * - means dereferencing
-    deposit_account.owner = *ctx.accounts.owner.key;
-    deposit_account.admin_auth_bump = ctx.bumps.pda_auth;
-    deposit_account.admin_treasury_vault_bump = Some(ctx.bumps.treasury_vault);
-    deposit_account.is_initialized = true;
+    contract_registry.owner = *ctx.accounts.owner.key;
+    contract_registry.next_contract_id = 0;
+    contract_registry.decimals = params.decimals;
+    contract_registry.max_oracle_staleness = params.max_oracle_staleness;
+    contract_registry.max_oracle_confidence_bps = params.max_oracle_confidence_bps;
+    contract_registry.is_initialized = true;
 
     Ok(())
 }
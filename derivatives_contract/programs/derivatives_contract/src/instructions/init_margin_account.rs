@@ -0,0 +1,46 @@
+//! InitMarginAccount instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        state::{derivative_contract::DerivativeContract, margin_account::MarginAccount},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InitMarginAccount<'info> {
+    #[account(
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = Some(owner.key()) == derivative_contract.buyer
+            || Some(owner.key()) == derivative_contract.seller @ CustomError::InvalidCounterparty
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + MarginAccount::INIT_SPACE,
+        seeds = [b"margin-account", derivative_contract.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub margin_account: Account<'info, MarginAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_margin_account(ctx: Context<InitMarginAccount>) -> Result<()> {
+    let margin_account = &mut ctx.accounts.margin_account;
+
+    // * - means dereferencing
+    margin_account.owner = *ctx.accounts.owner.key;
+    margin_account.derivative_contract = ctx.accounts.derivative_contract.key();
+    margin_account.balance = 0;
+    margin_account.bump = ctx.bumps.margin_account;
+    margin_account.is_initialized = true;
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! InitMockOracle instruction handler
+
+use {
+    crate::{error::CustomError, state::mock_oracle::MockOracle},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: InitMockOracleParams)]
+pub struct InitMockOracle<'info> {
+    // init means to create account
+    #[account(init, payer = owner, space = 8 + MockOracle::INIT_SPACE,
+        constraint = !mock_oracle.is_initialized @ CustomError::AccountAlreadyInitialized
+    )]
+    pub mock_oracle: Account<'info, MockOracle>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitMockOracleParams {
+    pub price: i64, // price * 10^expo SOL per whole token
+    pub conf: u64,
+    pub expo: i32,
+}
+
+pub fn init_mock_oracle(ctx: Context<InitMockOracle>, params: &InitMockOracleParams) -> Result<()> {
+    let mock_oracle = &mut ctx.accounts.mock_oracle;
+
+    // * - means dereferencing
+    mock_oracle.owner = *ctx.accounts.owner.key;
+    mock_oracle.price = params.price;
+    mock_oracle.conf = params.conf;
+    mock_oracle.expo = params.expo;
+    mock_oracle.publish_time = Clock::get()?.unix_timestamp;
+    mock_oracle.is_initialized = true;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+//! InitRateOracle instruction handler
+
+use {
+    crate::{error::CustomError, state::rate_oracle::RateOracle},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: InitRateOracleParams)]
+pub struct InitRateOracle<'info> {
+    // init means to create account
+    #[account(init, payer = owner, space = 8 + RateOracle::INIT_SPACE,
+        constraint = !rate_oracle.is_initialized @ CustomError::AccountAlreadyInitialized
+    )]
+    pub rate_oracle: Account<'info, RateOracle>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitRateOracleParams {
+    pub rate: u64, // basis points per year
+}
+
+pub fn init_rate_oracle(ctx: Context<InitRateOracle>, params: &InitRateOracleParams) -> Result<()> {
+    let rate_oracle = &mut ctx.accounts.rate_oracle;
+
+    // * - means dereferencing
+    rate_oracle.owner = *ctx.accounts.owner.key;
+    rate_oracle.rate = params.rate;
+    rate_oracle.updated_at = Clock::get()?.unix_timestamp;
+    rate_oracle.is_initialized = true;
+
+    Ok(())
+}
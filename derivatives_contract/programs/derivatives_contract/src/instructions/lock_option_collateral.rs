@@ -0,0 +1,115 @@
+//! LockOptionCollateral instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        options,
+        state::derivative_contract::{
+            ContractStatus, ContractType, DerivativeContract, OptionType,
+        },
+    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct LockOptionCollateral<'info> {
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Options @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen,
+        constraint = Some(owner.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
+    pub escrow_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
+    )]
+    pub escrow_funds: SystemAccount<'info>,
+    // seller's underlying asset, only transferred from for calls
+    #[account(mut)]
+    pub sender_tokens: Account<'info, TokenAccount>,
+    #[account(address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
+    pub mint_token: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn lock_option_collateral(ctx: Context<LockOptionCollateral>) -> Result<()> {
+    msg!("Validate inputs");
+    let accounts = ctx.accounts;
+    let derivative_contract = &mut accounts.derivative_contract;
+    if derivative_contract.collateral_locked {
+        return Err(CustomError::CollateralAlreadyLocked.into());
+    }
+
+    // collateral is locked before expiry, an option expiring uncovered is left to expire_option
+    if Clock::get()?.unix_timestamp >= derivative_contract.expiry_date {
+        return Err(CustomError::ContractExpired.into());
+    }
+
+    let option_type = derivative_contract
+        .option_type
+        .ok_or(CustomError::InvalidContractType)?;
+    let contract_size = derivative_contract
+        .contract_size
+        .ok_or(CustomError::InvalidContractType)?;
+
+    match option_type {
+        // a call locks the underlying asset the buyer may buy
+        OptionType::Call => {
+            transfer_checked(
+                CpiContext::new(
+                    accounts.token_program.to_account_info(),
+                    TransferChecked {
+                        from: accounts.sender_tokens.to_account_info(),
+                        mint: accounts.mint_token.to_account_info(),
+                        to: accounts.escrow_tokens.to_account_info(),
+                        authority: accounts.owner.to_account_info(),
+                    },
+                ),
+                contract_size,
+                accounts.mint_token.decimals,
+            )?;
+
+            derivative_contract.asset_deposited = derivative_contract
+                .asset_deposited
+                .checked_add(contract_size)
+                .ok_or(CustomError::InvalidArithmeticOperation)?;
+        }
+        // a put locks the lamports paid for the underlying asset the buyer may sell
+        OptionType::Put => {
+            let amount = options::strike_amount(
+                derivative_contract.price,
+                contract_size,
+                accounts.mint_token.decimals,
+            )
+            .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+            system_program::transfer(
+                CpiContext::new(
+                    accounts.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: accounts.owner.to_account_info(),
+                        to: accounts.escrow_funds.to_account_info(),
+                    },
+                ),
+                amount,
+            )?;
+
+            derivative_contract.funds_deposited = derivative_contract
+                .funds_deposited
+                .checked_add(amount)
+                .ok_or(CustomError::InvalidArithmeticOperation)?;
+        }
+    }
+
+    derivative_contract.collateral_locked = true;
+
+    Ok(())
+}
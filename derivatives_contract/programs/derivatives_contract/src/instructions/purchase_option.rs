@@ -0,0 +1,66 @@
+//! PurchaseOption instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        state::derivative_contract::{ContractStatus, ContractType, DerivativeContract},
+    },
+    anchor_lang::{prelude::*, system_program},
+};
+
+#[derive(Accounts)]
+pub struct PurchaseOption<'info> {
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Options @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen,
+        constraint = Some(owner.key()) == derivative_contract.buyer @ CustomError::InvalidBuyer
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut,
+        constraint = Some(seller.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller: SystemAccount<'info>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn purchase_option(ctx: Context<PurchaseOption>) -> Result<()> {
+    msg!("Validate inputs");
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    if derivative_contract.premium_paid {
+        return Err(CustomError::PremiumAlreadyPaid.into());
+    }
+
+    if Clock::get()?.unix_timestamp >= derivative_contract.expiry_date {
+        return Err(CustomError::ContractExpired.into());
+    }
+
+    // the buyer only pays once the seller's side is covered
+    if !derivative_contract.collateral_locked {
+        return Err(CustomError::CollateralNotLocked.into());
+    }
+
+    let premium = derivative_contract
+        .premium
+        .ok_or(CustomError::InvalidContractType)?;
+
+    if premium > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.owner.to_account_info(),
+                    to: ctx.accounts.seller.to_account_info(),
+                },
+            ),
+            premium,
+        )?;
+    }
+
+    derivative_contract.premium_paid = true;
+
+    Ok(())
+}
@@ -0,0 +1,34 @@
+//! SetMockOraclePrice instruction handler
+
+use {
+    crate::{error::CustomError, state::mock_oracle::MockOracle},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: SetMockOraclePriceParams)]
+pub struct SetMockOraclePrice<'info> {
+    #[account(mut,has_one = owner,
+        constraint = mock_oracle.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub mock_oracle: Account<'info, MockOracle>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetMockOraclePriceParams {
+    pub price: i64, // price * 10^expo SOL per whole token
+    pub conf: u64,
+}
+
+pub fn set_mock_oracle_price(
+    ctx: Context<SetMockOraclePrice>,
+    params: &SetMockOraclePriceParams,
+) -> Result<()> {
+    let mock_oracle = &mut ctx.accounts.mock_oracle;
+    mock_oracle.price = params.price;
+    mock_oracle.conf = params.conf;
+    mock_oracle.publish_time = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
@@ -3,47 +3,65 @@
 use {
     crate::{
         error::CustomError,
-        state::{deposit_base::DepositBase, derivative_contract::DerivativeContract},
+        escrow, oracle,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{
+                ContractStatus, ContractType, DerivativeContract, SETTLEMENT_GRACE_PERIOD,
+            },
+        },
     },
-    anchor_lang::{prelude::*, system_program},
+    anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+        token::{Mint, Token, TokenAccount},
     },
 };
 
 #[derive(Accounts)]
-#[instruction(params: SettleFuturesContractParams)]
 pub struct SettleFuturesContract<'info> {
+    #[account(mut,has_one = owner,
+        constraint = contract_registry.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
     #[account(mut,has_one = owner,
         constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Futures @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
     )]
     pub derivative_contract: Account<'info, DerivativeContract>,
-    #[account(mut)]
+    #[account(mut, address = derivative_contract.escrow_tokens @ CustomError::InvalidEscrowAccount)]
     pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    #[account(mut,
+        constraint = Some(recipient_tokens.owner) == derivative_contract.buyer @ CustomError::InvalidBuyer
+    )]
     pub recipient_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
+    // returned the asset the seller does not owe the buyer
+    #[account(mut,
+        constraint = Some(seller_tokens.owner) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller_tokens: Account<'info, TokenAccount>,
+    #[account(mut, address = derivative_contract.underlying_asset @ CustomError::InvalidTokenMint)]
     pub mint_token: Account<'info, Mint>,
     #[account(mut,
-        constraint = deposit_account.is_initialized @ CustomError::AccountNotInitialized
+        seeds = [b"escrow-funds", derivative_contract.key().as_ref()],
+        bump = derivative_contract.escrow_funds_bump
     )]
-    pub deposit_account: Account<'info, DepositBase>,
-    /*
-    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump)]
-    /// CHECK: no need to check this.
-    pub pda_auth: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump)]
-    pub treasury_vault: SystemAccount<'info>,
-    */
-    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump = deposit_account.admin_auth_bump)]
-    /// CHECK: no need to check this.
-    pub pda_auth: UncheckedAccount<'info>,
-    #[account(mut, seeds = [b"treasury-vault", pda_auth.key().as_ref()], bump = deposit_account.admin_treasury_vault_bump.unwrap())]
-    pub treasury_vault: SystemAccount<'info>,
-    // mut makes it changeble (mutable)
-    #[account(mut)]
-    pub seller: Signer<'info>,
+    pub escrow_funds: SystemAccount<'info>,
+    #[account(
+        constraint = Some(price_oracle.key()) == derivative_contract.price_oracle @ CustomError::InvalidPriceOracle
+    )]
+    /// CHECK: Pyth price account or MockOracle, its owner is checked when the price is read
+    pub price_oracle: UncheckedAccount<'info>,
+    // refunded the funds left over after paying the seller
+    #[account(mut,
+        constraint = Some(buyer.key()) == derivative_contract.buyer @ CustomError::InvalidBuyer
+    )]
+    pub buyer: SystemAccount<'info>,
+    #[account(mut,
+        constraint = Some(seller.key()) == derivative_contract.seller @ CustomError::InvalidSeller
+    )]
+    pub seller: SystemAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -52,93 +70,115 @@ pub struct SettleFuturesContract<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct SettleFuturesContractParams {
-    pub amount: u32,
-    pub funds_amount: u64,
-    pub buyer: Pubkey,
-}
-
-pub fn settle_futures_contract(
-    ctx: Context<SettleFuturesContract>,
-    params: &SettleFuturesContractParams,
-) -> Result<()> {
+pub fn settle_futures_contract(ctx: Context<SettleFuturesContract>) -> Result<()> {
     msg!("Validate inputs");
-    if params.amount == 0 {
+    let accounts = &ctx.accounts;
+    let contract_registry = &accounts.contract_registry;
+    let derivative_contract = &accounts.derivative_contract;
+    let amount = derivative_contract.asset_deposited;
+    if amount == 0 {
         return Err(CustomError::InvalidAmount.into());
     }
 
-    let derivative_contract = &mut ctx.accounts.derivative_contract;
-    let sys_program = &ctx.accounts.system_program;
-    let sender_tokens = &ctx.accounts.sender_tokens;
-    let recipient_tokens = &ctx.accounts.recipient_tokens;
-    let mint_token = &ctx.accounts.mint_token;
-    let deposit_account = &ctx.accounts.deposit_account;
-    let pda_auth = &mut ctx.accounts.pda_auth;
-    let treasury_vault = &mut ctx.accounts.treasury_vault;
-    let token_program = &ctx.accounts.token_program;
-    let decimals: u8 = derivative_contract.decimals;
-    let _amount = params.amount;
-    let funds_amount = params.funds_amount;
-
-    let buyer = match derivative_contract.buyer {
-        Some(buyer) => buyer,
-        None => return Err(CustomError::BuyerNotFound.into()),
-    };
-
-    if params.buyer != buyer {
-        return Err(CustomError::InvalidBuyer.into());
+    // settlement opens at expiry and closes at the end of the grace period
+    let now = Clock::get()?.unix_timestamp;
+    if now < derivative_contract.expiry_date {
+        return Err(CustomError::ContractNotExpired.into());
     }
 
-    // _buyer gets asset
-    // _seller gets sol
-
-    let base: u32 = 10;
-    let exponent = derivative_contract.decimals as u32;
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
+    let settlement_deadline = derivative_contract
+        .expiry_date
+        .checked_add(SETTLEMENT_GRACE_PERIOD)
         .ok_or(CustomError::InvalidArithmeticOperation)?;
+    if now > settlement_deadline {
+        return Err(CustomError::SettlementWindowClosed.into());
+    }
 
-    // Transfer assets from treasury vault to recipient - buyer
-    let cpi_accounts = TransferChecked {
-        from: sender_tokens.to_account_info(),
-        mint: mint_token.to_account_info(),
-        to: recipient_tokens.to_account_info(),
-        authority: treasury_vault.to_account_info(),
-    };
-
-    let seeds = &[
-        b"treasury-vault",
-        pda_auth.to_account_info().key.as_ref(),
-        &[deposit_account.admin_treasury_vault_bump.unwrap()],
-    ];
-
-    let signer = &[&seeds[..]];
-
-    let cpi = CpiContext::new_with_signer(token_program.to_account_info(), cpi_accounts, signer);
-
-    transfer_checked(cpi, _amount, decimals)?;
-
-    // Transfer funds(sol) from treasury vault to recipient - seller
-    let cpi_accounts = system_program::Transfer {
-        from: treasury_vault.to_account_info(),
-        to: ctx.accounts.seller.to_account_info(),
+    // the contract is cash settled at the mark price, the side the price moved against pays the
+    // difference to the contract price out of its deposit: the seller in the escrowed asset, the
+    // buyer in the escrowed funds, a buyer's loss is capped by the funds it deposited
+    let mark_price = oracle::mark_price(
+        &accounts.price_oracle,
+        now,
+        contract_registry.max_oracle_staleness,
+        contract_registry.max_oracle_confidence_bps,
+    )?;
+    let decimals = accounts.mint_token.decimals;
+    let difference = oracle::lamports_value(
+        mark_price.abs_diff(derivative_contract.price),
+        amount,
+        decimals,
+    )
+    .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+    let (tokens_to_buyer, funds_to_seller) = if mark_price > derivative_contract.price {
+        let tokens = oracle::token_amount(mark_price, difference, decimals)
+            .ok_or(CustomError::InvalidArithmeticOperation)?;
+        (tokens.min(amount), 0)
+    } else {
+        (0, difference.min(derivative_contract.funds_deposited))
     };
+    let tokens_to_seller = amount - tokens_to_buyer;
+    let funds_to_buyer = derivative_contract.funds_deposited - funds_to_seller;
+
+    // Transfer the asset owed to the buyer from the contract's escrow
+    if tokens_to_buyer > 0 {
+        escrow::transfer_escrow_tokens(
+            derivative_contract,
+            &accounts.sender_tokens,
+            &accounts.mint_token,
+            accounts.recipient_tokens.to_account_info(),
+            &accounts.token_program,
+            tokens_to_buyer,
+        )?;
+    }
 
-    let seeds = &[
-        b"treasury-vault",
-        pda_auth.to_account_info().key.as_ref(),
-        &[deposit_account.admin_treasury_vault_bump.unwrap()],
-    ];
+    // Return the rest of the asset to the seller
+    if tokens_to_seller > 0 {
+        escrow::transfer_escrow_tokens(
+            derivative_contract,
+            &accounts.sender_tokens,
+            &accounts.mint_token,
+            accounts.seller_tokens.to_account_info(),
+            &accounts.token_program,
+            tokens_to_seller,
+        )?;
+    }
 
-    let signer = &[&seeds[..]];
+    // Transfer the funds(sol) owed to the seller from the contract's escrow
+    if funds_to_seller > 0 {
+        escrow::transfer_escrow_funds(
+            derivative_contract,
+            &accounts.escrow_funds,
+            accounts.seller.to_account_info(),
+            &accounts.system_program,
+            funds_to_seller,
+        )?;
+    }
 
-    let cpi = CpiContext::new_with_signer(sys_program.to_account_info(), cpi_accounts, signer);
+    // Refund the rest of the funds(sol) to the buyer
+    if funds_to_buyer > 0 {
+        escrow::transfer_escrow_funds(
+            derivative_contract,
+            &accounts.escrow_funds,
+            accounts.buyer.to_account_info(),
+            &accounts.system_program,
+            funds_to_buyer,
+        )?;
+    }
 
-    system_program::transfer(cpi, funds_amount)?;
+    let derivative_contract_key = ctx.accounts.derivative_contract.key();
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    derivative_contract.asset_deposited = 0;
+    derivative_contract.funds_deposited = 0;
+    derivative_contract.settlement_price = Some(mark_price);
+    derivative_contract.status = ContractStatus::Settled;
+
+    // settled contracts are no longer listed as open
+    ctx.accounts
+        .contract_registry
+        .open_contracts
+        .retain(|x| *x != derivative_contract_key);
 
     Ok(())
 }
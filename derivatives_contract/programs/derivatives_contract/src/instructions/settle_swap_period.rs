@@ -0,0 +1,145 @@
+//! SettleSwapPeriod instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        state::{
+            contract_registry::ContractRegistry,
+            derivative_contract::{ContractStatus, ContractType, DerivativeContract},
+            margin_account::MarginAccount,
+            rate_oracle::RateOracle,
+        },
+        swaps,
+    },
+    anchor_lang::prelude::*,
+};
+
+// Anyone can crank a swap payment once its period has ended
+#[derive(Accounts)]
+pub struct SettleSwapPeriod<'info> {
+    #[account(mut,
+        constraint = contract_registry.owner == derivative_contract.owner @ CustomError::InvalidContractRegistry
+    )]
+    pub contract_registry: Account<'info, ContractRegistry>,
+    #[account(mut,
+        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized,
+        constraint = derivative_contract.contract_type == ContractType::Swaps @ CustomError::InvalidContractType,
+        constraint = derivative_contract.status == ContractStatus::Open @ CustomError::ContractNotOpen
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut,
+        seeds = [b"margin-account", derivative_contract.key().as_ref(), buyer_margin.owner.as_ref()],
+        bump = buyer_margin.bump,
+        constraint = Some(buyer_margin.owner) == derivative_contract.buyer @ CustomError::InvalidMarginAccount
+    )]
+    pub buyer_margin: Account<'info, MarginAccount>,
+    #[account(mut,
+        seeds = [b"margin-account", derivative_contract.key().as_ref(), seller_margin.owner.as_ref()],
+        bump = seller_margin.bump,
+        constraint = Some(seller_margin.owner) == derivative_contract.seller @ CustomError::InvalidMarginAccount
+    )]
+    pub seller_margin: Account<'info, MarginAccount>,
+    // required when the contract reads its floating rate from an oracle
+    pub rate_oracle: Option<Account<'info, RateOracle>>,
+}
+
+pub fn settle_swap_period(ctx: Context<SettleSwapPeriod>) -> Result<()> {
+    msg!("Validate inputs");
+    let derivative_contract = &mut ctx.accounts.derivative_contract;
+    let period_length = derivative_contract
+        .period_length
+        .ok_or(CustomError::InvalidPaymentSchedule)?;
+    let num_periods = derivative_contract
+        .num_periods
+        .ok_or(CustomError::InvalidPaymentSchedule)?;
+    let notional_amount = derivative_contract
+        .notional_amount
+        .ok_or(CustomError::InvalidContractType)?;
+    let fixed_rate = derivative_contract
+        .fixed_rate
+        .ok_or(CustomError::InvalidContractType)?;
+
+    let (period_start, period_end) = swaps::period_bounds(
+        derivative_contract.expiry_date,
+        period_length,
+        num_periods,
+        derivative_contract.periods_settled,
+    )
+    .ok_or(CustomError::InvalidPaymentSchedule)?;
+
+    if Clock::get()?.unix_timestamp < period_end {
+        return Err(CustomError::PeriodNotEnded.into());
+    }
+
+    // the floating rate of the period is the oracle's rate when it was published during the
+    // period, otherwise the last known rate carries over so a late crank can still settle
+    if let Some(rate_oracle_key) = derivative_contract.rate_oracle {
+        let rate_oracle = match &ctx.accounts.rate_oracle {
+            Some(rate_oracle) if rate_oracle.key() == rate_oracle_key => rate_oracle,
+            _ => return Err(CustomError::InvalidRateOracle.into()),
+        };
+
+        if rate_oracle.updated_at >= period_start && rate_oracle.updated_at <= period_end {
+            derivative_contract.floating_rate = Some(rate_oracle.rate);
+        }
+    }
+
+    let floating_rate = derivative_contract
+        .floating_rate
+        .ok_or(CustomError::InvalidContractType)?;
+
+    let fixed_payment = swaps::leg_payment(notional_amount, fixed_rate, period_length)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+    let floating_payment = swaps::leg_payment(notional_amount, floating_rate, period_length)
+        .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+    // only the difference of the legs is paid, by the side owing more
+    let (payer, payee, amount) = if fixed_payment >= floating_payment {
+        (
+            &mut ctx.accounts.buyer_margin,
+            &mut ctx.accounts.seller_margin,
+            fixed_payment - floating_payment,
+        )
+    } else {
+        (
+            &mut ctx.accounts.seller_margin,
+            &mut ctx.accounts.buyer_margin,
+            floating_payment - fixed_payment,
+        )
+    };
+
+    // a payer whose margin can't cover the payment defaults, the payee takes what is left
+    let defaulted = amount > payer.balance;
+    let amount = amount.min(payer.balance);
+
+    if amount > 0 {
+        payer.balance -= amount;
+        payee.balance = payee
+            .balance
+            .checked_add(amount)
+            .ok_or(CustomError::InvalidArithmeticOperation)?;
+
+        // margin accounts are owned by the program, their lamports are moved directly
+        payer.sub_lamports(amount)?;
+        payee.add_lamports(amount)?;
+    }
+
+    derivative_contract.periods_settled += 1;
+
+    // the last payment settles the swap, a default closes it early
+    if defaulted || derivative_contract.periods_settled == num_periods {
+        derivative_contract.status = if defaulted {
+            ContractStatus::Defaulted
+        } else {
+            ContractStatus::Settled
+        };
+
+        let derivative_contract_key = derivative_contract.key();
+        ctx.accounts
+            .contract_registry
+            .open_contracts
+            .retain(|x| *x != derivative_contract_key);
+    }
+
+    Ok(())
+}
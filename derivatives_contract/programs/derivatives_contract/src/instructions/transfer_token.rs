@@ -1,7 +1,7 @@
 //! TransferToken instruction handler
 
 use {
-    crate::{error::CustomError, state::derivative_contract::DerivativeContract},
+    crate::{error::CustomError, state::contract_registry::ContractRegistry},
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token,
@@ -14,9 +14,9 @@ use {
 #[instruction(params: TransferTokenParams)]
 pub struct TransferToken<'info> {
     #[account(mut,
-        constraint = derivative_contract.is_initialized @ CustomError::AccountNotInitialized
+        constraint = contract_registry.is_initialized @ CustomError::AccountNotInitialized
     )]
-    pub derivative_contract: Account<'info, DerivativeContract>,
+    pub contract_registry: Account<'info, ContractRegistry>,
     #[account(mut)]
     pub from_account: Account<'info, TokenAccount>,
     #[account(mut)]
@@ -42,12 +42,12 @@ pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams)
         return Err(CustomError::InvalidAmount.into());
     }
 
-    let derivative_contract = &ctx.accounts.derivative_contract;
-    let decimals = derivative_contract.decimals;
+    let contract_registry = &ctx.accounts.contract_registry;
+    let decimals = contract_registry.decimals;
     let _amount = params.amount;
 
     let base: u32 = 10;
-    let exponent = derivative_contract.decimals as u32;
+    let exponent = contract_registry.decimals as u32;
 
     // lets get the amount in decimal format
     // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
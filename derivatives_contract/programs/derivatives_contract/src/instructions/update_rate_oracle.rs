@@ -0,0 +1,32 @@
+//! UpdateRateOracle instruction handler
+
+use {
+    crate::{error::CustomError, state::rate_oracle::RateOracle},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: UpdateRateOracleParams)]
+pub struct UpdateRateOracle<'info> {
+    #[account(mut,has_one = owner,
+        constraint = rate_oracle.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub rate_oracle: Account<'info, RateOracle>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdateRateOracleParams {
+    pub rate: u64, // basis points per year
+}
+
+pub fn update_rate_oracle(
+    ctx: Context<UpdateRateOracle>,
+    params: &UpdateRateOracleParams,
+) -> Result<()> {
+    let rate_oracle = &mut ctx.accounts.rate_oracle;
+    rate_oracle.rate = params.rate;
+    rate_oracle.updated_at = Clock::get()?.unix_timestamp;
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+//! WithdrawMargin instruction handler
+
+use {
+    crate::{
+        error::CustomError,
+        state::{
+            derivative_contract::{ContractStatus, DerivativeContract},
+            margin_account::MarginAccount,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: WithdrawMarginParams)]
+pub struct WithdrawMargin<'info> {
+    // margin backs the contract's payments until it is no longer open
+    #[account(
+        constraint = derivative_contract.status != ContractStatus::Open @ CustomError::MarginLocked
+    )]
+    pub derivative_contract: Account<'info, DerivativeContract>,
+    #[account(mut,has_one = owner,has_one = derivative_contract,
+        constraint = margin_account.is_initialized @ CustomError::AccountNotInitialized
+    )]
+    pub margin_account: Account<'info, MarginAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawMarginParams {
+    pub amount: u64, // lamports
+}
+
+pub fn withdraw_margin(ctx: Context<WithdrawMargin>, params: &WithdrawMarginParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(CustomError::InvalidAmount.into());
+    }
+
+    let margin_account = &mut ctx.accounts.margin_account;
+    margin_account.balance = margin_account
+        .balance
+        .checked_sub(params.amount)
+        .ok_or(CustomError::InsufficientMargin)?;
+
+    // the margin account is owned by the program, its lamports are moved directly
+    margin_account.sub_lamports(params.amount)?;
+    ctx.accounts.owner.add_lamports(params.amount)?;
+
+    Ok(())
+}
@@ -1,62 +1,149 @@
 pub mod error;
+pub mod escrow;
 pub mod instructions;
+pub mod options;
+pub mod oracle;
 pub mod state;
+pub mod swaps;
 
 use {anchor_lang::prelude::*, instructions::*};
 
 declare_id!("HjQAsSTgfHJZrEgwtDTztwaphTqVjgQ148JZinYrj6VD");
 
-#[program]
-pub mod derivatives_contract {
-    use super::*;
+// The program is declared through a macro so the mock oracle instructions, and with them any
+// program writable price feed, only exist in builds with the mock-oracle feature: anchor's
+// #[program] does not honour #[cfg] on single instructions.
+macro_rules! derivatives_program {
+    ($($mock_oracle_instructions:tt)*) => {
+        #[program]
+        pub mod derivatives_contract {
+            use super::*;
 
-    // admin instructions
-    pub fn init(ctx: Context<Init>, params: InitParams) -> Result<()> {
-        instructions::init(ctx, &params)
-    }
+            // admin instructions
+            pub fn init(ctx: Context<Init>, params: InitParams) -> Result<()> {
+                instructions::init(ctx, &params)
+            }
 
-    // public instructions: add sol transfer by buyer and seller sends asset
-    pub fn create_futures_contract(
-        ctx: Context<CreateFuturesContract>,
-        params: CreateFuturesContractParams,
-    ) -> Result<()> {
-        instructions::create_futures_contract(ctx, &params)
-    }
+            pub fn init_rate_oracle(
+                ctx: Context<InitRateOracle>,
+                params: InitRateOracleParams,
+            ) -> Result<()> {
+                instructions::init_rate_oracle(ctx, &params)
+            }
 
-    pub fn create_options_contract(
-        ctx: Context<CreateOptionsContract>,
-        params: CreateOptionsContractParams,
-    ) -> Result<()> {
-        instructions::create_options_contract(ctx, &params)
-    }
+            pub fn update_rate_oracle(
+                ctx: Context<UpdateRateOracle>,
+                params: UpdateRateOracleParams,
+            ) -> Result<()> {
+                instructions::update_rate_oracle(ctx, &params)
+            }
 
-    pub fn create_swap_contract(
-        ctx: Context<CreateSwapContract>,
-        params: CreateSwapContractParams,
-    ) -> Result<()> {
-        instructions::create_swap_contract(ctx, &params)
-    }
+            $($mock_oracle_instructions)*
 
-    pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
-        instructions::create_token(ctx, &params)
-    }
+            // public instructions: add sol transfer by buyer and seller sends asset
+            pub fn create_futures_contract(
+                ctx: Context<CreateContract>,
+                params: CreateFuturesContractParams,
+            ) -> Result<()> {
+                instructions::create_futures_contract(ctx, &params)
+            }
 
-    pub fn transfer_token(ctx: Context<TransferToken>, params: TransferTokenParams) -> Result<()> {
-        instructions::transfer_token(ctx, &params)
-    }
+            pub fn create_options_contract(
+                ctx: Context<CreateContract>,
+                params: CreateOptionsContractParams,
+            ) -> Result<()> {
+                instructions::create_options_contract(ctx, &params)
+            }
 
-    pub fn deposit_asset(ctx: Context<DepositAsset>, params: DepositAssetParams) -> Result<()> {
-        instructions::deposit_asset(ctx, &params)
-    }
+            pub fn create_swap_contract(
+                ctx: Context<CreateContract>,
+                params: CreateSwapContractParams,
+            ) -> Result<()> {
+                instructions::create_swap_contract(ctx, &params)
+            }
+
+            pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
+                instructions::create_token(ctx, &params)
+            }
+
+            pub fn transfer_token(ctx: Context<TransferToken>, params: TransferTokenParams) -> Result<()> {
+                instructions::transfer_token(ctx, &params)
+            }
+
+            pub fn deposit_asset(ctx: Context<DepositAsset>, params: DepositAssetParams) -> Result<()> {
+                instructions::deposit_asset(ctx, &params)
+            }
+
+            pub fn deposit_funds(ctx: Context<DepositFunds>, params: DepositFundsParams) -> Result<()> {
+                instructions::deposit_funds(ctx, &params)
+            }
 
-    pub fn deposit_funds(ctx: Context<DepositFunds>, params: DepositFundsParams) -> Result<()> {
-        instructions::deposit_funds(ctx, &params)
+            pub fn settle_futures_contract(ctx: Context<SettleFuturesContract>) -> Result<()> {
+                instructions::settle_futures_contract(ctx)
+            }
+
+            pub fn cancel_contract(ctx: Context<CancelContract>) -> Result<()> {
+                instructions::cancel_contract(ctx)
+            }
+
+            pub fn lock_option_collateral(ctx: Context<LockOptionCollateral>) -> Result<()> {
+                instructions::lock_option_collateral(ctx)
+            }
+
+            pub fn purchase_option(ctx: Context<PurchaseOption>) -> Result<()> {
+                instructions::purchase_option(ctx)
+            }
+
+            pub fn exercise_option(ctx: Context<ExerciseOption>) -> Result<()> {
+                instructions::exercise_option(ctx)
+            }
+
+            pub fn expire_option(ctx: Context<ExpireOption>) -> Result<()> {
+                instructions::expire_option(ctx)
+            }
+
+            pub fn init_margin_account(ctx: Context<InitMarginAccount>) -> Result<()> {
+                instructions::init_margin_account(ctx)
+            }
+
+            pub fn deposit_margin(ctx: Context<DepositMargin>, params: DepositMarginParams) -> Result<()> {
+                instructions::deposit_margin(ctx, &params)
+            }
+
+            pub fn withdraw_margin(
+                ctx: Context<WithdrawMargin>,
+                params: WithdrawMarginParams,
+            ) -> Result<()> {
+                instructions::withdraw_margin(ctx, &params)
+            }
+
+            pub fn settle_swap_period(ctx: Context<SettleSwapPeriod>) -> Result<()> {
+                instructions::settle_swap_period(ctx)
+            }
+
+            pub fn expire_swap_contract(ctx: Context<ExpireSwapContract>) -> Result<()> {
+                instructions::expire_swap_contract(ctx)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "mock-oracle")]
+derivatives_program! {
+    pub fn init_mock_oracle(
+        ctx: Context<InitMockOracle>,
+        params: InitMockOracleParams,
+    ) -> Result<()> {
+        instructions::init_mock_oracle(ctx, &params)
     }
 
-    pub fn settle_futures_contract(
-        ctx: Context<SettleFuturesContract>,
-        params: SettleFuturesContractParams,
+    pub fn set_mock_oracle_price(
+        ctx: Context<SetMockOraclePrice>,
+        params: SetMockOraclePriceParams,
     ) -> Result<()> {
-        instructions::settle_futures_contract(ctx, &params)
+        instructions::set_mock_oracle_price(ctx, &params)
     }
 }
+
+#[cfg(not(feature = "mock-oracle"))]
+derivatives_program! {}
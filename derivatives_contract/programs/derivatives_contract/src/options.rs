@@ -0,0 +1,43 @@
+// Exercise rules and collateral amounts for the options contracts.
+//
+// strike_price is in lamports per whole token of the underlying asset, contract_size in base
+// units of the underlying asset. A call locks contract_size of the underlying asset, a put locks
+// the lamports the seller pays for it at the strike price.
+
+use crate::{
+    oracle,
+    state::derivative_contract::{OptionStyle, OptionType, EXERCISE_WINDOW},
+};
+
+/// Lamports for contract_size base units of the underlying asset at strike_price.
+pub fn strike_amount(strike_price: u64, contract_size: u64, decimals: u8) -> Option<u64> {
+    oracle::lamports_value(strike_price, contract_size, decimals)
+}
+
+/// Whether exercising at mark_price pays off for the buyer.
+pub fn in_the_money(option_type: OptionType, strike_price: u64, mark_price: u64) -> bool {
+    match option_type {
+        OptionType::Call => mark_price > strike_price,
+        OptionType::Put => mark_price < strike_price,
+    }
+}
+
+/// Last timestamp at which an option can be exercised.
+pub fn exercise_deadline(option_style: OptionStyle, expiry_date: i64) -> Option<i64> {
+    match option_style {
+        OptionStyle::American => Some(expiry_date),
+        OptionStyle::European => expiry_date.checked_add(EXERCISE_WINDOW),
+    }
+}
+
+/// Whether an option can be exercised at now.
+pub fn can_exercise(option_style: OptionStyle, expiry_date: i64, now: i64) -> bool {
+    let opens = match option_style {
+        OptionStyle::American => i64::MIN,
+        OptionStyle::European => expiry_date,
+    };
+    match exercise_deadline(option_style, expiry_date) {
+        Some(deadline) => opens <= now && now <= deadline,
+        None => false,
+    }
+}
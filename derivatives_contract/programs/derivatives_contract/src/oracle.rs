@@ -0,0 +1,161 @@
+// Price feeds read for futures settlement, option exercise and margin checks.
+//
+// A feed is a Pyth price account. Builds with the mock-oracle feature, for localnet only, also read
+// MockOracle accounts owned by this program. Feeds quote the underlying asset in SOL,
+// price * 10^expo SOL per whole token. A price is only used while it is no older than the
+// registry's max_oracle_staleness and its confidence interval is within max_oracle_confidence_bps
+// of the price.
+
+use {
+    crate::error::CustomError,
+    anchor_lang::{prelude::*, solana_program::pubkey},
+};
+
+// Pyth oracle programs, mainnet and devnet
+pub const PYTH_PROGRAM_IDS: [Pubkey; 2] = [
+    pubkey!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi9epH"),
+    pubkey!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s"),
+];
+
+// Pyth price account layout (version 2)
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PYTH_EXPO_OFFSET: usize = 20;
+const PYTH_TIMESTAMP_OFFSET: usize = 96;
+const PYTH_AGG_PRICE_OFFSET: usize = 208;
+const PYTH_AGG_CONF_OFFSET: usize = 216;
+const PYTH_AGG_STATUS_OFFSET: usize = 224;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const LAMPORTS_DECIMALS: i32 = 9;
+
+// Price read from a feed
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+}
+
+/// Reads the aggregate price of a Pyth price account, None if it is not a trading price account.
+pub fn parse_pyth_price(data: &[u8]) -> Option<OraclePrice> {
+    let magic = u32::from_le_bytes(read_bytes(data, 0)?);
+    let version = u32::from_le_bytes(read_bytes(data, 4)?);
+    let account_type = u32::from_le_bytes(read_bytes(data, 8)?);
+    if magic != PYTH_MAGIC || version != PYTH_VERSION || account_type != PYTH_ACCOUNT_TYPE_PRICE {
+        return None;
+    }
+
+    let status = u32::from_le_bytes(read_bytes(data, PYTH_AGG_STATUS_OFFSET)?);
+    if status != PYTH_STATUS_TRADING {
+        return None;
+    }
+
+    Some(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, PYTH_AGG_PRICE_OFFSET)?),
+        conf: u64::from_le_bytes(read_bytes(data, PYTH_AGG_CONF_OFFSET)?),
+        expo: i32::from_le_bytes(read_bytes(data, PYTH_EXPO_OFFSET)?),
+        publish_time: i64::from_le_bytes(read_bytes(data, PYTH_TIMESTAMP_OFFSET)?),
+    })
+}
+
+/// Reads the price of a feed, dispatching on the program owning the account.
+pub fn load_price(price_oracle: &AccountInfo) -> Result<OraclePrice> {
+    #[cfg(feature = "mock-oracle")]
+    if price_oracle.owner == &crate::ID {
+        let data = price_oracle.try_borrow_data()?;
+        let mock_oracle = crate::state::mock_oracle::MockOracle::try_deserialize(&mut &data[..])?;
+        if !mock_oracle.is_initialized {
+            return Err(CustomError::AccountNotInitialized.into());
+        }
+
+        return Ok(OraclePrice {
+            price: mock_oracle.price,
+            conf: mock_oracle.conf,
+            expo: mock_oracle.expo,
+            publish_time: mock_oracle.publish_time,
+        });
+    }
+
+    if PYTH_PROGRAM_IDS.contains(price_oracle.owner) {
+        let data = price_oracle.try_borrow_data()?;
+        return parse_pyth_price(&data).ok_or(CustomError::InvalidOraclePrice.into());
+    }
+
+    Err(CustomError::InvalidPriceOracle.into())
+}
+
+/// Rejects prices that are stale, not positive or too uncertain.
+pub fn check_price(
+    oracle_price: &OraclePrice,
+    now: i64,
+    max_staleness: i64,
+    max_confidence_bps: u16,
+) -> Result<()> {
+    let age = now.saturating_sub(oracle_price.publish_time);
+    if age > max_staleness {
+        return Err(CustomError::StaleOraclePrice.into());
+    }
+
+    if oracle_price.price <= 0 {
+        return Err(CustomError::InvalidOraclePrice.into());
+    }
+
+    // conf / price <= max_confidence_bps / BPS_DENOMINATOR
+    let conf = (oracle_price.conf as u128) * BPS_DENOMINATOR as u128;
+    let max_conf = (oracle_price.price as u128) * max_confidence_bps as u128;
+    if conf > max_conf {
+        return Err(CustomError::OracleConfidenceTooWide.into());
+    }
+
+    Ok(())
+}
+
+/// Lamports per whole token of a positive price, rounded down.
+pub fn lamports_per_token(oracle_price: &OraclePrice) -> Option<u64> {
+    let price = u128::try_from(oracle_price.price).ok()?;
+    let exponent = LAMPORTS_DECIMALS.checked_add(oracle_price.expo)?;
+    let lamports = if exponent >= 0 {
+        price.checked_mul(10u128.checked_pow(exponent as u32)?)?
+    } else {
+        price.checked_div(10u128.checked_pow(exponent.unsigned_abs())?)?
+    };
+    u64::try_from(lamports).ok()
+}
+
+/// Checked mark price of a feed in lamports per whole token.
+pub fn mark_price(
+    price_oracle: &AccountInfo,
+    now: i64,
+    max_staleness: i64,
+    max_confidence_bps: u16,
+) -> Result<u64> {
+    let oracle_price = load_price(price_oracle)?;
+    check_price(&oracle_price, now, max_staleness, max_confidence_bps)?;
+    let mark_price =
+        lamports_per_token(&oracle_price).ok_or(CustomError::InvalidArithmeticOperation)?;
+    Ok(mark_price)
+}
+
+/// Lamports for amount base units of a token with decimals at price lamports per whole token.
+pub fn lamports_value(price: u64, amount: u64, decimals: u8) -> Option<u64> {
+    let value = (price as u128)
+        .checked_mul(amount as u128)?
+        .checked_div(10u128.checked_pow(decimals as u32)?)?;
+    u64::try_from(value).ok()
+}
+
+/// Base units of a token with decimals worth value lamports at price lamports per whole token,
+/// rounded down.
+pub fn token_amount(price: u64, value: u64, decimals: u8) -> Option<u64> {
+    let amount = (value as u128)
+        .checked_mul(10u128.checked_pow(decimals as u32)?)?
+        .checked_div(price as u128)?;
+    u64::try_from(amount).ok()
+}
@@ -1,5 +1,10 @@
 // Program state handling.
 
 pub mod configs;
+pub mod contract_registry;
 pub mod deposit_base;
 pub mod derivative_contract;
+pub mod margin_account;
+#[cfg(feature = "mock-oracle")]
+pub mod mock_oracle;
+pub mod rate_oracle;
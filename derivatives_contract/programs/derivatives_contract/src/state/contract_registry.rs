@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+// Most contracts an admin can have open at once, matches the length of ContractRegistry.open_contracts
+pub const MAX_OPEN_CONTRACTS: usize = 32;
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct ContractRegistry {
+    pub owner: Pubkey,         // publickey of the admin
+    pub next_contract_id: u64, // id of the next contract created, increases monotonically
+    #[max_len(32)]
+    pub open_contracts: Vec<Pubkey>, // contracts created and not yet settled
+    pub decimals: u8,          // decimals for the token mint
+    pub max_oracle_staleness: i64, // seconds a price feed may go without an update
+    pub max_oracle_confidence_bps: u16, // widest confidence interval accepted, in basis points of the price
+    pub is_initialized: bool,           // is contract registry initialized
+}
@@ -1,22 +1,49 @@
 use anchor_lang::prelude::*;
 
+// Seconds after expiry_date during which a european option can be exercised
+pub const EXERCISE_WINDOW: i64 = 86_400;
+
+// Seconds after expiry_date during which a futures contract or the payments of a swap can be
+// settled, once it has passed the escrows can only be refunded through cancel_contract and the
+// swap margins are released through expire_swap_contract
+pub const SETTLEMENT_GRACE_PERIOD: i64 = 86_400;
+
 #[account]
 #[derive(InitSpace)]
 pub struct DerivativeContract {
-    pub owner: Pubkey, // publickey of the admin
+    pub owner: Pubkey,    // publickey of the admin
+    pub contract_id: u64, // id assigned by the contract registry, part of the contract pda seeds
     pub contract_type: ContractType,
+    pub status: ContractStatus,
     pub expiry_date: i64,
     pub underlying_asset: Pubkey,
-    pub price: u64,
+    pub price: u64, // futures contract price or option strike, lamports per whole token
     pub buyer: Option<Pubkey>,
     pub seller: Option<Pubkey>,
     pub custodian: Option<Pubkey>, // New field
     pub option_type: Option<OptionType>,
+    pub option_style: Option<OptionStyle>,
+    pub premium: Option<u64>, // lamports the buyer pays the seller for the option
+    pub contract_size: Option<u64>, // underlying asset covered by the option, in base units
+    pub collateral_locked: bool, // seller locked the option's collateral in escrow
+    pub premium_paid: bool,   // buyer paid the premium
     pub notional_amount: Option<u64>,
     pub fixed_rate: Option<u64>,
     pub floating_rate: Option<u64>,
-    pub is_initialized: bool, // is derivative contract initialized
-    pub decimals: u8,         // decimals for the token mint
+    pub period_length: Option<i64>,    // seconds between swap payments
+    pub num_periods: Option<u16>,      // swap payments, the last one falls on expiry_date
+    pub periods_settled: u16,          // swap payments made so far
+    pub rate_oracle: Option<Pubkey>,   // oracle the floating rate is read from each period
+    pub price_oracle: Option<Pubkey>,  // feed of the underlying asset's mark price in SOL
+    pub settlement_price: Option<u64>, // futures mark price at settlement, lamports per whole token
+    pub escrow_tokens: Pubkey, // underlying asset escrow, associated token account of the contract pda
+    pub escrow_funds: Pubkey,  // sol escrow pda of the contract
+    pub asset_deposited: u64,  // underlying asset held in escrow_tokens, in base units
+    pub funds_deposited: u64,  // lamports held in escrow_funds
+    pub bump: u8,              // bump of the contract pda, which signs for escrow_tokens
+    pub escrow_funds_bump: u8, // bump of the escrow_funds pda
+    pub is_initialized: bool,  // is derivative contract initialized
+    pub decimals: u8,          // decimals for the token mint
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq, InitSpace)]
@@ -31,3 +58,19 @@ pub enum OptionType {
     Call,
     Put,
 }
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum OptionStyle {
+    American, // exercisable until expiry_date
+    European, // exercisable from expiry_date until the end of the exercise window
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, PartialEq, Eq, InitSpace)]
+pub enum ContractStatus {
+    Open,      // listed in the contract registry
+    Settled,   // escrow paid out, removed from the contract registry
+    Exercised, // option exercised by the buyer, removed from the contract registry
+    Expired,   // option collateral returned or swap unsettled in time, removed from the registry
+    Cancelled, // escrows refunded to the depositors, removed from the contract registry
+    Defaulted, // swap payer's margin fell short, paid out and removed from the contract registry
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct MarginAccount {
+    pub owner: Pubkey,               // counterparty of the contract holding the margin
+    pub derivative_contract: Pubkey, // contract the margin is posted for
+    pub balance: u64,                // lamports posted as margin, rent excluded
+    pub bump: u8,
+    pub is_initialized: bool,
+}
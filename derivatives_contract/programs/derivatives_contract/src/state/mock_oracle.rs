@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// Price feed owned by the program for local testing, read like a Pyth price account
+#[account]
+#[derive(Default, InitSpace)]
+pub struct MockOracle {
+    pub owner: Pubkey,     // publickey allowed to publish the price
+    pub price: i64,        // price * 10^expo SOL per whole token of the underlying asset
+    pub conf: u64,         // confidence interval, in the same units as price
+    pub expo: i32,         // price exponent
+    pub publish_time: i64, // unix timestamp of the last update
+    pub is_initialized: bool,
+}
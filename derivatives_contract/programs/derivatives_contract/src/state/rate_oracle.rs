@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, InitSpace)]
+pub struct RateOracle {
+    pub owner: Pubkey,   // publickey allowed to publish the rate
+    pub rate: u64,       // floating rate in basis points per year
+    pub updated_at: i64, // unix timestamp of the last update
+    pub is_initialized: bool,
+}
@@ -0,0 +1,36 @@
+// Payment schedule and leg payments for the interest rate swaps.
+//
+// Rates are in basis points per year and the notional in lamports. The schedule runs num_periods
+// periods of period_length seconds, the last one ending at expiry_date. Each period the buyer owes
+// the fixed leg and the seller the floating leg, only the difference moves between their margin
+// accounts.
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+/// Start and end timestamps of period, counted from 0.
+pub fn period_bounds(
+    expiry_date: i64,
+    period_length: i64,
+    num_periods: u16,
+    period: u16,
+) -> Option<(i64, i64)> {
+    if period >= num_periods {
+        return None;
+    }
+
+    // periods still to come after this one
+    let later_periods = (num_periods - period - 1) as i64;
+    let end = expiry_date.checked_sub(period_length.checked_mul(later_periods)?)?;
+    let start = end.checked_sub(period_length)?;
+    Some((start, end))
+}
+
+/// Payment owed on notional at rate over period_length seconds, rounded down.
+pub fn leg_payment(notional: u64, rate: u64, period_length: i64) -> Option<u64> {
+    let amount = (notional as u128)
+        .checked_mul(rate as u128)?
+        .checked_mul(u128::try_from(period_length).ok()?)?
+        .checked_div(BPS_DENOMINATOR as u128 * SECONDS_PER_YEAR as u128)?;
+    u64::try_from(amount).ok()
+}
@@ -1,12 +1,9 @@
 import * as anchor from "@coral-xyz/anchor";
 import { Program } from "@coral-xyz/anchor";
 import { DerivativesContract } from "../target/types/derivatives_contract";
-import {
-  Account,
-  createAccount,
-  getOrCreateAssociatedTokenAccount,
-} from "@solana/spl-token";
+import { createAccount, getAccount } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
 
 describe("derivatives_contract", () => {
   // Configure the client to use the local cluster.
@@ -17,7 +14,6 @@ describe("derivatives_contract", () => {
   const program = anchor.workspace
     .DerivativesContract as Program<DerivativesContract>;
   const adminOwner = anchor.web3.Keypair.generate();
-  const depositAccount = anchor.web3.Keypair.generate();
 
   const payer = wallet.payer;
   const associateTokenProgram = new anchor.web3.PublicKey(
@@ -34,29 +30,58 @@ describe("derivatives_contract", () => {
   let sellerOwner = anchor.web3.Keypair.generate();
   let sellerOwnerATA = anchor.web3.Keypair.generate();
 
-  let treasuryVaultATA: Account;
+  const rateOracle = anchor.web3.Keypair.generate(); // floating rate feed of the swap contract
+  const mockOracle = anchor.web3.Keypair.generate(); // wBTC price feed in SOL for local testing
 
-  // pdaAuth
-  let [pdaAuth, adminPdaBump] = anchor.web3.PublicKey.findProgramAddressSync(
+  let futuresExpiryDate: number; // deposits close and settlement opens at expiry
+
+  let [contractRegistry] = anchor.web3.PublicKey.findProgramAddressSync(
     [
-      anchor.utils.bytes.utf8.encode("auth"),
-      depositAccount.publicKey.toBuffer(),
+      anchor.utils.bytes.utf8.encode("contract-registry"),
+      adminOwner.publicKey.toBuffer(),
     ],
     program.programId
   );
-  let [treasuryVault, adminTreasuryBump] =
-    anchor.web3.PublicKey.findProgramAddressSync(
-      [anchor.utils.bytes.utf8.encode("treasury-vault"), pdaAuth.toBuffer()],
+
+  // contract pda and escrows of the contract with the given id
+  const contractAccounts = (contractId: number) => {
+    let [derivativeContract] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("derivative-contract"),
+        adminOwner.publicKey.toBuffer(),
+        new anchor.BN(contractId).toArrayLike(Buffer, "le", 8),
+      ],
       program.programId
     );
+    let escrowTokens = anchor.utils.token.associatedAddress({
+      mint: mintToken.publicKey,
+      owner: derivativeContract,
+    });
+    let [escrowFunds] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("escrow-funds"),
+        derivativeContract.toBuffer(),
+      ],
+      program.programId
+    );
+    return { derivativeContract, escrowTokens, escrowFunds };
+  };
 
-  let [derivativeContract] = anchor.web3.PublicKey.findProgramAddressSync(
-    [
-      anchor.utils.bytes.utf8.encode("derivative-contract"),
-      adminOwner.publicKey.toBuffer(),
-    ],
-    program.programId
-  );
+  // contracts are created in this order, ids start at 0
+  const futuresContract = contractAccounts(0);
+  const optionsContract = contractAccounts(1);
+  const swapContract = contractAccounts(2);
+
+  // asserts that the call is rejected with the given CustomError
+  const assertCustomError = async (call: Promise<string>, code: string) => {
+    try {
+      await call;
+    } catch (error) {
+      assert.equal(error.error?.errorCode?.code, code);
+      return;
+    }
+    assert.fail(`expected the call to fail with ${code}`);
+  };
 
   // admin owner
   before(async () => {
@@ -110,19 +135,18 @@ describe("derivatives_contract", () => {
     try {
       let requestParams = {
         decimals: 9, // token mint in smallest unit i.e 9 decimals
+        maxOracleStaleness: new anchor.BN(300), // seconds
+        maxOracleConfidenceBps: 200, // 2% of the price
       };
 
       const tx = await program.methods
         .init(requestParams)
         .accounts({
           owner: adminOwner.publicKey,
-          derivativeContract: derivativeContract,
-          depositAccount: depositAccount.publicKey,
-          pdaAuth: pdaAuth,
-          treasuryVault: treasuryVault,
+          contractRegistry: contractRegistry,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([adminOwner, depositAccount])
+        .signers([adminOwner])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
@@ -130,10 +154,10 @@ describe("derivatives_contract", () => {
     }
 
     try {
-      let result = await program.account.derivativeContract.fetch(
-        derivativeContract
+      let result = await program.account.contractRegistry.fetch(
+        contractRegistry
       );
-      console.log("derivative contract: ", result);
+      console.log("contract registry: ", result);
     } catch (error) {
       console.log(error);
     }
@@ -152,7 +176,7 @@ describe("derivatives_contract", () => {
         .createToken(requestParams)
         .accounts({
           owner: payer.publicKey,
-          derivativeContract: derivativeContract,
+          contractRegistry: contractRegistry,
           mintToken: mintToken.publicKey,
           tokenAccount: tokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -193,7 +217,7 @@ describe("derivatives_contract", () => {
         .transferToken(requestParams)
         .accounts({
           owner: payer.publicKey,
-          derivativeContract: derivativeContract,
+          contractRegistry: contractRegistry,
           mintToken: mintToken.publicKey,
           fromAccount: tokenAccount,
           toAccount: sellerOwnerATA.publicKey,
@@ -210,12 +234,38 @@ describe("derivatives_contract", () => {
     }
   });
 
+  it("Is init mock oracle!", async () => {
+    try {
+      let requestParams = {
+        // 0.2 SOL per token
+        price: new anchor.BN(2000),
+        conf: new anchor.BN(10),
+        expo: -4,
+      };
+
+      const tx = await program.methods
+        .initMockOracle(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          mockOracle: mockOracle.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner, mockOracle])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
   it("Is create futures contract!", async () => {
     try {
+      futuresExpiryDate = Math.floor(Date.now() / 1000) + 10;
       let requestParams = {
-        expiryDate: new anchor.BN(12),
+        expiryDate: new anchor.BN(futuresExpiryDate),
         underlyingAsset: mintToken.publicKey,
-        price: new anchor.BN(3),
+        price: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 5), // 0.2 SOL per token
+        priceOracle: mockOracle.publicKey,
         buyer: buyerOwner.publicKey,
         seller: sellerOwner.publicKey,
       };
@@ -224,7 +274,13 @@ describe("derivatives_contract", () => {
         .createFuturesContract(requestParams)
         .accounts({
           owner: adminOwner.publicKey,
-          derivativeContract: derivativeContract,
+          contractRegistry: contractRegistry,
+          derivativeContract: futuresContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: futuresContract.escrowTokens,
+          escrowFunds: futuresContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([adminOwner])
@@ -238,11 +294,19 @@ describe("derivatives_contract", () => {
   it("Is create options contract!", async () => {
     try {
       let optionType = { call: {} };
+      let optionStyle = { american: {} };
       let requestParams = {
-        expiryDate: new anchor.BN(12),
+        // exercisable for the next hour
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
         underlyingAsset: mintToken.publicKey,
-        strikePrice: new anchor.BN(3),
+        // lamports per whole token
+        strikePrice: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
         optionType: optionType,
+        optionStyle: optionStyle,
+        premium: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 100),
+        // 5 tokens (in smallest unit i.e 9 decimals)
+        contractSize: new anchor.BN(5 * 10 ** 9),
+        priceOracle: mockOracle.publicKey,
         buyer: buyerOwner.publicKey,
         seller: sellerOwner.publicKey,
       };
@@ -251,7 +315,13 @@ describe("derivatives_contract", () => {
         .createOptionsContract(requestParams)
         .accounts({
           owner: adminOwner.publicKey,
-          derivativeContract: derivativeContract,
+          contractRegistry: contractRegistry,
+          derivativeContract: optionsContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: optionsContract.escrowTokens,
+          escrowFunds: optionsContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([adminOwner])
@@ -262,14 +332,39 @@ describe("derivatives_contract", () => {
     }
   });
 
+  it("Is init rate oracle!", async () => {
+    try {
+      let requestParams = {
+        rate: new anchor.BN(450), // basis points per year
+      };
+
+      const tx = await program.methods
+        .initRateOracle(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          rateOracle: rateOracle.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner, rateOracle])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
   it("Is create swap contract!", async () => {
     try {
       let requestParams = {
-        expiryDate: new anchor.BN(12),
+        // a single payment, due in 4 seconds
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 4),
         underlyingAsset: mintToken.publicKey,
-        notionalAmount: new anchor.BN(3),
-        fixedRate: new anchor.BN(10),
-        floatingRate: new anchor.BN(12),
+        notionalAmount: new anchor.BN(100 * anchor.web3.LAMPORTS_PER_SOL),
+        fixedRate: new anchor.BN(500), // basis points per year
+        floatingRate: new anchor.BN(450),
+        periodLength: new anchor.BN(4),
+        numPeriods: 1,
+        rateOracle: rateOracle.publicKey,
         buyer: buyerOwner.publicKey,
         seller: sellerOwner.publicKey,
       };
@@ -278,7 +373,13 @@ describe("derivatives_contract", () => {
         .createSwapContract(requestParams)
         .accounts({
           owner: adminOwner.publicKey,
-          derivativeContract: derivativeContract,
+          contractRegistry: contractRegistry,
+          derivativeContract: swapContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: swapContract.escrowTokens,
+          escrowFunds: swapContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([adminOwner])
@@ -290,34 +391,19 @@ describe("derivatives_contract", () => {
   });
 
   it("Is deposit asset - seller!", async () => {
-    try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintToken.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
-    } catch (error) {
-      console.log(error);
-    }
-
     try {
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(20),
+        // 20 tokens (in smallest unit i.e 9 decimals)
+        amount: new anchor.BN(20 * 10 ** 9),
       };
 
       const tx = await program.methods
         .depositAsset(requestParams)
         .accounts({
           owner: sellerOwner.publicKey,
-          derivativeContract: derivativeContract,
+          derivativeContract: futuresContract.derivativeContract,
           senderTokens: sellerOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATA.address,
+          recipientTokens: futuresContract.escrowTokens,
           mintToken: mintToken.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
@@ -332,7 +418,7 @@ describe("derivatives_contract", () => {
 
     try {
       let result = await program.account.derivativeContract.fetch(
-        derivativeContract
+        futuresContract.derivativeContract
       );
       console.log("derivative contract: ", result);
     } catch (error) {
@@ -351,10 +437,8 @@ describe("derivatives_contract", () => {
         .depositFunds(requestParams)
         .accounts({
           owner: buyerOwner.publicKey,
-          derivativeContract: derivativeContract,
-          depositAccount: depositAccount.publicKey,
-          pdaAuth: pdaAuth,
-          treasuryVault: treasuryVault,
+          derivativeContract: futuresContract.derivativeContract,
+          escrowFunds: futuresContract.escrowFunds,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([buyerOwner])
@@ -366,7 +450,7 @@ describe("derivatives_contract", () => {
 
     try {
       let result = await program.account.derivativeContract.fetch(
-        derivativeContract
+        futuresContract.derivativeContract
       );
       console.log("derivative contract: ", result);
     } catch (error) {
@@ -392,25 +476,52 @@ describe("derivatives_contract", () => {
       console.log(error);
     }
 
+    // settlement opens at expiry
+    await new Promise((resolve) =>
+      setTimeout(resolve, Math.max(0, (futuresExpiryDate + 1) * 1000 - Date.now()))
+    );
+
     try {
+      // mark price at settlement, 0.21 SOL per token
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amount: new anchor.BN(2),
-        fundsAmount: new anchor.BN(5 * anchor.web3.LAMPORTS_PER_SOL),
-        buyer: buyerOwner.publicKey,
+        price: new anchor.BN(2100),
+        conf: new anchor.BN(10),
       };
+
       const tx = await program.methods
-        .settleFuturesContract(requestParams)
+        .setMockOraclePrice(requestParams)
         .accounts({
           owner: adminOwner.publicKey,
-          seller: sellerOwner,
-          derivativeContract: derivativeContract,
-          senderTokens: treasuryVaultATA.address,
+          mockOracle: mockOracle.publicKey,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    const sellerTokensBefore = await getAccount(
+      provider.connection,
+      sellerOwnerATA.publicKey
+    );
+
+    try {
+      // the price rose 0.01 SOL per token, the seller owes the buyer 0.2 SOL on 20 tokens
+      const tx = await program.methods
+        .settleFuturesContract()
+        .accounts({
+          owner: adminOwner.publicKey,
+          buyer: buyerOwner.publicKey,
+          seller: sellerOwner.publicKey,
+          priceOracle: mockOracle.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: futuresContract.derivativeContract,
+          senderTokens: futuresContract.escrowTokens,
           recipientTokens: buyerOwnerATA.publicKey,
+          sellerTokens: sellerOwnerATA.publicKey,
           mintToken: mintToken.publicKey,
-          depositAccount: depositAccount.publicKey,
-          pdaAuth: pdaAuth,
-          treasuryVault: treasuryVault,
+          escrowFunds: futuresContract.escrowFunds,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -423,17 +534,694 @@ describe("derivatives_contract", () => {
     }
 
     try {
-      let result = await program.account.depositBase.fetch(
-        depositAccount.publicKey
+      let result = await program.account.contractRegistry.fetch(
+        contractRegistry
       );
-      console.log("deposit account: ", result);
+      console.log("contract registry: ", result);
 
       let result2 = await program.account.derivativeContract.fetch(
-        derivativeContract
+        futuresContract.derivativeContract
       );
       console.log("derivative contract: ", result2);
     } catch (error) {
       console.log(error);
     }
+
+    // paid in the asset at the mark price, 0.2 / 0.21 tokens, the rest goes back to the seller
+    let result = await program.account.derivativeContract.fetch(
+      futuresContract.derivativeContract
+    );
+    assert.ok(result.settlementPrice.eqn(210_000_000));
+    assert.ok(result.fundsDeposited.eqn(0));
+
+    const buyerTokens = await getAccount(
+      provider.connection,
+      buyerOwnerATA.publicKey
+    );
+    const sellerTokensAfter = await getAccount(
+      provider.connection,
+      sellerOwnerATA.publicKey
+    );
+    assert.equal(buyerTokens.amount, BigInt(952_380_952));
+    assert.equal(
+      sellerTokensAfter.amount - sellerTokensBefore.amount,
+      BigInt(20 * 10 ** 9 - 952_380_952)
+    );
+  });
+
+  it("Is lock option collateral - seller!", async () => {
+    try {
+      const tx = await program.methods
+        .lockOptionCollateral()
+        .accounts({
+          owner: sellerOwner.publicKey,
+          derivativeContract: optionsContract.derivativeContract,
+          escrowTokens: optionsContract.escrowTokens,
+          escrowFunds: optionsContract.escrowFunds,
+          senderTokens: sellerOwnerATA.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([sellerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is purchase option - buyer!", async () => {
+    try {
+      const tx = await program.methods
+        .purchaseOption()
+        .accounts({
+          owner: buyerOwner.publicKey,
+          derivativeContract: optionsContract.derivativeContract,
+          seller: sellerOwner.publicKey,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is exercise option - buyer!", async () => {
+    try {
+      const tx = await program.methods
+        .exerciseOption()
+        .accounts({
+          owner: buyerOwner.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: optionsContract.derivativeContract,
+          escrowTokens: optionsContract.escrowTokens,
+          escrowFunds: optionsContract.escrowFunds,
+          ownerTokens: buyerOwnerATA.publicKey,
+          sellerTokens: sellerOwnerATA.publicKey,
+          seller: sellerOwner.publicKey,
+          mintToken: mintToken.publicKey,
+          priceOracle: mockOracle.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.derivativeContract.fetch(
+        optionsContract.derivativeContract
+      );
+      console.log("derivative contract: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is expire option!", async () => {
+    const expiringContract = contractAccounts(3);
+
+    try {
+      let requestParams = {
+        // expires in 4 seconds
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 4),
+        underlyingAsset: mintToken.publicKey,
+        strikePrice: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+        optionType: { put: {} },
+        optionStyle: { american: {} },
+        premium: new anchor.BN(0),
+        contractSize: new anchor.BN(5 * 10 ** 9),
+        priceOracle: mockOracle.publicKey,
+        buyer: buyerOwner.publicKey,
+        seller: sellerOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .createOptionsContract(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: expiringContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: expiringContract.escrowTokens,
+          escrowFunds: expiringContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      // a put locks the strike in sol
+      const tx = await program.methods
+        .lockOptionCollateral()
+        .accounts({
+          owner: sellerOwner.publicKey,
+          derivativeContract: expiringContract.derivativeContract,
+          escrowTokens: expiringContract.escrowTokens,
+          escrowFunds: expiringContract.escrowFunds,
+          senderTokens: sellerOwnerATA.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([sellerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    try {
+      const tx = await program.methods
+        .expireOption()
+        .accounts({
+          contractRegistry: contractRegistry,
+          derivativeContract: expiringContract.derivativeContract,
+          escrowTokens: expiringContract.escrowTokens,
+          escrowFunds: expiringContract.escrowFunds,
+          sellerTokens: sellerOwnerATA.publicKey,
+          seller: sellerOwner.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.contractRegistry.fetch(
+        contractRegistry
+      );
+      console.log("contract registry: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is deposit margin - buyer and seller!", async () => {
+    for (const counterparty of [buyerOwner, sellerOwner]) {
+      let [marginAccount] = anchor.web3.PublicKey.findProgramAddressSync(
+        [
+          anchor.utils.bytes.utf8.encode("margin-account"),
+          swapContract.derivativeContract.toBuffer(),
+          counterparty.publicKey.toBuffer(),
+        ],
+        program.programId
+      );
+
+      try {
+        const tx = await program.methods
+          .initMarginAccount()
+          .accounts({
+            owner: counterparty.publicKey,
+            derivativeContract: swapContract.derivativeContract,
+            marginAccount: marginAccount,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([counterparty])
+          .rpc();
+        console.log("Your transaction signature", tx);
+      } catch (error) {
+        console.log(error);
+      }
+
+      try {
+        let requestParams = {
+          amount: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 10),
+        };
+
+        const tx = await program.methods
+          .depositMargin(requestParams)
+          .accounts({
+            owner: counterparty.publicKey,
+            marginAccount: marginAccount,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([counterparty])
+          .rpc();
+        console.log("Your transaction signature", tx);
+      } catch (error) {
+        console.log(error);
+      }
+    }
+  });
+
+  it("Is settle swap period!", async () => {
+    let [buyerMargin] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("margin-account"),
+        swapContract.derivativeContract.toBuffer(),
+        buyerOwner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+    let [sellerMargin] = anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("margin-account"),
+        swapContract.derivativeContract.toBuffer(),
+        sellerOwner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+    try {
+      // floating rate of the period
+      let requestParams = {
+        rate: new anchor.BN(480),
+      };
+
+      const tx = await program.methods
+        .updateRateOracle(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          rateOracle: rateOracle.publicKey,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // wait for the period to end
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    try {
+      const tx = await program.methods
+        .settleSwapPeriod()
+        .accounts({
+          contractRegistry: contractRegistry,
+          derivativeContract: swapContract.derivativeContract,
+          buyerMargin: buyerMargin,
+          sellerMargin: sellerMargin,
+          rateOracle: rateOracle.publicKey,
+        })
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.marginAccount.fetch(buyerMargin);
+      console.log("buyer margin account: ", result);
+
+      let result2 = await program.account.marginAccount.fetch(sellerMargin);
+      console.log("seller margin account: ", result2);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      // the swap is settled, the seller takes back the margin and the payment received
+      let result = await program.account.marginAccount.fetch(sellerMargin);
+      let requestParams = {
+        amount: result.balance,
+      };
+
+      const tx = await program.methods
+        .withdrawMargin(requestParams)
+        .accounts({
+          owner: sellerOwner.publicKey,
+          derivativeContract: swapContract.derivativeContract,
+          marginAccount: sellerMargin,
+        })
+        .signers([sellerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is cancel contract - admin!", async () => {
+    const cancelledContract = contractAccounts(4);
+
+    try {
+      let requestParams = {
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 3600),
+        underlyingAsset: mintToken.publicKey,
+        price: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 5), // 0.2 SOL per token
+        priceOracle: mockOracle.publicKey,
+        buyer: buyerOwner.publicKey,
+        seller: sellerOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .createFuturesContract(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: cancelledContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: cancelledContract.escrowTokens,
+          escrowFunds: cancelledContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let requestParams = {
+        amount: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL),
+      };
+
+      const tx = await program.methods
+        .depositFunds(requestParams)
+        .accounts({
+          owner: buyerOwner.publicKey,
+          derivativeContract: cancelledContract.derivativeContract,
+          escrowFunds: cancelledContract.escrowFunds,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([buyerOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      // before expiry only the admin can cancel, the buyer gets the funds back
+      const tx = await program.methods
+        .cancelContract()
+        .accounts({
+          authority: adminOwner.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: cancelledContract.derivativeContract,
+          escrowTokens: cancelledContract.escrowTokens,
+          escrowFunds: cancelledContract.escrowFunds,
+          sellerTokens: sellerOwnerATA.publicKey,
+          seller: sellerOwner.publicKey,
+          buyer: buyerOwner.publicKey,
+          mintToken: mintToken.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.derivativeContract.fetch(
+        cancelledContract.derivativeContract
+      );
+      console.log("derivative contract: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is settle swap period - default!", async () => {
+    const defaultedContract = contractAccounts(5);
+    const marginAccounts = [buyerOwner, sellerOwner].map(
+      (counterparty) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            anchor.utils.bytes.utf8.encode("margin-account"),
+            defaultedContract.derivativeContract.toBuffer(),
+            counterparty.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0]
+    );
+
+    try {
+      // a notional so large the buyer's margin can't cover the fixed leg
+      let requestParams = {
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 4),
+        underlyingAsset: mintToken.publicKey,
+        notionalAmount: new anchor.BN(1_000_000).mul(
+          new anchor.BN(anchor.web3.LAMPORTS_PER_SOL)
+        ),
+        fixedRate: new anchor.BN(500),
+        floatingRate: new anchor.BN(100),
+        periodLength: new anchor.BN(4),
+        numPeriods: 1,
+        rateOracle: null,
+        buyer: buyerOwner.publicKey,
+        seller: sellerOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .createSwapContract(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          contractRegistry: contractRegistry,
+          derivativeContract: defaultedContract.derivativeContract,
+          underlyingMint: mintToken.publicKey,
+          escrowTokens: defaultedContract.escrowTokens,
+          escrowFunds: defaultedContract.escrowFunds,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    for (const [i, counterparty] of [buyerOwner, sellerOwner].entries()) {
+      try {
+        const tx = await program.methods
+          .initMarginAccount()
+          .accounts({
+            owner: counterparty.publicKey,
+            derivativeContract: defaultedContract.derivativeContract,
+            marginAccount: marginAccounts[i],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([counterparty])
+          .rpc();
+        console.log("Your transaction signature", tx);
+
+        let requestParams = {
+          amount: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 100),
+        };
+
+        const tx2 = await program.methods
+          .depositMargin(requestParams)
+          .accounts({
+            owner: counterparty.publicKey,
+            marginAccount: marginAccounts[i],
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([counterparty])
+          .rpc();
+        console.log("Your transaction signature", tx2);
+      } catch (error) {
+        console.log(error);
+      }
+    }
+
+    // wait for the period to end
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    // the buyer's whole margin goes to the seller and the contract closes
+    const tx = await program.methods
+      .settleSwapPeriod()
+      .accounts({
+        contractRegistry: contractRegistry,
+        derivativeContract: defaultedContract.derivativeContract,
+        buyerMargin: marginAccounts[0],
+        sellerMargin: marginAccounts[1],
+        rateOracle: null,
+      })
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    let contract = await program.account.derivativeContract.fetch(
+      defaultedContract.derivativeContract
+    );
+    assert.ok(contract.status.defaulted !== undefined);
+
+    let buyerMargin = await program.account.marginAccount.fetch(
+      marginAccounts[0]
+    );
+    let sellerMargin = await program.account.marginAccount.fetch(
+      marginAccounts[1]
+    );
+    assert.ok(buyerMargin.balance.eqn(0));
+    assert.ok(
+      sellerMargin.balance.eqn((2 * anchor.web3.LAMPORTS_PER_SOL) / 100)
+    );
+
+    // the margins are no longer locked
+    const tx2 = await program.methods
+      .withdrawMargin({ amount: sellerMargin.balance })
+      .accounts({
+        owner: sellerOwner.publicKey,
+        derivativeContract: defaultedContract.derivativeContract,
+        marginAccount: marginAccounts[1],
+      })
+      .signers([sellerOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+  });
+
+  it("Is settle swap period - stale rate oracle!", async () => {
+    const staleContract = contractAccounts(6);
+    const marginAccounts = [buyerOwner, sellerOwner].map(
+      (counterparty) =>
+        anchor.web3.PublicKey.findProgramAddressSync(
+          [
+            anchor.utils.bytes.utf8.encode("margin-account"),
+            staleContract.derivativeContract.toBuffer(),
+            counterparty.publicKey.toBuffer(),
+          ],
+          program.programId
+        )[0]
+    );
+
+    // the rate oracle was last updated before this swap's only period
+    const tx = await program.methods
+      .createSwapContract({
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 4),
+        underlyingAsset: mintToken.publicKey,
+        notionalAmount: new anchor.BN(100 * anchor.web3.LAMPORTS_PER_SOL),
+        fixedRate: new anchor.BN(500),
+        floatingRate: new anchor.BN(450),
+        periodLength: new anchor.BN(4),
+        numPeriods: 1,
+        rateOracle: rateOracle.publicKey,
+        buyer: buyerOwner.publicKey,
+        seller: sellerOwner.publicKey,
+      })
+      .accounts({
+        owner: adminOwner.publicKey,
+        contractRegistry: contractRegistry,
+        derivativeContract: staleContract.derivativeContract,
+        underlyingMint: mintToken.publicKey,
+        escrowTokens: staleContract.escrowTokens,
+        escrowFunds: staleContract.escrowFunds,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    for (const [i, counterparty] of [buyerOwner, sellerOwner].entries()) {
+      const tx = await program.methods
+        .initMarginAccount()
+        .accounts({
+          owner: counterparty.publicKey,
+          derivativeContract: staleContract.derivativeContract,
+          marginAccount: marginAccounts[i],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([counterparty])
+        .rpc();
+      console.log("Your transaction signature", tx);
+
+      const tx2 = await program.methods
+        .depositMargin({
+          amount: new anchor.BN(anchor.web3.LAMPORTS_PER_SOL / 100),
+        })
+        .accounts({
+          owner: counterparty.publicKey,
+          marginAccount: marginAccounts[i],
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([counterparty])
+        .rpc();
+      console.log("Your transaction signature", tx2);
+    }
+
+    // wait for the period to end
+    await new Promise((resolve) => setTimeout(resolve, 5000));
+
+    // settles at the last known floating rate
+    const tx3 = await program.methods
+      .settleSwapPeriod()
+      .accounts({
+        contractRegistry: contractRegistry,
+        derivativeContract: staleContract.derivativeContract,
+        buyerMargin: marginAccounts[0],
+        sellerMargin: marginAccounts[1],
+        rateOracle: rateOracle.publicKey,
+      })
+      .rpc();
+    console.log("Your transaction signature", tx3);
+
+    let contract = await program.account.derivativeContract.fetch(
+      staleContract.derivativeContract
+    );
+    assert.ok(contract.status.settled !== undefined);
+    assert.ok(contract.floatingRate.eqn(450));
+  });
+
+  it("Is expire swap contract!", async () => {
+    const unmatchedContract = contractAccounts(7);
+
+    // the seller never opens a margin account, so no period can settle
+    const tx = await program.methods
+      .createSwapContract({
+        expiryDate: new anchor.BN(Math.floor(Date.now() / 1000) + 4),
+        underlyingAsset: mintToken.publicKey,
+        notionalAmount: new anchor.BN(100 * anchor.web3.LAMPORTS_PER_SOL),
+        fixedRate: new anchor.BN(500),
+        floatingRate: new anchor.BN(450),
+        periodLength: new anchor.BN(4),
+        numPeriods: 1,
+        rateOracle: null,
+        buyer: buyerOwner.publicKey,
+        seller: sellerOwner.publicKey,
+      })
+      .accounts({
+        owner: adminOwner.publicKey,
+        contractRegistry: contractRegistry,
+        derivativeContract: unmatchedContract.derivativeContract,
+        underlyingMint: mintToken.publicKey,
+        escrowTokens: unmatchedContract.escrowTokens,
+        escrowFunds: unmatchedContract.escrowFunds,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    // the margins are released only once the settlement grace period has passed
+    await assertCustomError(
+      program.methods
+        .expireSwapContract()
+        .accounts({
+          contractRegistry: contractRegistry,
+          derivativeContract: unmatchedContract.derivativeContract,
+        })
+        .rpc(),
+      "SettlementGracePeriodNotOver"
+    );
   });
 });
@@ -0,0 +1,292 @@
+// Tick, position and swap step bookkeeping for the concentrated liquidity pools.
+//
+// Fee growth is tracked per unit of liquidity in Q64.64 and is allowed to wrap, like the oracle's
+// cumulative prices only differences are meaningful. Each initialized tick stores the fee growth
+// on the side of the tick away from tick_current, which is flipped whenever the price crosses it.
+
+use crate::{
+    state::{
+        pool::BPS_DENOMINATOR,
+        position::Position,
+        tick_array::{Tick, TickArray, TICK_ARRAY_SIZE},
+    },
+    tick_math::{self, MAX_TICK, MIN_TICK, Q64},
+};
+
+/// Number of ticks covered by one tick array.
+pub fn tick_array_span(tick_spacing: u16) -> i32 {
+    tick_spacing as i32 * TICK_ARRAY_SIZE as i32
+}
+
+/// Start tick of the tick array holding tick.
+pub fn tick_array_start(tick: i32, tick_spacing: u16) -> i32 {
+    let span = tick_array_span(tick_spacing);
+    tick.div_euclid(span) * span
+}
+
+/// Whether tick is within range and a multiple of tick_spacing.
+pub fn is_valid_tick(tick: i32, tick_spacing: u16) -> bool {
+    (MIN_TICK..=MAX_TICK).contains(&tick) && tick % tick_spacing as i32 == 0
+}
+
+/// Offset of tick in the tick array, None if the array does not hold it.
+pub fn tick_offset(tick_array: &TickArray, tick: i32, tick_spacing: u16) -> Option<usize> {
+    let offset = tick.checked_sub(tick_array.start_tick_index)?;
+    if offset < 0 || offset >= tick_array_span(tick_spacing) || offset % tick_spacing as i32 != 0 {
+        return None;
+    }
+    Some((offset / tick_spacing as i32) as usize)
+}
+
+/// Applies liquidity_delta of a position to its lower or upper tick.
+pub fn update_tick(
+    tick: &mut Tick,
+    tick_index: i32,
+    tick_current: i32,
+    liquidity_delta: i128,
+    upper: bool,
+    fee_growth_global_a: u128,
+    fee_growth_global_b: u128,
+) -> Option<()> {
+    // All fees so far are assumed to have been earned below the tick
+    if tick.liquidity_gross == 0 && tick_index <= tick_current {
+        tick.fee_growth_outside_a = fee_growth_global_a;
+        tick.fee_growth_outside_b = fee_growth_global_b;
+    }
+
+    tick.liquidity_gross = if liquidity_delta >= 0 {
+        tick.liquidity_gross
+            .checked_add(liquidity_delta.unsigned_abs())?
+    } else {
+        tick.liquidity_gross
+            .checked_sub(liquidity_delta.unsigned_abs())?
+    };
+
+    // Liquidity enters the range at the lower tick and leaves it at the upper tick
+    tick.liquidity_net = if upper {
+        tick.liquidity_net.checked_sub(liquidity_delta)?
+    } else {
+        tick.liquidity_net.checked_add(liquidity_delta)?
+    };
+
+    if tick.liquidity_gross == 0 {
+        *tick = Tick::default();
+    }
+    Some(())
+}
+
+/// Flips the fee growth outside of a tick the price crosses, returns its liquidity_net.
+pub fn cross_tick(tick: &mut Tick, fee_growth_global_a: u128, fee_growth_global_b: u128) -> i128 {
+    tick.fee_growth_outside_a = fee_growth_global_a.wrapping_sub(tick.fee_growth_outside_a);
+    tick.fee_growth_outside_b = fee_growth_global_b.wrapping_sub(tick.fee_growth_outside_b);
+    tick.liquidity_net
+}
+
+/// Fee growth (a, b) inside the range [tick_lower, tick_upper].
+pub fn fee_growth_inside(
+    lower: &Tick,
+    upper: &Tick,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_current: i32,
+    fee_growth_global_a: u128,
+    fee_growth_global_b: u128,
+) -> (u128, u128) {
+    let (below_a, below_b) = if tick_current >= tick_lower {
+        (lower.fee_growth_outside_a, lower.fee_growth_outside_b)
+    } else {
+        (
+            fee_growth_global_a.wrapping_sub(lower.fee_growth_outside_a),
+            fee_growth_global_b.wrapping_sub(lower.fee_growth_outside_b),
+        )
+    };
+
+    let (above_a, above_b) = if tick_current < tick_upper {
+        (upper.fee_growth_outside_a, upper.fee_growth_outside_b)
+    } else {
+        (
+            fee_growth_global_a.wrapping_sub(upper.fee_growth_outside_a),
+            fee_growth_global_b.wrapping_sub(upper.fee_growth_outside_b),
+        )
+    };
+
+    (
+        fee_growth_global_a
+            .wrapping_sub(below_a)
+            .wrapping_sub(above_a),
+        fee_growth_global_b
+            .wrapping_sub(below_b)
+            .wrapping_sub(above_b),
+    )
+}
+
+/// Credits the fees earned since the last update to the position and applies liquidity_delta.
+pub fn update_position(
+    position: &mut Position,
+    liquidity_delta: i128,
+    fee_growth_inside_a: u128,
+    fee_growth_inside_b: u128,
+) -> Option<()> {
+    let fees_a = tick_math::mul_div(
+        position.liquidity,
+        fee_growth_inside_a.wrapping_sub(position.fee_growth_inside_a_last),
+        Q64,
+        false,
+    )?;
+    let fees_b = tick_math::mul_div(
+        position.liquidity,
+        fee_growth_inside_b.wrapping_sub(position.fee_growth_inside_b_last),
+        Q64,
+        false,
+    )?;
+
+    position.tokens_owed_a = position
+        .tokens_owed_a
+        .checked_add(u64::try_from(fees_a).ok()?)?;
+    position.tokens_owed_b = position
+        .tokens_owed_b
+        .checked_add(u64::try_from(fees_b).ok()?)?;
+    position.fee_growth_inside_a_last = fee_growth_inside_a;
+    position.fee_growth_inside_b_last = fee_growth_inside_b;
+
+    position.liquidity = if liquidity_delta >= 0 {
+        position
+            .liquidity
+            .checked_add(liquidity_delta.unsigned_abs())?
+    } else {
+        position
+            .liquidity
+            .checked_sub(liquidity_delta.unsigned_abs())?
+    };
+    Some(())
+}
+
+/// Token amounts (a, b) backing liquidity over [tick_lower, tick_upper] at the pool's price.
+pub fn amounts_for_liquidity(
+    sqrt_price: u128,
+    tick_current: i32,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<(u64, u64)> {
+    let sqrt_price_lower = tick_math::sqrt_price_at_tick(tick_lower)?;
+    let sqrt_price_upper = tick_math::sqrt_price_at_tick(tick_upper)?;
+
+    if tick_current < tick_lower {
+        // price below the range, only token a
+        let amount_a =
+            tick_math::amount_a_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+        Some((amount_a, 0))
+    } else if tick_current < tick_upper {
+        let amount_a =
+            tick_math::amount_a_delta(sqrt_price, sqrt_price_upper, liquidity, round_up)?;
+        let amount_b =
+            tick_math::amount_b_delta(sqrt_price_lower, sqrt_price, liquidity, round_up)?;
+        Some((amount_a, amount_b))
+    } else {
+        // price above the range, only token b
+        let amount_b =
+            tick_math::amount_b_delta(sqrt_price_lower, sqrt_price_upper, liquidity, round_up)?;
+        Some((0, amount_b))
+    }
+}
+
+/// Next tick to swap towards within the tick array, starting from search_tick inclusive:
+/// (tick, initialized). Falls back to the last tick of the array in the swap direction.
+pub fn next_initialized_tick(
+    tick_array: &TickArray,
+    search_tick: i32,
+    tick_spacing: u16,
+    a_to_b: bool,
+) -> Option<(i32, bool)> {
+    let offset = tick_offset(tick_array, search_tick, tick_spacing)?;
+    let tick_at = |i: usize| tick_array.start_tick_index + i as i32 * tick_spacing as i32;
+
+    if a_to_b {
+        // price moves down, search towards the start of the array
+        for i in (0..=offset).rev() {
+            if tick_array.ticks[i].liquidity_gross > 0 {
+                return Some((tick_at(i), true));
+            }
+        }
+        Some((tick_at(0), false))
+    } else {
+        // price moves up, search towards the end of the array
+        for i in offset..TICK_ARRAY_SIZE {
+            if tick_array.ticks[i].liquidity_gross > 0 {
+                return Some((tick_at(i), true));
+            }
+        }
+        Some((tick_at(TICK_ARRAY_SIZE - 1), false))
+    }
+}
+
+// Result of swapping within a range of constant liquidity
+pub struct SwapStep {
+    pub sqrt_price_next: u128, // sqrt price after the step
+    pub amount_in: u64,        // input consumed by the step, fee excluded
+    pub amount_out: u64,       // output of the step
+    pub fee: u64,              // fee charged on the step's input
+}
+
+/// Swaps up to amount_remaining (fee included) from sqrt_price towards sqrt_price_target.
+pub fn compute_swap_step(
+    sqrt_price: u128,
+    sqrt_price_target: u128,
+    liquidity: u128,
+    amount_remaining: u64,
+    fee_bps: u16,
+    a_to_b: bool,
+) -> Option<SwapStep> {
+    let fee_complement = BPS_DENOMINATOR.checked_sub(fee_bps as u64)? as u128;
+    let amount_remaining_less_fee = tick_math::mul_div(
+        amount_remaining as u128,
+        fee_complement,
+        BPS_DENOMINATOR as u128,
+        false,
+    )? as u64;
+
+    // Input needed to reach the target, too large for a u64 means out of reach
+    let amount_in_to_target = if a_to_b {
+        tick_math::amount_a_delta(sqrt_price_target, sqrt_price, liquidity, true)
+    } else {
+        tick_math::amount_b_delta(sqrt_price, sqrt_price_target, liquidity, true)
+    }
+    .unwrap_or(u64::MAX);
+
+    let reached = amount_remaining_less_fee >= amount_in_to_target;
+    let sqrt_price_next = if reached {
+        sqrt_price_target
+    } else if a_to_b {
+        tick_math::next_sqrt_price_from_amount_a(sqrt_price, liquidity, amount_remaining_less_fee)?
+    } else {
+        tick_math::next_sqrt_price_from_amount_b(sqrt_price, liquidity, amount_remaining_less_fee)?
+    };
+
+    // A step ending mid-range consumes all of the input its price was derived from
+    let amount_in = if reached {
+        amount_in_to_target
+    } else {
+        amount_remaining_less_fee
+    };
+    let amount_out = if a_to_b {
+        tick_math::amount_b_delta(sqrt_price_next, sqrt_price, liquidity, false)?
+    } else {
+        tick_math::amount_a_delta(sqrt_price, sqrt_price_next, liquidity, false)?
+    };
+
+    // The fee is charged on the input actually used, or takes the rest when the step ends mid-range
+    let fee = if reached {
+        tick_math::mul_div(amount_in as u128, fee_bps as u128, fee_complement, true)? as u64
+    } else {
+        amount_remaining.checked_sub(amount_in)?
+    };
+
+    Some(SwapStep {
+        sqrt_price_next,
+        amount_in,
+        amount_out,
+        fee,
+    })
+}
@@ -0,0 +1,136 @@
+// Pricing curves for the pools.
+//
+// Constant product pools price with x * y = k. StableSwap pools price with the StableSwap
+// invariant on balances scaled to the larger of the two token decimals, with an amplification
+// coefficient the admin can ramp linearly between two points in time.
+
+use crate::{
+    math, oracle,
+    state::pool::{CurveType, Pool},
+    tick_math,
+};
+use anchor_lang::prelude::*;
+
+/// Amplification coefficient at now, interpolated linearly while a ramp is in progress.
+pub fn current_amp(pool: &Pool, now: i64) -> u64 {
+    if now >= pool.future_amp_time || pool.future_amp_time <= pool.initial_amp_time {
+        return pool.future_amp;
+    }
+
+    let elapsed = now.saturating_sub(pool.initial_amp_time).max(0) as u128;
+    let duration = (pool.future_amp_time - pool.initial_amp_time) as u128;
+    let initial_amp = pool.initial_amp as u128;
+    let future_amp = pool.future_amp as u128;
+
+    let amp = if future_amp > initial_amp {
+        initial_amp + (future_amp - initial_amp) * elapsed / duration
+    } else {
+        initial_amp - (initial_amp - future_amp) * elapsed / duration
+    };
+    amp as u64
+}
+
+// Multipliers (token_in, token_out) that scale both balances to the same precision
+fn multipliers(pool: &Pool, token_in: &Pubkey) -> Option<(u128, u128)> {
+    let (decimals_in, decimals_out) = if *token_in == pool.token_a {
+        (pool.decimals_a, pool.decimals_b)
+    } else {
+        (pool.decimals_b, pool.decimals_a)
+    };
+    let decimals = decimals_in.max(decimals_out);
+
+    Some((
+        10u128.checked_pow((decimals - decimals_in) as u32)?,
+        10u128.checked_pow((decimals - decimals_out) as u32)?,
+    ))
+}
+
+/// Q64.64 marginal prices (price_a, price_b) on the pool's curve at the reserves, None if either
+/// reserve is empty. Constant product pools price at the reserve ratio.
+pub fn prices(pool: &Pool, reserve_a: u64, reserve_b: u64, now: i64) -> Option<(u128, u128)> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => oracle::prices(reserve_a, reserve_b),
+        CurveType::StableSwap => {
+            if reserve_a == 0 || reserve_b == 0 {
+                return None;
+            }
+
+            let amp = current_amp(pool, now);
+            let (multiplier_a, multiplier_b) = multipliers(pool, &pool.token_a)?;
+            let x = (reserve_a as u128).checked_mul(multiplier_a)?;
+            let y = (reserve_b as u128).checked_mul(multiplier_b)?;
+
+            // prices of the scaled balances, back to the tokens' own decimals
+            let price_a = math::stable_price(amp, x, y)?;
+            let price_b = math::stable_price(amp, y, x)?;
+            Some((
+                tick_math::mul_div(price_a, multiplier_a, multiplier_b, false)?,
+                tick_math::mul_div(price_b, multiplier_b, multiplier_a, false)?,
+            ))
+        }
+    }
+}
+
+/// Output for an (after fee) input amount of token_in on the pool's curve, rounded down.
+pub fn get_amount_out(
+    pool: &Pool,
+    token_in: &Pubkey,
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    now: i64,
+) -> Option<u64> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => math::get_amount_out(amount_in, reserve_in, reserve_out),
+        CurveType::StableSwap => {
+            let amp = current_amp(pool, now);
+            let (multiplier_in, multiplier_out) = multipliers(pool, token_in)?;
+            let x = (reserve_in as u128).checked_mul(multiplier_in)?;
+            let y = (reserve_out as u128).checked_mul(multiplier_out)?;
+            let d = math::stable_invariant(amp, x, y)?;
+
+            let new_x = x.checked_add((amount_in as u128).checked_mul(multiplier_in)?)?;
+            let new_y = math::stable_balance(amp, new_x, d)?;
+
+            // one unit less in favour of the pool, for the rounding of the iterations
+            let amount_out = y.checked_sub(new_y)?.saturating_sub(1) / multiplier_out;
+            u64::try_from(amount_out).ok()
+        }
+    }
+}
+
+/// (After fee) input of token_in required for an exact output amount on the pool's curve,
+/// rounded up.
+pub fn get_amount_in(
+    pool: &Pool,
+    token_in: &Pubkey,
+    amount_out: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+    now: i64,
+) -> Option<u64> {
+    match pool.curve_type {
+        CurveType::ConstantProduct => math::get_amount_in(amount_out, reserve_in, reserve_out),
+        CurveType::StableSwap => {
+            if amount_out >= reserve_out {
+                return None;
+            }
+
+            let amp = current_amp(pool, now);
+            let (multiplier_in, multiplier_out) = multipliers(pool, token_in)?;
+            let x = (reserve_in as u128).checked_mul(multiplier_in)?;
+            let y = (reserve_out as u128).checked_mul(multiplier_out)?;
+            let d = math::stable_invariant(amp, x, y)?;
+
+            let new_y = y.checked_sub((amount_out as u128).checked_mul(multiplier_out)?)?;
+            let new_x = math::stable_balance(amp, new_y, d)?;
+
+            let amount_in = new_x
+                .checked_sub(x)?
+                .checked_add(1)?
+                .checked_add(multiplier_in - 1)?
+                / multiplier_in;
+            u64::try_from(amount_in).ok()
+        }
+    }
+}
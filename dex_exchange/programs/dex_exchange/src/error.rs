@@ -32,6 +32,118 @@ pub enum DexError {
     #[msg("Insufficient funds.")]
     InsufficientFunds,
 
+    // liquidity
+    #[msg("Insufficient liquidity minted.")]
+    InsufficientLiquidityMinted,
+    #[msg("Insufficient liquidity burned.")]
+    InsufficientLiquidityBurned,
+    #[msg("Insufficient liquidity shares.")]
+    InsufficientShares,
+    #[msg("Invalid lp token mint.")]
+    InvalidLpMint,
+    #[msg("Output amount is below the minimum amount.")]
+    SlippageExceeded,
+    #[msg("Token account does not match the pool.")]
+    InvalidTokenAccount,
+    #[msg("Token mint does not match the pool.")]
+    InvalidTokenMint,
+    #[msg("Token account is not the pool vault.")]
+    InvalidVault,
+    #[msg("Pool tokens must be two different mints, sorted by address.")]
+    InvalidTokenPair,
+    #[msg("Pool registry is full.")]
+    PoolRegistryFull,
+
+    // fees
+    #[msg("Invalid fee basis points.")]
+    InvalidFee,
+    #[msg("Liquidity provider holds fee checkpoints for too many pools.")]
+    TooManyProviderPools,
+
+    // concentrated liquidity
+    #[msg("Invalid tick spacing.")]
+    InvalidTickSpacing,
+    #[msg("Tick out of range or not a multiple of the tick spacing.")]
+    InvalidTick,
+    #[msg("Lower tick must be below the upper tick.")]
+    InvalidTickRange,
+    #[msg("Invalid tick array.")]
+    InvalidTickArray,
+    #[msg("The tick arrays passed do not cover the swap.")]
+    TickArrayMissing,
+    #[msg("Invalid sqrt price.")]
+    InvalidSqrtPrice,
+    #[msg("Invalid sqrt price limit.")]
+    InvalidSqrtPriceLimit,
+    #[msg("Position does not belong to the pool.")]
+    InvalidPosition,
+    #[msg("Insufficient position liquidity.")]
+    InsufficientPositionLiquidity,
+
+    // order book
+    #[msg("Invalid order book account.")]
+    InvalidOrderBook,
+    #[msg("Invalid event queue account.")]
+    InvalidEventQueue,
+    #[msg("Invalid open orders account.")]
+    InvalidOpenOrders,
+    #[msg("Order book is full.")]
+    OrderBookFull,
+    #[msg("Event queue is full, match_orders must consume it first.")]
+    EventQueueFull,
+    #[msg("Post-only order would match.")]
+    OrderWouldMatch,
+    #[msg("Order not found.")]
+    OrderNotFound,
+    #[msg("Order belongs to another open orders account.")]
+    InvalidOrderOwner,
+
+    // curve
+    #[msg("Operation not supported by the pool curve.")]
+    InvalidCurve,
+    #[msg("Invalid amplification coefficient.")]
+    InvalidAmp,
+    #[msg("Invalid amplification ramp.")]
+    InvalidAmpRamp,
+
+    // oracle
+    #[msg("Invalid observations account.")]
+    InvalidObservations,
+    #[msg("No observation old enough for the requested window.")]
+    OracleWindowUnavailable,
+
+    // swap
+    #[msg("Token in is not one of the pool tokens.")]
+    InvalidTokenIn,
+    #[msg("Insufficient output amount.")]
+    InsufficientOutputAmount,
+    #[msg("Insufficient liquidity in the pool.")]
+    InsufficientLiquidity,
+    #[msg("Input amount exceeds the maximum amount.")]
+    ExcessiveInputAmount,
+    #[msg("Swap deadline has passed.")]
+    DeadlineExceeded,
+    #[msg("Invalid route: legs must chain token out to token in, up to 4 pools.")]
+    InvalidRoute,
+
+    // flash loan
+    #[msg("A flash loan is outstanding on the pool.")]
+    FlashLoanActive,
+    #[msg("No flash loan is outstanding on the pool.")]
+    FlashLoanNotActive,
+    #[msg("Flash borrow must be followed by a flash repay of the same pool in the transaction.")]
+    FlashRepayMissing,
+    #[msg("Flash loans can not be taken through a cross-program invocation.")]
+    FlashLoanCpi,
+
+    // rewards
+    #[msg("Reward schedule must end after it starts and in the future.")]
+    InvalidRewardSchedule,
+    #[msg("Stake position does not belong to the reward schedule.")]
+    InvalidStakePosition,
+    #[msg("Insufficient staked lp tokens.")]
+    InsufficientStake,
+
     // liquidity provider
     #[msg("Liquidity provider has no active status.")]
     InvalidLiquidityProviderStatus,
@@ -42,9 +154,25 @@ pub enum DexError {
     #[msg("Trader has no active status.")]
     InvalidTraderStatus,
 
+    // dex configs
+    #[msg("Instruction category is paused.")]
+    Paused,
+    #[msg("Swap moves the price more than the circuit breaker allows.")]
+    PriceMoveExceeded,
+
+    // pool admin
+    #[msg("Signer is not the proposed pool admin.")]
+    InvalidPendingOwner,
+
+    // allowlist
+    #[msg("Wallet is not on the pool allowlist.")]
+    NotAllowlisted,
+
     // account
     #[msg("Account is not initialized.")]
     AccountNotInitialized,
     #[msg("Account is already initialized.")]
     AccountAlreadyInitialized,
+    #[msg("Account balances must be zero to close it.")]
+    NonZeroBalance,
 }
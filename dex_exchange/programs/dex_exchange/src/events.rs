@@ -0,0 +1,407 @@
+// Events emitted by the instruction handlers, for indexers.
+//
+// Amounts are in base units of their mint. Events are emitted once the handler's state changes
+// are made, a failing instruction emits nothing.
+
+use crate::state::{market::Side, pool::CurveType};
+use anchor_lang::prelude::*;
+
+// admin
+
+#[event]
+pub struct DexInitialized {
+    pub owner: Pubkey,
+    pub pool_registry: Pubkey,
+    pub dex_configs: Pubkey,
+}
+
+#[event]
+pub struct PoolInitialized {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub lp_mint: Pubkey,
+    pub fee_tier: u16,
+    pub fee_bps: u16,
+    pub protocol_fee_bps: u16,
+    pub curve_type: CurveType,
+    pub amp: u64,
+}
+
+#[event]
+pub struct ConcentratedPoolInitialized {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub tick_spacing: u16,
+    pub fee_bps: u16,
+    pub sqrt_price: u128,
+    pub tick_current: i32,
+}
+
+#[event]
+pub struct MarketCreated {
+    pub market: Pubkey,
+    pub owner: Pubkey,
+    pub base_mint: Pubkey,
+    pub quote_mint: Pubkey,
+    pub base_lot_size: u64,
+}
+
+#[event]
+pub struct PoolFeesUpdated {
+    pub pool: Pubkey,
+    pub fee_bps: u16,
+    pub protocol_fee_bps: u16,
+}
+
+#[event]
+pub struct AmpRampStarted {
+    pub pool: Pubkey,
+    pub initial_amp: u64,
+    pub future_amp: u64,
+    pub initial_amp_time: i64,
+    pub future_amp_time: i64,
+}
+
+#[event]
+pub struct AmpRampStopped {
+    pub pool: Pubkey,
+    pub amp: u64,
+}
+
+#[event]
+pub struct RewardScheduleCreated {
+    pub reward_schedule: Pubkey,
+    pub pool: Pubkey,
+    pub reward_mint: Pubkey,
+    pub emission_rate: u64,
+    pub start_time: i64,
+    pub end_time: i64,
+    pub total_rewards: u64,
+}
+
+#[event]
+pub struct TraderStatusUpdated {
+    pub trader: Pubkey,
+    pub owner: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct LiquidityProviderStatusUpdated {
+    pub liquidity_provider: Pubkey,
+    pub owner: Pubkey,
+    pub active: bool,
+}
+
+#[event]
+pub struct PoolAllowlistUpdated {
+    pub pool: Pubkey,
+    pub enabled: bool,
+}
+
+#[event]
+pub struct AllowlistEntryAdded {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct AllowlistEntryRemoved {
+    pub pool: Pubkey,
+    pub wallet: Pubkey,
+}
+
+#[event]
+pub struct PauseFlagsUpdated {
+    pub swaps: bool,
+    pub liquidity: bool,
+    pub flash_loans: bool,
+    pub orders: bool,
+}
+
+#[event]
+pub struct CircuitBreakerUpdated {
+    pub max_price_move_bps: u16,
+}
+
+#[event]
+pub struct AdminProposed {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub pending_owner: Pubkey,
+}
+
+#[event]
+pub struct AdminAccepted {
+    pub pool: Pubkey,
+    pub previous_owner: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct ProtocolFeesCollected {
+    pub pool: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct TreasuryWithdrawn {
+    pub mint: Pubkey,
+    pub recipient: Pubkey,
+    pub amount: u64,
+}
+
+// liquidity providers and traders
+
+#[event]
+pub struct LiquidityProviderRegistered {
+    pub liquidity_provider: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct LiquidityProviderClosed {
+    pub liquidity_provider: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct TraderRegistered {
+    pub trader: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct TraderClosed {
+    pub trader: Pubkey,
+    pub owner: Pubkey,
+    pub swap_count: u64,
+}
+
+// liquidity pools
+
+#[event]
+pub struct LiquidityAdded {
+    pub pool: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub shares: u64, // lp tokens minted
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub pool: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub shares: u64, // lp tokens burned
+    pub reserve_a: u64,
+    pub reserve_b: u64,
+    pub total_shares: u64,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub pool: Pubkey,
+    pub liquidity_provider: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+// Emitted by swap, swap_exact_out and by swap_route for each leg
+#[event]
+pub struct Swapped {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64, // fee included
+    pub amount_out: u64,
+    pub fee: u64,
+    pub protocol_fee: u64, // share of fee kept by the protocol
+    pub reserve_a: u64,    // reserves after the swap
+    pub reserve_b: u64,
+}
+
+#[event]
+pub struct RouteSwapped {
+    pub trader: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub hops: u8,
+}
+
+#[event]
+pub struct FlashBorrowed {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub token: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct FlashRepaid {
+    pub pool: Pubkey,
+    pub token: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
+
+// liquidity mining
+
+#[event]
+pub struct StakeOpened {
+    pub stake_position: Pubkey,
+    pub reward_schedule: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct LpTokensStaked {
+    pub stake_position: Pubkey,
+    pub reward_schedule: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct LpTokensUnstaked {
+    pub stake_position: Pubkey,
+    pub reward_schedule: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct RewardsClaimed {
+    pub stake_position: Pubkey,
+    pub reward_schedule: Pubkey,
+    pub amount: u64,
+}
+
+// concentrated liquidity pools
+
+#[event]
+pub struct TickArrayInitialized {
+    pub pool: Pubkey,
+    pub tick_array: Pubkey,
+    pub start_tick_index: i32,
+}
+
+#[event]
+pub struct PositionOpened {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub owner: Pubkey,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+}
+
+// Emitted by increase_liquidity and decrease_liquidity
+#[event]
+pub struct PositionLiquidityUpdated {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub liquidity_delta: i128,
+    pub amount_a: u64,
+    pub amount_b: u64,
+    pub liquidity: u128, // position liquidity after the update
+}
+
+#[event]
+pub struct PositionFeesCollected {
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub amount_a: u64,
+    pub amount_b: u64,
+}
+
+#[event]
+pub struct ConcentratedSwapped {
+    pub pool: Pubkey,
+    pub trader: Pubkey,
+    pub token_in: Pubkey,
+    pub token_out: Pubkey,
+    pub amount_in: u64, // fee included
+    pub amount_out: u64,
+    pub sqrt_price: u128, // pool state after the swap
+    pub tick_current: i32,
+    pub liquidity: u128,
+}
+
+// order book markets
+
+#[event]
+pub struct OpenOrdersInitialized {
+    pub market: Pubkey,
+    pub open_orders: Pubkey,
+    pub owner: Pubkey,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub market: Pubkey,
+    pub open_orders: Pubkey,
+    pub order_id: u64,
+    pub side: Side,
+    pub price: u64,
+    pub quantity: u64, // lots ordered
+    pub filled: u64,   // lots filled on placement
+    pub posted: u64,   // lots resting on the book
+}
+
+#[event]
+pub struct OrderCancelled {
+    pub market: Pubkey,
+    pub open_orders: Pubkey,
+    pub order_id: u64,
+    pub side: Side,
+    pub quantity: u64, // lots released
+}
+
+// Emitted by match_orders for each fill credited to a maker
+#[event]
+pub struct OrderFilled {
+    pub market: Pubkey,
+    pub maker: Pubkey, // open orders account of the maker
+    pub maker_order_id: u64,
+    pub maker_side: Side,
+    pub price: u64,
+    pub quantity: u64,
+}
+
+#[event]
+pub struct FundsSettled {
+    pub market: Pubkey,
+    pub open_orders: Pubkey,
+    pub base_amount: u64,
+    pub quote_amount: u64,
+}
+
+// test tokens
+
+#[event]
+pub struct TokenCreated {
+    pub mint: Pubkey,
+    pub owner: Pubkey,
+    pub decimals: u8,
+    pub amount: u64,
+}
+
+#[event]
+pub struct TokenTransferred {
+    pub from: Pubkey,
+    pub to: Pubkey,
+    pub amount: u64,
+}
@@ -0,0 +1,109 @@
+// Liquidity provider fee accounting for the pools.
+//
+// The liquidity providers' share of the swap and flash loan fees is set aside from the reserves
+// and tracked per fee share in Q64.64, like the concentrated pools' fee growth it is allowed to
+// wrap. Fee shares are the shares a provider deposited through add_liquidity; lp tokens received
+// by transfer earn no fees and any holder can redeem them, remove_liquidity only drops the fee
+// shares the burned lp tokens cover. Without any fee shares the fees stay in the reserves.
+
+use crate::{
+    state::{
+        liquidity_provider::{FeeCheckpoint, LiquidityProvider, MAX_PROVIDER_POOLS},
+        pool::Pool,
+    },
+    tick_math::{self, Q64},
+};
+use anchor_lang::prelude::*;
+
+/// Credits amount of token to the liquidity providers' fees of the pool.
+pub fn accrue_lp_fee(pool: &mut Pool, token: &Pubkey, amount: u64) -> Option<()> {
+    if amount == 0 {
+        return Some(());
+    }
+
+    let is_token_a = *token == pool.token_a;
+
+    if pool.fee_shares == 0 {
+        if is_token_a {
+            pool.reserve_a = pool.reserve_a.checked_add(amount)?;
+        } else {
+            pool.reserve_b = pool.reserve_b.checked_add(amount)?;
+        }
+        return Some(());
+    }
+
+    let fee_growth = ((amount as u128) << 64) / pool.fee_shares as u128;
+    if is_token_a {
+        pool.lp_fees_a = pool.lp_fees_a.checked_add(amount)?;
+        pool.fee_growth_a = pool.fee_growth_a.wrapping_add(fee_growth);
+    } else {
+        pool.lp_fees_b = pool.lp_fees_b.checked_add(amount)?;
+        pool.fee_growth_b = pool.fee_growth_b.wrapping_add(fee_growth);
+    }
+    Some(())
+}
+
+/// Index of the provider's checkpoint for the pool, None if it has none.
+pub fn checkpoint_index(liquidity_provider: &LiquidityProvider, pool: &Pubkey) -> Option<usize> {
+    liquidity_provider
+        .fee_checkpoints
+        .iter()
+        .position(|x| x.pool == *pool)
+}
+
+/// Index of the provider's checkpoint for the pool, adding one if it has none.
+/// None if the provider already holds checkpoints for MAX_PROVIDER_POOLS pools.
+pub fn checkpoint_index_or_insert(
+    liquidity_provider: &mut LiquidityProvider,
+    pool: &Pool,
+    pool_key: &Pubkey,
+) -> Option<usize> {
+    if let Some(index) = checkpoint_index(liquidity_provider, pool_key) {
+        return Some(index);
+    }
+
+    if liquidity_provider.fee_checkpoints.len() >= MAX_PROVIDER_POOLS {
+        return None;
+    }
+
+    liquidity_provider.fee_checkpoints.push(FeeCheckpoint {
+        pool: *pool_key,
+        fee_growth_a_last: pool.fee_growth_a,
+        fee_growth_b_last: pool.fee_growth_b,
+        ..FeeCheckpoint::default()
+    });
+    Some(liquidity_provider.fee_checkpoints.len() - 1)
+}
+
+/// Credits the fees earned by the checkpoint's shares since it was last updated.
+pub fn update_checkpoint(checkpoint: &mut FeeCheckpoint, pool: &Pool) -> Option<()> {
+    let fees_a = tick_math::mul_div(
+        checkpoint.shares as u128,
+        pool.fee_growth_a.wrapping_sub(checkpoint.fee_growth_a_last),
+        Q64,
+        false,
+    )?;
+    let fees_b = tick_math::mul_div(
+        checkpoint.shares as u128,
+        pool.fee_growth_b.wrapping_sub(checkpoint.fee_growth_b_last),
+        Q64,
+        false,
+    )?;
+
+    checkpoint.fees_owed_a = checkpoint
+        .fees_owed_a
+        .checked_add(u64::try_from(fees_a).ok()?)?;
+    checkpoint.fees_owed_b = checkpoint
+        .fees_owed_b
+        .checked_add(u64::try_from(fees_b).ok()?)?;
+    checkpoint.fee_growth_a_last = pool.fee_growth_a;
+    checkpoint.fee_growth_b_last = pool.fee_growth_b;
+    Some(())
+}
+
+/// Drops the provider's checkpoints without shares or fees owed, freeing their slots.
+pub fn prune_checkpoints(liquidity_provider: &mut LiquidityProvider) {
+    liquidity_provider
+        .fee_checkpoints
+        .retain(|x| x.shares > 0 || x.fees_owed_a > 0 || x.fees_owed_b > 0);
+}
@@ -1,17 +1,66 @@
 // admin instructions
+pub mod add_to_allowlist;
+pub mod collect_protocol_fees;
+pub mod create_concentrated_pool;
+pub mod create_market;
+pub mod create_pool;
+pub mod create_reward_schedule;
 pub mod init;
+pub mod propose_admin;
+pub mod ramp_amp;
+pub mod remove_from_allowlist;
+pub mod set_paused;
+pub mod set_pool_allowlist;
+pub mod suspend_liquidity_provider;
+pub mod suspend_trader;
+pub mod update_pool_fees;
+pub mod withdraw_treasury;
 //pub mod register_lottery_game;
 
 // public instructions
+pub mod accept_admin;
 pub mod add_liquidity;
+pub mod cancel_order;
+pub mod claim_fees;
+pub mod claim_rewards;
+pub mod close_liquidity_provider;
+pub mod close_trader;
+pub mod collect_position_fees;
 pub mod create_token;
+pub mod decrease_liquidity;
+pub mod flash_borrow;
+pub mod flash_repay;
+pub mod get_twap;
+pub mod increase_liquidity;
+pub mod init_open_orders;
+pub mod initialize_tick_array;
+pub mod match_orders;
+pub mod open_position;
+pub mod open_stake;
+pub mod place_order;
 pub mod register_liquidity_provider;
 pub mod register_trader;
+pub mod remove_liquidity;
+pub mod settle_funds;
+pub mod stake_lp_tokens;
 pub mod swap;
+pub mod swap_concentrated;
+pub mod swap_exact_out;
+pub mod swap_route;
 pub mod transfer_token;
+pub mod unstake_lp_tokens;
 
 // bring everything in scope
 pub use {
-    add_liquidity::*, create_token::*, init::*, register_liquidity_provider::*, register_trader::*,
-    swap::*, transfer_token::*,
+    accept_admin::*, add_liquidity::*, add_to_allowlist::*, cancel_order::*, claim_fees::*,
+    claim_rewards::*, close_liquidity_provider::*, close_trader::*, collect_position_fees::*,
+    collect_protocol_fees::*, create_concentrated_pool::*, create_market::*, create_pool::*,
+    create_reward_schedule::*, create_token::*, decrease_liquidity::*, flash_borrow::*,
+    flash_repay::*, get_twap::*, increase_liquidity::*, init::*, init_open_orders::*,
+    initialize_tick_array::*, match_orders::*, open_position::*, open_stake::*, place_order::*,
+    propose_admin::*, ramp_amp::*, register_liquidity_provider::*, register_trader::*,
+    remove_from_allowlist::*, remove_liquidity::*, set_paused::*, set_pool_allowlist::*,
+    settle_funds::*, stake_lp_tokens::*, suspend_liquidity_provider::*, suspend_trader::*, swap::*,
+    swap_concentrated::*, swap_exact_out::*, swap_route::*, transfer_token::*,
+    unstake_lp_tokens::*, update_pool_fees::*, withdraw_treasury::*,
 };
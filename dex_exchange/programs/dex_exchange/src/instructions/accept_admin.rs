@@ -0,0 +1,33 @@
+//! AcceptAdmin instruction handler
+
+use {
+    crate::{error::DexError, events::AdminAccepted, state::pool::Pool},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    #[account(mut,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = liquidity_pool.pending_owner == pending_owner.key() @ DexError::InvalidPendingOwner
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    pub pending_owner: Signer<'info>, // pool admin proposed by the owner
+}
+
+pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+
+    let previous_owner = liquidity_pool.owner;
+
+    liquidity_pool.owner = liquidity_pool.pending_owner;
+    liquidity_pool.pending_owner = Pubkey::default();
+
+    emit!(AdminAccepted {
+        pool: liquidity_pool.key(),
+        previous_owner,
+        owner: liquidity_pool.owner,
+    });
+
+    Ok(())
+}
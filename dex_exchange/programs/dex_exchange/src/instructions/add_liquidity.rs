@@ -3,36 +3,63 @@
 use {
     crate::{
         error::DexError,
-        state::{liquidity_provider::LiquidityProvider, pool::Pool},
+        events::LiquidityAdded,
+        fees,
+        instructions::set_pool_allowlist::check_allowlist,
+        math, oracle,
+        state::{
+            allowlist_entry::AllowlistEntry,
+            configs::DexConfigs,
+            liquidity_provider::LiquidityProvider,
+            observations::Observations,
+            pool::{Pool, MINIMUM_LIQUIDITY},
+        },
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token::{mint_to, transfer_checked, Mint, MintTo, Token, TokenAccount, TransferChecked},
     },
 };
 
 #[derive(Accounts)]
 #[instruction(params: AddLiquidityParams)]
 pub struct AddLiquidity<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.liquidity_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
     #[account(mut,
-        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
     )]
     pub liquidity_pool: Account<'info, Pool>,
+    #[account(mut, address = liquidity_pool.observations @ DexError::InvalidObservations)]
+    pub observations: Box<Account<'info, Observations>>,
     #[account(mut,has_one = owner,
         constraint = liquidity_provider.active @ DexError::InvalidLiquidityProviderStatus
     )]
     pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // required when the pool is in allowlist mode
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
     #[account(mut)]
     pub sender_tokens_a: Account<'info, TokenAccount>,
     #[account(mut)]
     pub sender_tokens_b: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub recipient_tokens_a: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub recipient_tokens_b: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub mint_token: Account<'info, Mint>,
+    #[account(mut, address = liquidity_pool.vault_a @ DexError::InvalidVault)]
+    pub pool_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut, address = liquidity_pool.vault_b @ DexError::InvalidVault)]
+    pub pool_tokens_b: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_a @ DexError::InvalidTokenMint)]
+    pub mint_token_a: Account<'info, Mint>,
+    #[account(address = liquidity_pool.token_b @ DexError::InvalidTokenMint)]
+    pub mint_token_b: Account<'info, Mint>,
+    #[account(mut, address = liquidity_pool.lp_mint @ DexError::InvalidLpMint)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_lp_tokens.mint == lp_mint.key() @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_lp_tokens: Account<'info, TokenAccount>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -43,8 +70,8 @@ pub struct AddLiquidity<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct AddLiquidityParams {
-    pub amount_a: u32,
-    pub amount_b: u32,
+    pub amount_a: u64, // amount of token a, in base units
+    pub amount_b: u64, // amount of token b, in base units
 }
 
 pub fn add_liquidity(ctx: Context<AddLiquidity>, params: &AddLiquidityParams) -> Result<()> {
@@ -57,11 +84,21 @@ pub fn add_liquidity(ctx: Context<AddLiquidity>, params: &AddLiquidityParams) ->
         return Err(DexError::InvalidAmount.into());
     }
 
+    check_allowlist(
+        &ctx.accounts.liquidity_pool,
+        &ctx.accounts.allowlist_entry,
+        ctx.accounts.owner.key,
+    )?;
+
     let sender = &ctx.accounts.owner;
     let sender_tokens_a = &ctx.accounts.sender_tokens_a;
     let sender_tokens_b = &ctx.accounts.sender_tokens_b;
-    let recipient_tokens_a = &ctx.accounts.recipient_tokens_a;
-    let recipient_tokens_b = &ctx.accounts.recipient_tokens_b;
+    let pool_tokens_a = &ctx.accounts.pool_tokens_a;
+    let pool_tokens_b = &ctx.accounts.pool_tokens_b;
+    let mint_token_a = &ctx.accounts.mint_token_a;
+    let mint_token_b = &ctx.accounts.mint_token_b;
+    let lp_mint = &ctx.accounts.lp_mint;
+    let recipient_lp_tokens = &ctx.accounts.recipient_lp_tokens;
     let token_program = &ctx.accounts.token_program;
     let liquidity_pool = &mut ctx.accounts.liquidity_pool;
     let liquidity_provider = &mut ctx.accounts.liquidity_provider;
@@ -69,9 +106,16 @@ pub fn add_liquidity(ctx: Context<AddLiquidity>, params: &AddLiquidityParams) ->
     let token_b_reserve = liquidity_pool.reserve_b;
     let reserve_a_available_funds = liquidity_provider.reserve_a_available_funds;
     let reserve_b_available_funds = liquidity_provider.reserve_b_available_funds;
-    let decimals = liquidity_pool.decimals as u64;
     let amount_a = params.amount_a;
     let amount_b = params.amount_b;
+    let total_shares = liquidity_pool.total_shares;
+
+    // Accumulate the price oracle before the reserves move
+    oracle::update(
+        liquidity_pool,
+        &mut ctx.accounts.observations,
+        Clock::get()?.unix_timestamp,
+    );
 
     // Increment reserve_a with amount_a
     liquidity_pool.reserve_a = token_a_reserve
@@ -93,51 +137,127 @@ pub fn add_liquidity(ctx: Context<AddLiquidity>, params: &AddLiquidityParams) ->
         .checked_add(amount_b)
         .ok_or(DexError::InvalidArithmeticOperation)?;
 
-    let base: u32 = 10;
-    let exponent = liquidity_pool.decimals as u32;
+    // LP tokens to mint for this deposit:
+    // the first deposit gets sqrt(amount_a * amount_b) less the locked minimum liquidity,
+    // later deposits get the smaller of their proportional contributions to each reserve
+    let liquidity = if total_shares == 0 {
+        let liquidity = math::sqrt((amount_a as u128) * (amount_b as u128));
+        let liquidity =
+            u64::try_from(liquidity).map_err(|_| DexError::InvalidArithmeticOperation)?;
 
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let amount_a = (amount_a as u64)
-        .checked_mul(result as u64)
-        .ok_or(DexError::InvalidArithmeticOperation)?;
+        // MINIMUM_LIQUIDITY is counted in total_shares but never minted, so the pool can never be drained to zero
+        liquidity_pool.total_shares = liquidity;
+        liquidity
+            .checked_sub(MINIMUM_LIQUIDITY)
+            .ok_or(DexError::InsufficientLiquidityMinted)?
+    } else {
+        let liquidity_a = (amount_a as u128)
+            .checked_mul(total_shares as u128)
+            .ok_or(DexError::InvalidArithmeticOperation)?
+            .checked_div(token_a_reserve as u128)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+        let liquidity_b = (amount_b as u128)
+            .checked_mul(total_shares as u128)
+            .ok_or(DexError::InvalidArithmeticOperation)?
+            .checked_div(token_b_reserve as u128)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+        let liquidity = u64::try_from(liquidity_a.min(liquidity_b))
+            .map_err(|_| DexError::InvalidArithmeticOperation)?;
+
+        // Increment total_shares with liquidity
+        liquidity_pool.total_shares = total_shares
+            .checked_add(liquidity)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+        liquidity
+    };
+
+    if liquidity == 0 {
+        return Err(DexError::InsufficientLiquidityMinted.into());
+    }
 
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let amount_b = (amount_b as u64)
-        .checked_mul(result as u64)
+    // Credit the fees earned so far before the provider's fee shares change
+    let pool_key = liquidity_pool.key();
+    let index = fees::checkpoint_index_or_insert(liquidity_provider, liquidity_pool, &pool_key)
+        .ok_or(DexError::TooManyProviderPools)?;
+    let checkpoint = &mut liquidity_provider.fee_checkpoints[index];
+    fees::update_checkpoint(checkpoint, liquidity_pool)
         .ok_or(DexError::InvalidArithmeticOperation)?;
 
-    // check if liquidity provider exists before adding
-    liquidity_pool.liquidity_providers.push(*sender.key);
+    // Increment the fee shares with liquidity
+    checkpoint.shares = checkpoint
+        .shares
+        .checked_add(liquidity)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    liquidity_pool.fee_shares = liquidity_pool
+        .fee_shares
+        .checked_add(liquidity)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
 
     // token a
-    transfer(
+    transfer_checked(
         CpiContext::new(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: sender_tokens_a.to_account_info(),
-                to: recipient_tokens_a.to_account_info(),
+                mint: mint_token_a.to_account_info(),
+                to: pool_tokens_a.to_account_info(),
                 authority: sender.to_account_info(),
             },
         ),
         amount_a,
+        mint_token_a.decimals,
     )?;
 
     // token b
-    transfer(
+    transfer_checked(
         CpiContext::new(
             token_program.to_account_info(),
-            Transfer {
+            TransferChecked {
                 from: sender_tokens_b.to_account_info(),
-                to: recipient_tokens_b.to_account_info(),
+                mint: mint_token_b.to_account_info(),
+                to: pool_tokens_b.to_account_info(),
                 authority: sender.to_account_info(),
             },
         ),
         amount_b,
+        mint_token_b.decimals,
+    )?;
+
+    let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+    let seeds = &[
+        b"liquidity-pool",
+        liquidity_pool.token_a.as_ref(),
+        liquidity_pool.token_b.as_ref(),
+        fee_tier.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // lp tokens
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: lp_mint.to_account_info(),
+                to: recipient_lp_tokens.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            signer,
+        ),
+        liquidity,
     )?;
 
+    emit!(LiquidityAdded {
+        pool: liquidity_pool.key(),
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        amount_a,
+        amount_b,
+        shares: liquidity,
+        reserve_a: liquidity_pool.reserve_a,
+        reserve_b: liquidity_pool.reserve_b,
+        total_shares: liquidity_pool.total_shares,
+    });
+
     Ok(())
 }
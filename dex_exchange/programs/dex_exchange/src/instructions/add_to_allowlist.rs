@@ -0,0 +1,57 @@
+//! AddToAllowlist instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::AllowlistEntryAdded,
+        state::{allowlist_entry::AllowlistEntry, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: AddToAllowlistParams)]
+pub struct AddToAllowlist<'info> {
+    #[account(has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    // init means to create account
+    // bump to use unique address for account
+    // one entry per pool and wallet
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + AllowlistEntry::INIT_SPACE,
+        seeds = [
+            b"allowlist",
+            liquidity_pool.key().as_ref(),
+            params.wallet.as_ref()
+        ],
+        bump
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct AddToAllowlistParams {
+    pub wallet: Pubkey, // wallet to allow
+}
+
+pub fn add_to_allowlist(ctx: Context<AddToAllowlist>, params: &AddToAllowlistParams) -> Result<()> {
+    let allowlist_entry = &mut ctx.accounts.allowlist_entry;
+
+    allowlist_entry.pool = ctx.accounts.liquidity_pool.key();
+    allowlist_entry.wallet = params.wallet;
+
+    emit!(AllowlistEntryAdded {
+        pool: allowlist_entry.pool,
+        wallet: allowlist_entry.wallet,
+    });
+
+    Ok(())
+}
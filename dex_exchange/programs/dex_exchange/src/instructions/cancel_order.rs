@@ -0,0 +1,79 @@
+//! CancelOrder instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::OrderCancelled,
+        matching,
+        state::{
+            market::{Market, Side},
+            open_orders::OpenOrders,
+            order_book::OrderBook,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CancelOrder<'info> {
+    #[account(
+        constraint = market.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = market.bids @ DexError::InvalidOrderBook)]
+    pub bids: Box<Account<'info, OrderBook>>,
+    #[account(mut, address = market.asks @ DexError::InvalidOrderBook)]
+    pub asks: Box<Account<'info, OrderBook>>,
+    #[account(mut,has_one = owner,
+        constraint = open_orders.market == market.key() @ DexError::InvalidOpenOrders
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CancelOrderParams {
+    pub side: Side,
+    pub order_id: u64, // id of the resting order
+}
+
+pub fn cancel_order(mut ctx: Context<CancelOrder>, params: &CancelOrderParams) -> Result<()> {
+    let accounts = &mut ctx.accounts;
+    let open_orders = &mut accounts.open_orders;
+    let book = match params.side {
+        Side::Bid => &mut accounts.bids,
+        Side::Ask => &mut accounts.asks,
+    };
+
+    let index = book
+        .orders
+        .iter()
+        .position(|x| x.order_id == params.order_id)
+        .ok_or(DexError::OrderNotFound)?;
+
+    let order = book.orders[index];
+    if order.open_orders != open_orders.key() {
+        return Err(DexError::InvalidOrderOwner.into());
+    }
+
+    book.orders.remove(index);
+
+    // The unfilled quantity's funds become free to settle
+    matching::unlock(
+        open_orders,
+        params.side,
+        order.price,
+        order.quantity,
+        accounts.market.base_lot_size,
+    )?;
+
+    emit!(OrderCancelled {
+        market: accounts.market.key(),
+        open_orders: open_orders.key(),
+        order_id: order.order_id,
+        side: params.side,
+        quantity: order.quantity,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,131 @@
+//! ClaimFees instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::FeesClaimed,
+        fees,
+        state::{liquidity_provider::LiquidityProvider, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(mut,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    // a suspended liquidity provider can still exit, only new deposits are blocked
+    #[account(mut,has_one = owner)]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    #[account(mut, address = liquidity_pool.vault_a @ DexError::InvalidVault)]
+    pub pool_tokens_a: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_a @ DexError::InvalidTokenMint)]
+    pub mint_token_a: Account<'info, Mint>,
+    #[account(mut, address = liquidity_pool.vault_b @ DexError::InvalidVault)]
+    pub pool_tokens_b: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_b @ DexError::InvalidTokenMint)]
+    pub mint_token_b: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_tokens_a.mint == liquidity_pool.token_a @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_tokens_b.mint == liquidity_pool.token_b @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens_b: Account<'info, TokenAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    let index = fees::checkpoint_index(liquidity_provider, &liquidity_pool.key())
+        .ok_or(DexError::InsufficientShares)?;
+    let checkpoint = &mut liquidity_provider.fee_checkpoints[index];
+    fees::update_checkpoint(checkpoint, liquidity_pool)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let amount_a = checkpoint.fees_owed_a;
+    let amount_b = checkpoint.fees_owed_b;
+    checkpoint.fees_owed_a = 0;
+    checkpoint.fees_owed_b = 0;
+    fees::prune_checkpoints(liquidity_provider);
+
+    // Decrement lp_fees_a with amount_a
+    liquidity_pool.lp_fees_a = liquidity_pool
+        .lp_fees_a
+        .checked_sub(amount_a)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Decrement lp_fees_b with amount_b
+    liquidity_pool.lp_fees_b = liquidity_pool
+        .lp_fees_b
+        .checked_sub(amount_b)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let accounts = &ctx.accounts;
+    let liquidity_pool = &accounts.liquidity_pool;
+    let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+    let seeds = &[
+        b"liquidity-pool",
+        liquidity_pool.token_a.as_ref(),
+        liquidity_pool.token_b.as_ref(),
+        fee_tier.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // token a
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.pool_tokens_a.to_account_info(),
+                    mint: accounts.mint_token_a.to_account_info(),
+                    to: accounts.recipient_tokens_a.to_account_info(),
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_a,
+            accounts.mint_token_a.decimals,
+        )?;
+    }
+
+    // token b
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.pool_tokens_b.to_account_info(),
+                    mint: accounts.mint_token_b.to_account_info(),
+                    to: accounts.recipient_tokens_b.to_account_info(),
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_b,
+            accounts.mint_token_b.decimals,
+        )?;
+    }
+
+    emit!(FeesClaimed {
+        pool: liquidity_pool.key(),
+        liquidity_provider: accounts.liquidity_provider.key(),
+        amount_a,
+        amount_b,
+    });
+
+    Ok(())
+}
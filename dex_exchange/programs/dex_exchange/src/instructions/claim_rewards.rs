@@ -0,0 +1,86 @@
+//! ClaimRewards instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::RewardsClaimed,
+        rewards,
+        state::{reward_schedule::RewardSchedule, stake_position::StakePosition},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct ClaimRewards<'info> {
+    #[account(mut,
+        constraint = reward_schedule.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+    #[account(mut,has_one = owner,
+        constraint = stake_position.reward_schedule == reward_schedule.key() @ DexError::InvalidStakePosition
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(mut, address = reward_schedule.reward_vault @ DexError::InvalidVault)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    #[account(address = reward_schedule.reward_mint @ DexError::InvalidTokenMint)]
+    pub reward_mint: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == reward_schedule.reward_mint @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+    let reward_schedule = &mut ctx.accounts.reward_schedule;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    rewards::update_schedule(reward_schedule, Clock::get()?.unix_timestamp)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    rewards::update_stake(stake_position, reward_schedule)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let amount = stake_position.rewards_owed;
+    stake_position.rewards_owed = 0;
+
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let accounts = &ctx.accounts;
+    let reward_schedule = &accounts.reward_schedule;
+    let seeds = &[
+        b"reward-schedule",
+        reward_schedule.pool.as_ref(),
+        reward_schedule.reward_mint.as_ref(),
+        &[reward_schedule.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer the rewards from the reward vault to the owner
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.reward_vault.to_account_info(),
+                mint: accounts.reward_mint.to_account_info(),
+                to: accounts.recipient_tokens.to_account_info(),
+                authority: reward_schedule.to_account_info(),
+            },
+            signer,
+        ),
+        amount,
+        accounts.reward_mint.decimals,
+    )?;
+
+    emit!(RewardsClaimed {
+        stake_position: accounts.stake_position.key(),
+        reward_schedule: reward_schedule.key(),
+        amount,
+    });
+
+    Ok(())
+}
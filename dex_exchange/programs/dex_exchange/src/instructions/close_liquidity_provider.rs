@@ -0,0 +1,39 @@
+//! CloseLiquidityProvider instruction handler
+
+use {
+    crate::{
+        error::DexError, events::LiquidityProviderClosed,
+        state::liquidity_provider::LiquidityProvider,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseLiquidityProvider<'info> {
+    // close refunds the rent to the owner
+    // a suspended liquidity provider can close too, its suspension record keeps it from
+    // registering again
+    #[account(mut,has_one = owner, close = owner)]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn close_liquidity_provider(ctx: Context<CloseLiquidityProvider>) -> Result<()> {
+    let liquidity_provider = &ctx.accounts.liquidity_provider;
+
+    // Fee shares and unclaimed fees must be gone; reserve_*_available_funds only track deposits and
+    // rarely return to zero once swaps move the reserves. Positions and stakes belong to the wallet
+    // and stay reachable without the account.
+    if !liquidity_provider.fee_checkpoints.is_empty() {
+        return Err(DexError::NonZeroBalance.into());
+    }
+
+    emit!(LiquidityProviderClosed {
+        liquidity_provider: liquidity_provider.key(),
+        owner: liquidity_provider.owner,
+    });
+
+    Ok(())
+}
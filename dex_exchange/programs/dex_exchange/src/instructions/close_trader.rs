@@ -0,0 +1,29 @@
+//! CloseTrader instruction handler
+
+use {
+    crate::{events::TraderClosed, state::trader::Trader},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct CloseTrader<'info> {
+    // close refunds the rent to the owner
+    // a suspended trader can close too, its suspension record keeps it from registering again
+    #[account(mut,has_one = owner, close = owner)]
+    pub trader: Account<'info, Trader>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn close_trader(ctx: Context<CloseTrader>) -> Result<()> {
+    let trader = &ctx.accounts.trader;
+
+    emit!(TraderClosed {
+        trader: trader.key(),
+        owner: trader.owner,
+        swap_count: trader.swap_count,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,29 @@
+//! CollectPositionFees instruction handler
+
+use {
+    crate::{events::PositionFeesCollected, instructions::increase_liquidity::ModifyLiquidity},
+    anchor_lang::prelude::*,
+};
+
+pub fn collect_position_fees(ctx: Context<ModifyLiquidity>) -> Result<()> {
+    // Credit the fees earned since the last update without changing the liquidity
+    ctx.accounts.modify(0)?;
+
+    let position = &mut ctx.accounts.position;
+    let amount_a = position.tokens_owed_a;
+    let amount_b = position.tokens_owed_b;
+
+    position.tokens_owed_a = 0;
+    position.tokens_owed_b = 0;
+
+    ctx.accounts.transfer_out(amount_a, amount_b)?;
+
+    emit!(PositionFeesCollected {
+        pool: ctx.accounts.concentrated_pool.key(),
+        position: ctx.accounts.position.key(),
+        amount_a,
+        amount_b,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+//! CollectProtocolFees instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::ProtocolFeesCollected,
+        state::{deposit_base::DepositBase, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct CollectProtocolFees<'info> {
+    #[account(mut,has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    // the dex treasury, created once by init; the pool admin collects into it even after the pool
+    // changed hands through propose_admin and accept_admin
+    #[account(constraint = deposit_account.is_initialized @ DexError::AccountNotInitialized)]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump = deposit_account.admin_auth_bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    #[account(seeds = [b"treasury-vault", pda_auth.key().as_ref()],
+        bump = deposit_account.admin_treasury_vault_bump.ok_or(DexError::AccountNotInitialized)?
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    #[account(mut, address = liquidity_pool.vault_a @ DexError::InvalidVault)]
+    pub pool_tokens_a: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_a @ DexError::InvalidTokenMint)]
+    pub mint_token_a: Account<'info, Mint>,
+    #[account(mut, address = liquidity_pool.vault_b @ DexError::InvalidVault)]
+    pub pool_tokens_b: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_b @ DexError::InvalidTokenMint)]
+    pub mint_token_b: Account<'info, Mint>,
+    #[account(mut,
+        constraint = treasury_tokens_a.mint == liquidity_pool.token_a @ DexError::InvalidTokenAccount,
+        constraint = treasury_tokens_a.owner == treasury_vault.key() @ DexError::InvalidTokenAccount
+    )]
+    pub treasury_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = treasury_tokens_b.mint == liquidity_pool.token_b @ DexError::InvalidTokenAccount,
+        constraint = treasury_tokens_b.owner == treasury_vault.key() @ DexError::InvalidTokenAccount
+    )]
+    pub treasury_tokens_b: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+    let pool_tokens_a = &ctx.accounts.pool_tokens_a;
+    let pool_tokens_b = &ctx.accounts.pool_tokens_b;
+    let mint_token_a = &ctx.accounts.mint_token_a;
+    let mint_token_b = &ctx.accounts.mint_token_b;
+    let treasury_tokens_a = &ctx.accounts.treasury_tokens_a;
+    let treasury_tokens_b = &ctx.accounts.treasury_tokens_b;
+    let token_program = &ctx.accounts.token_program;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let amount_a = liquidity_pool.protocol_fees_a;
+    let amount_b = liquidity_pool.protocol_fees_b;
+
+    if amount_a == 0 && amount_b == 0 {
+        return Err(DexError::InsufficientFunds.into());
+    }
+
+    liquidity_pool.protocol_fees_a = 0;
+    liquidity_pool.protocol_fees_b = 0;
+
+    let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+    let seeds = &[
+        b"liquidity-pool",
+        liquidity_pool.token_a.as_ref(),
+        liquidity_pool.token_b.as_ref(),
+        fee_tier.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // token a
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_tokens_a.to_account_info(),
+                    mint: mint_token_a.to_account_info(),
+                    to: treasury_tokens_a.to_account_info(),
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_a,
+            mint_token_a.decimals,
+        )?;
+    }
+
+    // token b
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_tokens_b.to_account_info(),
+                    mint: mint_token_b.to_account_info(),
+                    to: treasury_tokens_b.to_account_info(),
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_b,
+            mint_token_b.decimals,
+        )?;
+    }
+
+    emit!(ProtocolFeesCollected {
+        pool: liquidity_pool.key(),
+        amount_a,
+        amount_b,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,136 @@
+//! CreateConcentratedPool instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::ConcentratedPoolInitialized,
+        state::{
+            concentrated_pool::{ConcentratedPool, MAX_TICK_SPACING, MIN_TICK_SPACING},
+            pool::BPS_DENOMINATOR,
+            pool_registry::{PoolRegistry, MAX_POOLS},
+        },
+        tick_math::{self, MAX_SQRT_PRICE, MIN_SQRT_PRICE},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: CreateConcentratedPoolParams)]
+pub struct CreateConcentratedPool<'info> {
+    #[account(mut,has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    // init means to create account
+    // bump to use unique address for account
+    // one pool per (token_a, token_b, tick spacing), so a duplicate pair fails here
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + ConcentratedPool::INIT_SPACE,
+        constraint = !concentrated_pool.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [
+            b"concentrated-pool",
+            mint_token_a.key().as_ref(),
+            mint_token_b.key().as_ref(),
+            params.tick_spacing.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub concentrated_pool: Box<Account<'info, ConcentratedPool>>,
+    // the pair is stored in canonical order, so token a must sort before token b
+    #[account(constraint = mint_token_a.key() < mint_token_b.key() @ DexError::InvalidTokenPair)]
+    pub mint_token_a: Account<'info, Mint>,
+    pub mint_token_b: Account<'info, Mint>,
+    // pool vaults, owned by the pool pda
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token_a,
+        associated_token::authority = concentrated_pool,
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token_b,
+        associated_token::authority = concentrated_pool,
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateConcentratedPoolParams {
+    tick_spacing: u16, // spacing of the usable ticks, also part of the pool pda seeds
+    fee_bps: u16,      // swap fee charged on amount_in in basis points
+    sqrt_price: u128,  // initial Q64.64 square root of the price of token a in token b
+}
+
+pub fn create_concentrated_pool(
+    ctx: Context<CreateConcentratedPool>,
+    params: &CreateConcentratedPoolParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.tick_spacing < MIN_TICK_SPACING || params.tick_spacing > MAX_TICK_SPACING {
+        return Err(DexError::InvalidTickSpacing.into());
+    }
+
+    if params.fee_bps as u64 >= BPS_DENOMINATOR {
+        return Err(DexError::InvalidFee.into());
+    }
+
+    if params.sqrt_price < MIN_SQRT_PRICE || params.sqrt_price >= MAX_SQRT_PRICE {
+        return Err(DexError::InvalidSqrtPrice.into());
+    }
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    let concentrated_pool = &mut ctx.accounts.concentrated_pool;
+
+    if pool_registry.pools.len() >= MAX_POOLS {
+        return Err(DexError::PoolRegistryFull.into());
+    }
+
+    // concentrated pool
+    concentrated_pool.owner = *ctx.accounts.owner.key;
+    concentrated_pool.token_a = ctx.accounts.mint_token_a.key();
+    concentrated_pool.token_b = ctx.accounts.mint_token_b.key();
+    concentrated_pool.vault_a = ctx.accounts.vault_a.key();
+    concentrated_pool.vault_b = ctx.accounts.vault_b.key();
+    concentrated_pool.bump = ctx.bumps.concentrated_pool;
+    concentrated_pool.tick_spacing = params.tick_spacing;
+    concentrated_pool.fee_bps = params.fee_bps;
+    concentrated_pool.sqrt_price = params.sqrt_price;
+    concentrated_pool.tick_current =
+        tick_math::tick_at_sqrt_price(params.sqrt_price).ok_or(DexError::InvalidSqrtPrice)?;
+    concentrated_pool.liquidity = 0;
+    concentrated_pool.fee_growth_global_a = 0;
+    concentrated_pool.fee_growth_global_b = 0;
+    concentrated_pool.is_initialized = true;
+
+    // pool registry
+    pool_registry.pools.push(concentrated_pool.key());
+
+    emit!(ConcentratedPoolInitialized {
+        pool: concentrated_pool.key(),
+        owner: concentrated_pool.owner,
+        token_a: concentrated_pool.token_a,
+        token_b: concentrated_pool.token_b,
+        tick_spacing: concentrated_pool.tick_spacing,
+        fee_bps: concentrated_pool.fee_bps,
+        sqrt_price: concentrated_pool.sqrt_price,
+        tick_current: concentrated_pool.tick_current,
+    });
+
+    Ok(())
+}
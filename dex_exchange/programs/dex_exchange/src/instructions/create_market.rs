@@ -0,0 +1,139 @@
+//! CreateMarket instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::MarketCreated,
+        state::{
+            event_queue::EventQueue,
+            market::{Market, Side},
+            order_book::OrderBook,
+            pool_registry::PoolRegistry,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+pub struct CreateMarket<'info> {
+    // the registry owner is the dex admin
+    #[account(has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    // init means to create account
+    // bump to use unique address for account
+    // one market per (base, quote) pair
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Market::INIT_SPACE,
+        constraint = !market.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [b"market", base_mint.key().as_ref(), quote_mint.key().as_ref()],
+        bump
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"bids", market.key().as_ref()],
+        bump
+    )]
+    pub bids: Box<Account<'info, OrderBook>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OrderBook::INIT_SPACE,
+        seeds = [b"asks", market.key().as_ref()],
+        bump
+    )]
+    pub asks: Box<Account<'info, OrderBook>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + EventQueue::INIT_SPACE,
+        seeds = [b"event-queue", market.key().as_ref()],
+        bump
+    )]
+    pub event_queue: Box<Account<'info, EventQueue>>,
+    #[account(constraint = base_mint.key() != quote_mint.key() @ DexError::InvalidTokenPair)]
+    pub base_mint: Box<Account<'info, Mint>>,
+    pub quote_mint: Box<Account<'info, Mint>>,
+    // market vaults, owned by the market pda
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = base_mint,
+        associated_token::authority = market,
+    )]
+    pub base_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = quote_mint,
+        associated_token::authority = market,
+    )]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateMarketParams {
+    pub base_lot_size: u64, // base units per lot, order quantities are in lots
+}
+
+pub fn create_market(ctx: Context<CreateMarket>, params: &CreateMarketParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.base_lot_size == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let market = &mut ctx.accounts.market;
+    let bids = &mut ctx.accounts.bids;
+    let asks = &mut ctx.accounts.asks;
+    let event_queue = &mut ctx.accounts.event_queue;
+
+    // market
+    market.owner = *ctx.accounts.owner.key;
+    market.base_mint = ctx.accounts.base_mint.key();
+    market.quote_mint = ctx.accounts.quote_mint.key();
+    market.base_vault = ctx.accounts.base_vault.key();
+    market.quote_vault = ctx.accounts.quote_vault.key();
+    market.bids = bids.key();
+    market.asks = asks.key();
+    market.event_queue = event_queue.key();
+    market.base_lot_size = params.base_lot_size;
+    market.next_order_id = 0;
+    market.bump = ctx.bumps.market;
+    market.is_initialized = true;
+
+    // order books
+    bids.market = market.key();
+    bids.side = Side::Bid;
+    asks.market = market.key();
+    asks.side = Side::Ask;
+
+    // event queue
+    event_queue.market = market.key();
+
+    emit!(MarketCreated {
+        market: market.key(),
+        owner: market.owner,
+        base_mint: market.base_mint,
+        quote_mint: market.quote_mint,
+        base_lot_size: market.base_lot_size,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! CreatePool instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::PoolInitialized,
+        state::{
+            observations::Observations,
+            pool::{CurveType, Pool, BPS_DENOMINATOR, LP_MINT_DECIMALS, MAX_AMP, MIN_AMP},
+            pool_registry::{PoolRegistry, MAX_POOLS},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+    },
+};
+
+#[derive(Accounts)]
+#[instruction(params: CreatePoolParams)]
+pub struct CreatePool<'info> {
+    #[account(mut,has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    // init means to create account
+    // bump to use unique address for account
+    // one pool per (token_a, token_b, fee tier), so a duplicate pair fails here
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Pool::INIT_SPACE,
+        constraint = !liquidity_pool.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [
+            b"liquidity-pool",
+            mint_token_a.key().as_ref(),
+            mint_token_b.key().as_ref(),
+            params.fee_bps.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"lp-mint", liquidity_pool.key().as_ref()],
+        bump,
+        mint::decimals = LP_MINT_DECIMALS,
+        mint::authority = liquidity_pool,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Observations::INIT_SPACE,
+        seeds = [b"observations", liquidity_pool.key().as_ref()],
+        bump
+    )]
+    pub observations: Box<Account<'info, Observations>>,
+    // the pair is stored in canonical order, so token a must sort before token b
+    #[account(constraint = mint_token_a.key() < mint_token_b.key() @ DexError::InvalidTokenPair)]
+    pub mint_token_a: Account<'info, Mint>,
+    pub mint_token_b: Account<'info, Mint>,
+    // pool vaults, owned by the pool pda
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token_a,
+        associated_token::authority = liquidity_pool,
+    )]
+    pub vault_a: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint_token_b,
+        associated_token::authority = liquidity_pool,
+    )]
+    pub vault_b: Box<Account<'info, TokenAccount>>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreatePoolParams {
+    fee_bps: u16, // swap fee charged on amount_in in basis points, also the pool's fee tier
+    protocol_fee_bps: u16, // share of the swap fee kept by the protocol, in basis points of the fee
+    curve_type: CurveType, // pricing curve of the pool
+    amp: u64,     // StableSwap amplification coefficient, ignored by constant product pools
+}
+
+pub fn create_pool(ctx: Context<CreatePool>, params: &CreatePoolParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.fee_bps as u64 >= BPS_DENOMINATOR {
+        return Err(DexError::InvalidFee.into());
+    }
+
+    if params.protocol_fee_bps as u64 > BPS_DENOMINATOR {
+        return Err(DexError::InvalidFee.into());
+    }
+
+    if params.curve_type == CurveType::StableSwap && (params.amp < MIN_AMP || params.amp > MAX_AMP)
+    {
+        return Err(DexError::InvalidAmp.into());
+    }
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let observations = &mut ctx.accounts.observations;
+
+    if pool_registry.pools.len() >= MAX_POOLS {
+        return Err(DexError::PoolRegistryFull.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let amp = match params.curve_type {
+        CurveType::ConstantProduct => 0,
+        CurveType::StableSwap => params.amp,
+    };
+
+    // liquidity pool
+    liquidity_pool.owner = *ctx.accounts.owner.key;
+    liquidity_pool.token_a = ctx.accounts.mint_token_a.key();
+    liquidity_pool.token_b = ctx.accounts.mint_token_b.key();
+    liquidity_pool.reserve_a = 0;
+    liquidity_pool.reserve_b = 0;
+    liquidity_pool.decimals_a = ctx.accounts.mint_token_a.decimals;
+    liquidity_pool.decimals_b = ctx.accounts.mint_token_b.decimals;
+    liquidity_pool.lp_mint = ctx.accounts.lp_mint.key();
+    liquidity_pool.vault_a = ctx.accounts.vault_a.key();
+    liquidity_pool.vault_b = ctx.accounts.vault_b.key();
+    liquidity_pool.total_shares = 0;
+    liquidity_pool.bump = ctx.bumps.liquidity_pool;
+    liquidity_pool.fee_tier = params.fee_bps;
+    liquidity_pool.fee_bps = params.fee_bps;
+    liquidity_pool.protocol_fee_bps = params.protocol_fee_bps;
+    liquidity_pool.protocol_fees_a = 0;
+    liquidity_pool.protocol_fees_b = 0;
+    liquidity_pool.price_a_cumulative = 0;
+    liquidity_pool.price_b_cumulative = 0;
+    liquidity_pool.last_update_timestamp = now;
+    liquidity_pool.observations = observations.key();
+    liquidity_pool.curve_type = params.curve_type;
+    liquidity_pool.initial_amp = amp;
+    liquidity_pool.future_amp = amp;
+    liquidity_pool.initial_amp_time = now;
+    liquidity_pool.future_amp_time = now;
+    liquidity_pool.flash_loan_active = false;
+    liquidity_pool.flash_loan_token = Pubkey::default();
+    liquidity_pool.flash_loan_amount = 0;
+    liquidity_pool.fee_growth_a = 0;
+    liquidity_pool.fee_growth_b = 0;
+    liquidity_pool.fee_shares = 0;
+    liquidity_pool.lp_fees_a = 0;
+    liquidity_pool.lp_fees_b = 0;
+    liquidity_pool.allowlist_enabled = false;
+    liquidity_pool.pending_owner = Pubkey::default();
+    liquidity_pool.is_initialized = true;
+
+    // observations
+    observations.pool = liquidity_pool.key();
+    observations.index = 0;
+
+    // pool registry
+    pool_registry.pools.push(liquidity_pool.key());
+
+    emit!(PoolInitialized {
+        pool: liquidity_pool.key(),
+        owner: liquidity_pool.owner,
+        token_a: liquidity_pool.token_a,
+        token_b: liquidity_pool.token_b,
+        lp_mint: liquidity_pool.lp_mint,
+        fee_tier: liquidity_pool.fee_tier,
+        fee_bps: liquidity_pool.fee_bps,
+        protocol_fee_bps: liquidity_pool.protocol_fee_bps,
+        curve_type: liquidity_pool.curve_type,
+        amp,
+    });
+
+    Ok(())
+}
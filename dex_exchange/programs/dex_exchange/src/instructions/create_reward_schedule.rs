@@ -0,0 +1,147 @@
+//! CreateRewardSchedule instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::RewardScheduleCreated,
+        state::{pool::Pool, reward_schedule::RewardSchedule},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct CreateRewardSchedule<'info> {
+    #[account(has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    // init means to create account
+    // bump to use unique address for account
+    // one schedule per pool and reward mint
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + RewardSchedule::INIT_SPACE,
+        constraint = !reward_schedule.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [
+            b"reward-schedule",
+            liquidity_pool.key().as_ref(),
+            reward_mint.key().as_ref()
+        ],
+        bump
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+    pub reward_mint: Box<Account<'info, Mint>>,
+    #[account(address = liquidity_pool.lp_mint @ DexError::InvalidLpMint)]
+    pub lp_mint: Box<Account<'info, Mint>>,
+    // schedule vaults, owned by the schedule pda
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"reward-vault", reward_schedule.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = reward_schedule,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = owner,
+        seeds = [b"stake-vault", reward_schedule.key().as_ref()],
+        bump,
+        token::mint = lp_mint,
+        token::authority = reward_schedule,
+    )]
+    pub stake_vault: Box<Account<'info, TokenAccount>>,
+    #[account(mut,
+        constraint = sender_tokens.mint == reward_mint.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Box<Account<'info, TokenAccount>>, // admin's reward token account funding the schedule
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct CreateRewardScheduleParams {
+    pub emission_rate: u64, // reward tokens emitted per second, in base units
+    pub start_time: i64,    // unix timestamp emission starts, now if it is in the past
+    pub end_time: i64,      // unix timestamp emission ends
+}
+
+pub fn create_reward_schedule(
+    ctx: Context<CreateRewardSchedule>,
+    params: &CreateRewardScheduleParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.emission_rate == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    if params.end_time <= params.start_time || params.end_time <= now {
+        return Err(DexError::InvalidRewardSchedule.into());
+    }
+
+    // Emission can't start in the past, so no rewards accrue unfunded before the schedule exists
+    let start_time = params.start_time.max(now);
+
+    // The whole emission is deposited up front
+    let total_rewards = (params.emission_rate as u128)
+        .checked_mul((params.end_time - start_time) as u128)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let total_rewards =
+        u64::try_from(total_rewards).map_err(|_| DexError::InvalidArithmeticOperation)?;
+
+    let reward_schedule = &mut ctx.accounts.reward_schedule;
+
+    // reward schedule
+    reward_schedule.owner = *ctx.accounts.owner.key;
+    reward_schedule.pool = ctx.accounts.liquidity_pool.key();
+    reward_schedule.reward_mint = ctx.accounts.reward_mint.key();
+    reward_schedule.reward_vault = ctx.accounts.reward_vault.key();
+    reward_schedule.stake_vault = ctx.accounts.stake_vault.key();
+    reward_schedule.emission_rate = params.emission_rate;
+    reward_schedule.start_time = start_time;
+    reward_schedule.end_time = params.end_time;
+    reward_schedule.reward_growth = 0;
+    reward_schedule.last_update_time = now;
+    reward_schedule.total_staked = 0;
+    reward_schedule.bump = ctx.bumps.reward_schedule;
+    reward_schedule.is_initialized = true;
+
+    let accounts = &ctx.accounts;
+
+    // Transfer the rewards from the admin to the reward vault
+    transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.sender_tokens.to_account_info(),
+                mint: accounts.reward_mint.to_account_info(),
+                to: accounts.reward_vault.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        total_rewards,
+        accounts.reward_mint.decimals,
+    )?;
+
+    let reward_schedule = &accounts.reward_schedule;
+
+    emit!(RewardScheduleCreated {
+        reward_schedule: reward_schedule.key(),
+        pool: reward_schedule.pool,
+        reward_mint: reward_schedule.reward_mint,
+        emission_rate: reward_schedule.emission_rate,
+        start_time: reward_schedule.start_time,
+        end_time: reward_schedule.end_time,
+        total_rewards,
+    });
+
+    Ok(())
+}
@@ -1,7 +1,7 @@
 //! CreateToken instruction handler
 
 use {
-    crate::{error::DexError, state::pool::Pool},
+    crate::{error::DexError, events::TokenCreated},
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token,
@@ -13,10 +13,6 @@ use {
 #[derive(Accounts)]
 #[instruction(params: CreateTokenParams)]
 pub struct CreateToken<'info> {
-    #[account(mut,
-        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
-    )]
-    pub liquidity_pool: Account<'info, Pool>,
     #[account(mut)]
     ///CHECK:
     pub token_account: AccountInfo<'info>,
@@ -33,7 +29,8 @@ pub struct CreateToken<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct CreateTokenParams {
-    pub amount: u32,
+    pub amount: u64,  // amount in base units
+    pub decimals: u8, // decimals for the token mint
 }
 
 pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Result<()> {
@@ -42,20 +39,9 @@ pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Re
         return Err(DexError::InvalidAmount.into());
     }
 
-    let liquidity_pool = &ctx.accounts.liquidity_pool;
-    let decimals = liquidity_pool.decimals;
+    let decimals = params.decimals;
     let _amount = params.amount;
 
-    let base: u32 = 10;
-    let exponent = liquidity_pool.decimals as u32;
-
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
-        .ok_or(DexError::InvalidArithmeticOperation)?;
-
     system_program::create_account(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
@@ -106,5 +92,12 @@ pub fn create_token(ctx: Context<CreateToken>, params: &CreateTokenParams) -> Re
         _amount,
     )?;
 
+    emit!(TokenCreated {
+        mint: ctx.accounts.mint_token.key(),
+        owner: *ctx.accounts.owner.key,
+        decimals,
+        amount: _amount,
+    });
+
     Ok(())
 }
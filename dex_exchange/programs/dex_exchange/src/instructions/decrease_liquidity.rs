@@ -0,0 +1,60 @@
+//! DecreaseLiquidity instruction handler
+
+use {
+    crate::{
+        error::DexError, events::PositionLiquidityUpdated,
+        instructions::increase_liquidity::ModifyLiquidity,
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct DecreaseLiquidityParams {
+    pub liquidity: u128,       // liquidity to remove from the position
+    pub minimum_amount_a: u64, // minimum amount of token a to receive
+    pub minimum_amount_b: u64, // minimum amount of token b to receive
+}
+
+pub fn decrease_liquidity(
+    ctx: Context<ModifyLiquidity>,
+    params: &DecreaseLiquidityParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.liquidity == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if ctx.accounts.dex_configs.liquidity_paused {
+        return Err(DexError::Paused.into());
+    }
+
+    if params.liquidity > ctx.accounts.position.liquidity {
+        return Err(DexError::InsufficientPositionLiquidity.into());
+    }
+
+    let liquidity_delta = i128::try_from(params.liquidity)
+        .map_err(|_| DexError::InvalidArithmeticOperation)?
+        .checked_neg()
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let (amount_a, amount_b) = ctx.accounts.modify(liquidity_delta)?;
+
+    if amount_a < params.minimum_amount_a || amount_b < params.minimum_amount_b {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    // Fees stay owed to the position until collect_position_fees
+    ctx.accounts.transfer_out(amount_a, amount_b)?;
+
+    let accounts = &ctx.accounts;
+
+    emit!(PositionLiquidityUpdated {
+        pool: accounts.concentrated_pool.key(),
+        position: accounts.position.key(),
+        liquidity_delta,
+        amount_a,
+        amount_b,
+        liquidity: accounts.position.liquidity,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,170 @@
+//! FlashBorrow instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::FlashBorrowed,
+        instructions::set_pool_allowlist::check_allowlist,
+        state::{allowlist_entry::AllowlistEntry, configs::DexConfigs, pool::Pool, trader::Trader},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::sysvar::instructions::{
+            load_current_index_checked, load_instruction_at_checked,
+        },
+        Discriminator,
+    },
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct FlashBorrow<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.flash_loans_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    #[account(has_one = owner,
+        constraint = trader.active @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Account<'info, Trader>,
+    // required when the pool is in allowlist mode
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+    #[account(mut,
+        constraint = recipient_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>, // borrower's account of the borrowed token
+    #[account(mut)]
+    pub pool_tokens: Account<'info, TokenAccount>, // pool vault of the borrowed token
+    pub mint: Account<'info, Mint>, // mint of the borrowed token
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    /// CHECK: instructions sysvar, checked by address
+    pub instructions: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FlashBorrowParams {
+    pub token: Pubkey, // mint of the token to borrow
+    pub amount: u64,   // amount to borrow, in base units
+}
+
+pub fn flash_borrow(ctx: Context<FlashBorrow>, params: &FlashBorrowParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let liquidity_pool = &ctx.accounts.liquidity_pool;
+
+    check_allowlist(
+        liquidity_pool,
+        &ctx.accounts.allowlist_entry,
+        ctx.accounts.owner.key,
+    )?;
+
+    let (reserve, vault) = if params.token == liquidity_pool.token_a {
+        (liquidity_pool.reserve_a, liquidity_pool.vault_a)
+    } else if params.token == liquidity_pool.token_b {
+        (liquidity_pool.reserve_b, liquidity_pool.vault_b)
+    } else {
+        return Err(DexError::InvalidTokenIn.into());
+    };
+
+    if ctx.accounts.mint.key() != params.token {
+        return Err(DexError::InvalidTokenMint.into());
+    }
+
+    if ctx.accounts.recipient_tokens.mint != params.token {
+        return Err(DexError::InvalidTokenAccount.into());
+    }
+
+    if ctx.accounts.pool_tokens.key() != vault {
+        return Err(DexError::InvalidVault.into());
+    }
+
+    if params.amount > reserve {
+        return Err(DexError::InsufficientLiquidity.into());
+    }
+
+    let instructions = &ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(instructions)? as usize;
+
+    // The instruction at the current index is the top level one, it is only this
+    // program's when flash_borrow was not reached through a cross-program invocation
+    let current = load_instruction_at_checked(current_index, instructions)?;
+    if current.program_id != crate::ID {
+        return Err(DexError::FlashLoanCpi.into());
+    }
+
+    // A later instruction of the transaction must repay the loan to the same pool,
+    // flash_repay takes the pool as its first account
+    let mut index = current_index + 1;
+    let mut repay_found = false;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions) {
+        if instruction.program_id == crate::ID
+            && instruction.data.get(..8) == Some(&crate::instruction::FlashRepay::DISCRIMINATOR[..])
+            && instruction.accounts.first().map(|x| x.pubkey) == Some(liquidity_pool.key())
+        {
+            repay_found = true;
+            break;
+        }
+        index += 1;
+    }
+
+    if !repay_found {
+        return Err(DexError::FlashRepayMissing.into());
+    }
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+
+    // Lock the pool until the loan is repaid, the reserves are not changed by the loan
+    liquidity_pool.flash_loan_active = true;
+    liquidity_pool.flash_loan_token = params.token;
+    liquidity_pool.flash_loan_amount = params.amount;
+
+    let accounts = &ctx.accounts;
+    let liquidity_pool = &accounts.liquidity_pool;
+    let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+    let seeds = &[
+        b"liquidity-pool",
+        liquidity_pool.token_a.as_ref(),
+        liquidity_pool.token_b.as_ref(),
+        fee_tier.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer the loan from the pool vault to the borrower
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.pool_tokens.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.recipient_tokens.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            signer,
+        ),
+        params.amount,
+        accounts.mint.decimals,
+    )?;
+
+    emit!(FlashBorrowed {
+        pool: liquidity_pool.key(),
+        trader: accounts.trader.key(),
+        token: params.token,
+        amount: params.amount,
+    });
+
+    Ok(())
+}
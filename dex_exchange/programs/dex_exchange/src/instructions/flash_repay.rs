@@ -0,0 +1,103 @@
+//! FlashRepay instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::FlashRepaid,
+        fees, math, oracle,
+        state::{
+            observations::Observations,
+            pool::{Pool, FLASH_FEE_BPS},
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+// liquidity_pool must stay the first account, flash_borrow looks the repay up by it
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    #[account(mut,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = liquidity_pool.flash_loan_active @ DexError::FlashLoanNotActive
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    #[account(mut, address = liquidity_pool.observations @ DexError::InvalidObservations)]
+    pub observations: Box<Account<'info, Observations>>,
+    #[account(mut,
+        constraint = sender_tokens.owner == owner.key() @ DexError::InvalidTokenAccount,
+        constraint = sender_tokens.mint == liquidity_pool.flash_loan_token @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>, // repayer's account of the borrowed token
+    #[account(mut)]
+    pub pool_tokens: Account<'info, TokenAccount>, // pool vault of the borrowed token
+    #[account(address = liquidity_pool.flash_loan_token @ DexError::InvalidTokenMint)]
+    pub mint: Account<'info, Mint>, // mint of the borrowed token
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let token = liquidity_pool.flash_loan_token;
+    let amount = liquidity_pool.flash_loan_amount;
+
+    let vault = if token == liquidity_pool.token_a {
+        liquidity_pool.vault_a
+    } else {
+        liquidity_pool.vault_b
+    };
+
+    if ctx.accounts.pool_tokens.key() != vault {
+        return Err(DexError::InvalidVault.into());
+    }
+
+    // Flash fee is rounded up in favour of the pool
+    let fee = math::flash_fee(amount, FLASH_FEE_BPS).ok_or(DexError::InvalidArithmeticOperation)?;
+    let repay_amount = amount
+        .checked_add(fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Accumulate the price oracle before the reserves move
+    oracle::update(
+        liquidity_pool,
+        &mut ctx.accounts.observations,
+        Clock::get()?.unix_timestamp,
+    );
+
+    // The whole fee goes to the liquidity providers
+    fees::accrue_lp_fee(liquidity_pool, &token, fee).ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Release the pool
+    liquidity_pool.flash_loan_active = false;
+    liquidity_pool.flash_loan_token = Pubkey::default();
+    liquidity_pool.flash_loan_amount = 0;
+
+    let accounts = &ctx.accounts;
+
+    // Transfer the loan and the fee from the repayer to the pool vault
+    transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.sender_tokens.to_account_info(),
+                mint: accounts.mint.to_account_info(),
+                to: accounts.pool_tokens.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        repay_amount,
+        accounts.mint.decimals,
+    )?;
+
+    emit!(FlashRepaid {
+        pool: accounts.liquidity_pool.key(),
+        token,
+        amount,
+        fee,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+//! GetTwap instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        oracle,
+        state::{observations::Observations, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: GetTwapParams)]
+pub struct GetTwap<'info> {
+    #[account(
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    #[account(address = liquidity_pool.observations @ DexError::InvalidObservations)]
+    pub observations: Box<Account<'info, Observations>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct GetTwapParams {
+    pub window: i64, // length of the averaging window, in seconds
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct TwapPrice {
+    pub price_a: u128, // average price of token a in token b, Q64.64 fixed point
+    pub price_b: u128, // average price of token b in token a, Q64.64 fixed point
+    pub window: i64,   // requested window, in seconds
+}
+
+pub fn get_twap(ctx: Context<GetTwap>, params: &GetTwapParams) -> Result<TwapPrice> {
+    msg!("Validate inputs");
+    if params.window <= 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let (price_a, price_b) = oracle::twap(
+        &ctx.accounts.liquidity_pool,
+        &ctx.accounts.observations,
+        now,
+        params.window,
+    )?;
+
+    Ok(TwapPrice {
+        price_a,
+        price_b,
+        window: params.window,
+    })
+}
@@ -0,0 +1,293 @@
+//! IncreaseLiquidity instruction handler
+
+use {
+    crate::{
+        concentrated,
+        error::DexError,
+        events::PositionLiquidityUpdated,
+        state::{
+            concentrated_pool::ConcentratedPool, configs::DexConfigs,
+            liquidity_provider::LiquidityProvider, position::Position, tick_array::TickArray,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+// Shared by increase_liquidity, decrease_liquidity and collect_position_fees
+#[derive(Accounts)]
+pub struct ModifyLiquidity<'info> {
+    // collect_position_fees stays open while liquidity is paused
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump)]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,
+        constraint = concentrated_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub concentrated_pool: Box<Account<'info, ConcentratedPool>>,
+    // a suspended liquidity provider can still exit, increase_liquidity blocks new deposits
+    #[account(has_one = owner)]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    #[account(mut,has_one = owner,
+        constraint = position.pool == concentrated_pool.key() @ DexError::InvalidPosition
+    )]
+    pub position: Account<'info, Position>,
+    // may be the same account as tick_array_upper when the range fits one tick array
+    #[account(mut,
+        constraint = tick_array_lower.pool == concentrated_pool.key() @ DexError::InvalidTickArray
+    )]
+    pub tick_array_lower: Box<Account<'info, TickArray>>,
+    #[account(mut,
+        constraint = tick_array_upper.pool == concentrated_pool.key() @ DexError::InvalidTickArray
+    )]
+    pub tick_array_upper: Box<Account<'info, TickArray>>,
+    #[account(mut,
+        constraint = owner_tokens_a.mint == concentrated_pool.token_a @ DexError::InvalidTokenAccount
+    )]
+    pub owner_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = owner_tokens_b.mint == concentrated_pool.token_b @ DexError::InvalidTokenAccount
+    )]
+    pub owner_tokens_b: Account<'info, TokenAccount>,
+    #[account(mut, address = concentrated_pool.vault_a @ DexError::InvalidVault)]
+    pub pool_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut, address = concentrated_pool.vault_b @ DexError::InvalidVault)]
+    pub pool_tokens_b: Account<'info, TokenAccount>,
+    #[account(address = concentrated_pool.token_a @ DexError::InvalidTokenMint)]
+    pub mint_token_a: Account<'info, Mint>,
+    #[account(address = concentrated_pool.token_b @ DexError::InvalidTokenMint)]
+    pub mint_token_b: Account<'info, Mint>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ModifyLiquidity<'info> {
+    // Applies liquidity_delta to the position, its ticks and the pool, crediting the fees earned
+    // so far to the position. Returns the token amounts (a, b) backing liquidity_delta, rounded
+    // up when liquidity is added and down when it is removed.
+    pub fn modify(&mut self, liquidity_delta: i128) -> Result<(u64, u64)> {
+        let concentrated_pool = &mut self.concentrated_pool;
+        let position = &mut self.position;
+        let tick_spacing = concentrated_pool.tick_spacing;
+        let tick_current = concentrated_pool.tick_current;
+        let fee_growth_global_a = concentrated_pool.fee_growth_global_a;
+        let fee_growth_global_b = concentrated_pool.fee_growth_global_b;
+        let tick_lower = position.tick_lower;
+        let tick_upper = position.tick_upper;
+        let same_array = self.tick_array_lower.key() == self.tick_array_upper.key();
+
+        let lower_offset =
+            concentrated::tick_offset(&self.tick_array_lower, tick_lower, tick_spacing)
+                .ok_or(DexError::InvalidTickArray)?;
+        let upper_offset =
+            concentrated::tick_offset(&self.tick_array_upper, tick_upper, tick_spacing)
+                .ok_or(DexError::InvalidTickArray)?;
+
+        // lower tick
+        concentrated::update_tick(
+            &mut self.tick_array_lower.ticks[lower_offset],
+            tick_lower,
+            tick_current,
+            liquidity_delta,
+            false,
+            fee_growth_global_a,
+            fee_growth_global_b,
+        )
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // upper tick, updated through the lower array when both are the same account
+        let upper_array = if same_array {
+            &mut self.tick_array_lower
+        } else {
+            &mut self.tick_array_upper
+        };
+        concentrated::update_tick(
+            &mut upper_array.ticks[upper_offset],
+            tick_upper,
+            tick_current,
+            liquidity_delta,
+            true,
+            fee_growth_global_a,
+            fee_growth_global_b,
+        )
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+        let upper_tick = upper_array.ticks[upper_offset];
+
+        let (fee_growth_inside_a, fee_growth_inside_b) = concentrated::fee_growth_inside(
+            &self.tick_array_lower.ticks[lower_offset],
+            &upper_tick,
+            tick_lower,
+            tick_upper,
+            tick_current,
+            fee_growth_global_a,
+            fee_growth_global_b,
+        );
+
+        concentrated::update_position(
+            position,
+            liquidity_delta,
+            fee_growth_inside_a,
+            fee_growth_inside_b,
+        )
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Both accounts are written back on exit, keep them identical
+        if same_array {
+            self.tick_array_upper.ticks = self.tick_array_lower.ticks;
+        }
+
+        // Only positions in range contribute to the active liquidity
+        if tick_lower <= tick_current && tick_current < tick_upper {
+            concentrated_pool.liquidity = concentrated_pool
+                .liquidity
+                .checked_add_signed(liquidity_delta)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+
+        let amounts = concentrated::amounts_for_liquidity(
+            concentrated_pool.sqrt_price,
+            tick_current,
+            tick_lower,
+            tick_upper,
+            liquidity_delta.unsigned_abs(),
+            liquidity_delta > 0,
+        )
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        Ok(amounts)
+    }
+
+    // Pays amount_a and amount_b from the pool vaults to the owner's token accounts
+    pub fn transfer_out(&self, amount_a: u64, amount_b: u64) -> Result<()> {
+        let concentrated_pool = &self.concentrated_pool;
+        let tick_spacing = concentrated_pool.tick_spacing.to_le_bytes();
+        let seeds = &[
+            b"concentrated-pool",
+            concentrated_pool.token_a.as_ref(),
+            concentrated_pool.token_b.as_ref(),
+            tick_spacing.as_ref(),
+            &[concentrated_pool.bump],
+        ];
+
+        let signer = &[&seeds[..]];
+
+        // token a
+        if amount_a > 0 {
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_tokens_a.to_account_info(),
+                        mint: self.mint_token_a.to_account_info(),
+                        to: self.owner_tokens_a.to_account_info(),
+                        authority: concentrated_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount_a,
+                self.mint_token_a.decimals,
+            )?;
+        }
+
+        // token b
+        if amount_b > 0 {
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    TransferChecked {
+                        from: self.pool_tokens_b.to_account_info(),
+                        mint: self.mint_token_b.to_account_info(),
+                        to: self.owner_tokens_b.to_account_info(),
+                        authority: concentrated_pool.to_account_info(),
+                    },
+                    signer,
+                ),
+                amount_b,
+                self.mint_token_b.decimals,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct IncreaseLiquidityParams {
+    pub liquidity: u128,       // liquidity to add to the position
+    pub maximum_amount_a: u64, // most amount of token a the provider is willing to deposit
+    pub maximum_amount_b: u64, // most amount of token b the provider is willing to deposit
+}
+
+pub fn increase_liquidity(
+    ctx: Context<ModifyLiquidity>,
+    params: &IncreaseLiquidityParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.liquidity == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if ctx.accounts.dex_configs.liquidity_paused {
+        return Err(DexError::Paused.into());
+    }
+
+    if !ctx.accounts.liquidity_provider.active {
+        return Err(DexError::InvalidLiquidityProviderStatus.into());
+    }
+
+    let liquidity_delta =
+        i128::try_from(params.liquidity).map_err(|_| DexError::InvalidArithmeticOperation)?;
+    let (amount_a, amount_b) = ctx.accounts.modify(liquidity_delta)?;
+
+    if amount_a > params.maximum_amount_a || amount_b > params.maximum_amount_b {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    let accounts = &ctx.accounts;
+
+    // token a
+    if amount_a > 0 {
+        transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.owner_tokens_a.to_account_info(),
+                    mint: accounts.mint_token_a.to_account_info(),
+                    to: accounts.pool_tokens_a.to_account_info(),
+                    authority: accounts.owner.to_account_info(),
+                },
+            ),
+            amount_a,
+            accounts.mint_token_a.decimals,
+        )?;
+    }
+
+    // token b
+    if amount_b > 0 {
+        transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.owner_tokens_b.to_account_info(),
+                    mint: accounts.mint_token_b.to_account_info(),
+                    to: accounts.pool_tokens_b.to_account_info(),
+                    authority: accounts.owner.to_account_info(),
+                },
+            ),
+            amount_b,
+            accounts.mint_token_b.decimals,
+        )?;
+    }
+
+    emit!(PositionLiquidityUpdated {
+        pool: accounts.concentrated_pool.key(),
+        position: accounts.position.key(),
+        liquidity_delta,
+        amount_a,
+        amount_b,
+        liquidity: accounts.position.liquidity,
+    });
+
+    Ok(())
+}
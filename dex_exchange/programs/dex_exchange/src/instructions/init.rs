@@ -3,25 +3,34 @@
 use {
     crate::{
         error::DexError,
-        state::{deposit_base::DepositBase, pool::Pool},
+        events::DexInitialized,
+        state::{configs::DexConfigs, deposit_base::DepositBase, pool_registry::PoolRegistry},
     },
     anchor_lang::prelude::*,
 };
 
 #[derive(Accounts)]
-#[instruction(params: InitParams)]
 pub struct Init<'info> {
     // init means to create account
     // bump to use unique address for account
     #[account(
         init,
         payer = owner,
-        space = 8 + Pool::INIT_SPACE,
-        constraint = !liquidity_pool.is_initialized @ DexError::AccountAlreadyInitialized,
-        seeds = [b"liquidity-pool", owner.key().as_ref()],
+        space = 8 + PoolRegistry::INIT_SPACE,
+        constraint = !pool_registry.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [b"pool-registry"],
         bump
     )]
-    pub liquidity_pool: Account<'info, Pool>,
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + DexConfigs::INIT_SPACE,
+        constraint = !dex_configs.is_initialized @ DexError::AccountAlreadyInitialized,
+        seeds = [b"dex-configs"],
+        bump
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
     // init means to create account
     // bump to use unique address for account
     #[account(init, payer = owner, space = 8 + DepositBase::INIT_SPACE,
@@ -39,25 +48,25 @@ pub struct Init<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct InitParams {
-    token_a: Pubkey,
-    token_b: Pubkey,
-    decimals: u8, // decimals for the token mint
-}
-
-pub fn init(ctx: Context<Init>, params: &InitParams) -> Result<()> {
-    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+pub fn init(ctx: Context<Init>) -> Result<()> {
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    let dex_configs = &mut ctx.accounts.dex_configs;
     let deposit_account = &mut ctx.accounts.deposit_account;
 
-    // liquidity pool
-    liquidity_pool.owner = *ctx.accounts.owner.key;
-    liquidity_pool.token_a = params.token_a;
-    liquidity_pool.token_b = params.token_b;
-    liquidity_pool.reserve_a = 0;
-    liquidity_pool.reserve_b = 0;
-    liquidity_pool.decimals = params.decimals;
-    liquidity_pool.is_initialized = true;
+    // pool registry
+    pool_registry.owner = *ctx.accounts.owner.key;
+    pool_registry.pools = Vec::new();
+    pool_registry.is_initialized = true;
+
+    // dex configs, nothing paused and no circuit breaker
+    dex_configs.owner = *ctx.accounts.owner.key;
+    dex_configs.swaps_paused = false;
+    dex_configs.liquidity_paused = false;
+    dex_configs.flash_loans_paused = false;
+    dex_configs.orders_paused = false;
+    dex_configs.max_price_move_bps = 0;
+    dex_configs.bump = ctx.bumps.dex_configs;
+    dex_configs.is_initialized = true;
 
     // deposit account
     // * - means dereferencing
@@ -66,5 +75,11 @@ pub fn init(ctx: Context<Init>, params: &InitParams) -> Result<()> {
     deposit_account.admin_treasury_vault_bump = Some(ctx.bumps.treasury_vault);
     deposit_account.is_initialized = true;
 
+    emit!(DexInitialized {
+        owner: *ctx.accounts.owner.key,
+        pool_registry: pool_registry.key(),
+        dex_configs: dex_configs.key(),
+    });
+
     Ok(())
 }
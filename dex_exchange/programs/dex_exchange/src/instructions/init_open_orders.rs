@@ -0,0 +1,56 @@
+//! InitOpenOrders instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::OpenOrdersInitialized,
+        state::{market::Market, open_orders::OpenOrders, trader::Trader},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct InitOpenOrders<'info> {
+    #[account(
+        constraint = market.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(has_one = owner,
+        constraint = trader.active @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Account<'info, Trader>,
+    // init means to create account
+    // bump to use unique address for account
+    // one open orders account per trader and market
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + OpenOrders::INIT_SPACE,
+        seeds = [b"open-orders", market.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+    let open_orders = &mut ctx.accounts.open_orders;
+
+    open_orders.owner = *ctx.accounts.owner.key;
+    open_orders.market = ctx.accounts.market.key();
+    open_orders.base_free = 0;
+    open_orders.base_locked = 0;
+    open_orders.quote_free = 0;
+    open_orders.quote_locked = 0;
+
+    emit!(OpenOrdersInitialized {
+        market: open_orders.market,
+        open_orders: open_orders.key(),
+        owner: open_orders.owner,
+    });
+
+    Ok(())
+}
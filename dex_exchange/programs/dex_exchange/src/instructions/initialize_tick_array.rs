@@ -0,0 +1,73 @@
+//! InitializeTickArray instruction handler
+
+use {
+    crate::{
+        concentrated,
+        error::DexError,
+        events::TickArrayInitialized,
+        state::{concentrated_pool::ConcentratedPool, tick_array::TickArray},
+        tick_math::{MAX_TICK, MIN_TICK},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: InitializeTickArrayParams)]
+pub struct InitializeTickArray<'info> {
+    #[account(
+        constraint = concentrated_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub concentrated_pool: Box<Account<'info, ConcentratedPool>>,
+    // init means to create account
+    // bump to use unique address for account
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + TickArray::INIT_SPACE,
+        seeds = [
+            b"tick-array",
+            concentrated_pool.key().as_ref(),
+            params.start_tick_index.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub tick_array: Box<Account<'info, TickArray>>,
+    // anyone may pay for the tick arrays a position or swap needs
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct InitializeTickArrayParams {
+    pub start_tick_index: i32, // multiple of tick_spacing * TICK_ARRAY_SIZE
+}
+
+pub fn initialize_tick_array(
+    ctx: Context<InitializeTickArray>,
+    params: &InitializeTickArrayParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    let tick_spacing = ctx.accounts.concentrated_pool.tick_spacing;
+    let start_tick_index = params.start_tick_index;
+
+    if concentrated::tick_array_start(start_tick_index, tick_spacing) != start_tick_index
+        || start_tick_index + concentrated::tick_array_span(tick_spacing) <= MIN_TICK
+        || start_tick_index > MAX_TICK
+    {
+        return Err(DexError::InvalidTickArray.into());
+    }
+
+    let tick_array = &mut ctx.accounts.tick_array;
+
+    tick_array.pool = ctx.accounts.concentrated_pool.key();
+    tick_array.start_tick_index = start_tick_index;
+
+    emit!(TickArrayInitialized {
+        pool: tick_array.pool,
+        tick_array: tick_array.key(),
+        start_tick_index,
+    });
+
+    Ok(())
+}
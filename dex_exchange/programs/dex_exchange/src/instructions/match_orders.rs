@@ -0,0 +1,78 @@
+//! MatchOrders instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::OrderFilled,
+        matching,
+        state::{event_queue::EventQueue, market::Market, open_orders::OpenOrders},
+    },
+    anchor_lang::prelude::*,
+};
+
+// Crank, anyone may call it. The makers' open orders accounts of the queued fills are passed as
+// remaining accounts (mut); consuming stops at the first fill whose maker is missing.
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    #[account(
+        constraint = market.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = market.event_queue @ DexError::InvalidEventQueue)]
+    pub event_queue: Box<Account<'info, EventQueue>>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct MatchOrdersParams {
+    pub limit: u16, // most fills to consume
+}
+
+pub fn match_orders<'info>(
+    ctx: Context<'_, '_, 'info, 'info, MatchOrders<'info>>,
+    params: &MatchOrdersParams,
+) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let event_queue = &mut ctx.accounts.event_queue;
+    let remaining_accounts = ctx.remaining_accounts;
+
+    let mut makers = Vec::with_capacity(remaining_accounts.len());
+    for (i, account) in remaining_accounts.iter().enumerate() {
+        // the same open orders twice would be written back twice
+        if remaining_accounts[..i].iter().any(|x| x.key == account.key) {
+            return Err(DexError::InvalidOpenOrders.into());
+        }
+
+        let open_orders = Account::<OpenOrders>::try_from(account)?;
+        if open_orders.market != market.key() {
+            return Err(DexError::InvalidOpenOrders.into());
+        }
+        makers.push(open_orders);
+    }
+
+    let mut consumed = 0;
+    for event in event_queue.events.iter().take(params.limit as usize) {
+        let Some(maker) = makers.iter_mut().find(|x| x.key() == event.maker) else {
+            break;
+        };
+
+        matching::credit_maker(maker, event, market.base_lot_size)?;
+
+        emit!(OrderFilled {
+            market: market.key(),
+            maker: event.maker,
+            maker_order_id: event.maker_order_id,
+            maker_side: event.maker_side,
+            price: event.price,
+            quantity: event.quantity,
+        });
+        consumed += 1;
+    }
+
+    event_queue.events.drain(..consumed);
+
+    for maker in makers.iter() {
+        maker.exit(&crate::ID)?;
+    }
+
+    Ok(())
+}
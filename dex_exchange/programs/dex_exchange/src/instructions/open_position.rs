@@ -0,0 +1,91 @@
+//! OpenPosition instruction handler
+
+use {
+    crate::{
+        concentrated,
+        error::DexError,
+        events::PositionOpened,
+        state::{
+            concentrated_pool::ConcentratedPool, liquidity_provider::LiquidityProvider,
+            position::Position,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: OpenPositionParams)]
+pub struct OpenPosition<'info> {
+    #[account(
+        constraint = concentrated_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub concentrated_pool: Box<Account<'info, ConcentratedPool>>,
+    #[account(has_one = owner,
+        constraint = liquidity_provider.active @ DexError::InvalidLiquidityProviderStatus
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // init means to create account
+    // bump to use unique address for account
+    // one position per owner and price range
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Position::INIT_SPACE,
+        seeds = [
+            b"position",
+            concentrated_pool.key().as_ref(),
+            owner.key().as_ref(),
+            params.tick_lower.to_le_bytes().as_ref(),
+            params.tick_upper.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub position: Account<'info, Position>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct OpenPositionParams {
+    pub tick_lower: i32, // lower tick of the price range, a multiple of tick_spacing
+    pub tick_upper: i32, // upper tick of the price range, a multiple of tick_spacing
+}
+
+pub fn open_position(ctx: Context<OpenPosition>, params: &OpenPositionParams) -> Result<()> {
+    msg!("Validate inputs");
+    let tick_spacing = ctx.accounts.concentrated_pool.tick_spacing;
+
+    if !concentrated::is_valid_tick(params.tick_lower, tick_spacing)
+        || !concentrated::is_valid_tick(params.tick_upper, tick_spacing)
+    {
+        return Err(DexError::InvalidTick.into());
+    }
+
+    if params.tick_lower >= params.tick_upper {
+        return Err(DexError::InvalidTickRange.into());
+    }
+
+    let position = &mut ctx.accounts.position;
+
+    position.owner = *ctx.accounts.owner.key;
+    position.pool = ctx.accounts.concentrated_pool.key();
+    position.tick_lower = params.tick_lower;
+    position.tick_upper = params.tick_upper;
+    position.liquidity = 0;
+    position.fee_growth_inside_a_last = 0;
+    position.fee_growth_inside_b_last = 0;
+    position.tokens_owed_a = 0;
+    position.tokens_owed_b = 0;
+
+    emit!(PositionOpened {
+        pool: position.pool,
+        position: position.key(),
+        owner: position.owner,
+        tick_lower: position.tick_lower,
+        tick_upper: position.tick_upper,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! OpenStake instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::StakeOpened,
+        state::{
+            liquidity_provider::LiquidityProvider, reward_schedule::RewardSchedule,
+            stake_position::StakePosition,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct OpenStake<'info> {
+    #[account(
+        constraint = reward_schedule.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+    #[account(has_one = owner,
+        constraint = liquidity_provider.active @ DexError::InvalidLiquidityProviderStatus
+    )]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // init means to create account
+    // bump to use unique address for account
+    // one stake position per owner and reward schedule
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + StakePosition::INIT_SPACE,
+        seeds = [
+            b"stake-position",
+            reward_schedule.key().as_ref(),
+            owner.key().as_ref()
+        ],
+        bump
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn open_stake(ctx: Context<OpenStake>) -> Result<()> {
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    stake_position.owner = *ctx.accounts.owner.key;
+    stake_position.reward_schedule = ctx.accounts.reward_schedule.key();
+    stake_position.amount = 0;
+    stake_position.reward_growth_last = ctx.accounts.reward_schedule.reward_growth;
+    stake_position.rewards_owed = 0;
+
+    emit!(StakeOpened {
+        stake_position: stake_position.key(),
+        reward_schedule: stake_position.reward_schedule,
+        owner: stake_position.owner,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,202 @@
+//! PlaceOrder instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::OrderPlaced,
+        matching,
+        state::{
+            configs::DexConfigs,
+            event_queue::EventQueue,
+            market::{Market, OrderType, Side},
+            open_orders::OpenOrders,
+            order_book::{Order, OrderBook},
+            trader::Trader,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct PlaceOrder<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.orders_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,
+        constraint = market.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut, address = market.bids @ DexError::InvalidOrderBook)]
+    pub bids: Box<Account<'info, OrderBook>>,
+    #[account(mut, address = market.asks @ DexError::InvalidOrderBook)]
+    pub asks: Box<Account<'info, OrderBook>>,
+    #[account(mut, address = market.event_queue @ DexError::InvalidEventQueue)]
+    pub event_queue: Box<Account<'info, EventQueue>>,
+    #[account(mut,has_one = owner,
+        constraint = open_orders.market == market.key() @ DexError::InvalidOpenOrders
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+    #[account(has_one = owner,
+        constraint = trader.active @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Account<'info, Trader>,
+    #[account(mut,
+        constraint = sender_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>, // quote account for a bid, base account for an ask
+    #[account(mut)]
+    pub market_vault: Account<'info, TokenAccount>, // quote vault for a bid, base vault for an ask
+    pub mint: Account<'info, Mint>, // quote mint for a bid, base mint for an ask
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct PlaceOrderParams {
+    pub side: Side,
+    pub price: u64,    // quote base units per lot
+    pub quantity: u64, // lots of base_lot_size base units
+    pub order_type: OrderType,
+}
+
+pub fn place_order(mut ctx: Context<PlaceOrder>, params: &PlaceOrderParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.price == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if params.quantity == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let accounts = &mut ctx.accounts;
+    let market = &mut accounts.market;
+    let open_orders = &mut accounts.open_orders;
+    let side = params.side;
+    let base_lot_size = market.base_lot_size;
+
+    // Bids lock quote, asks lock base
+    let (vault, mint) = match side {
+        Side::Bid => (market.quote_vault, market.quote_mint),
+        Side::Ask => (market.base_vault, market.base_mint),
+    };
+
+    if accounts.market_vault.key() != vault {
+        return Err(DexError::InvalidVault.into());
+    }
+
+    if accounts.mint.key() != mint {
+        return Err(DexError::InvalidTokenMint.into());
+    }
+
+    if accounts.sender_tokens.mint != mint {
+        return Err(DexError::InvalidTokenAccount.into());
+    }
+
+    let (book, opposite) = match side {
+        Side::Bid => (&mut accounts.bids, &mut accounts.asks),
+        Side::Ask => (&mut accounts.asks, &mut accounts.bids),
+    };
+
+    if params.order_type == OrderType::PostOnly && matching::crosses(opposite, side, params.price) {
+        return Err(DexError::OrderWouldMatch.into());
+    }
+
+    // Lock the order's funds, using the free balance before depositing the rest
+    let required = matching::locked_amount(side, params.price, params.quantity, base_lot_size)?;
+    let free = match side {
+        Side::Bid => &mut open_orders.quote_free,
+        Side::Ask => &mut open_orders.base_free,
+    };
+    let from_free = required.min(*free);
+    let deposit = required - from_free;
+    *free -= from_free;
+
+    match side {
+        Side::Bid => {
+            open_orders.quote_locked = open_orders
+                .quote_locked
+                .checked_add(required)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+        Side::Ask => {
+            open_orders.base_locked = open_orders
+                .base_locked
+                .checked_add(required)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+    }
+
+    if deposit > 0 {
+        transfer_checked(
+            CpiContext::new(
+                accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: accounts.sender_tokens.to_account_info(),
+                    mint: accounts.mint.to_account_info(),
+                    to: accounts.market_vault.to_account_info(),
+                    authority: accounts.owner.to_account_info(),
+                },
+            ),
+            deposit,
+            accounts.mint.decimals,
+        )?;
+    }
+
+    // Take what the opposite side offers, post-only orders never match
+    let filled = match params.order_type {
+        OrderType::PostOnly => 0,
+        OrderType::Limit | OrderType::ImmediateOrCancel => matching::match_order(
+            opposite,
+            &mut accounts.event_queue,
+            open_orders,
+            side,
+            params.price,
+            params.quantity,
+            base_lot_size,
+        )?,
+    };
+    let remaining = params.quantity - filled;
+    let mut posted = 0;
+
+    if remaining > 0 {
+        match params.order_type {
+            OrderType::Limit | OrderType::PostOnly => {
+                let order = Order {
+                    order_id: market.next_order_id,
+                    open_orders: open_orders.key(),
+                    price: params.price,
+                    quantity: remaining,
+                };
+                matching::insert_order(book, order)?;
+                posted = remaining;
+            }
+            OrderType::ImmediateOrCancel => {
+                matching::unlock(open_orders, side, params.price, remaining, base_lot_size)?;
+            }
+        }
+    }
+
+    emit!(OrderPlaced {
+        market: market.key(),
+        open_orders: open_orders.key(),
+        order_id: market.next_order_id,
+        side,
+        price: params.price,
+        quantity: params.quantity,
+        filled,
+        posted,
+    });
+
+    // Increment next_order_id
+    market.next_order_id = market
+        .next_order_id
+        .checked_add(1)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    Ok(())
+}
@@ -0,0 +1,35 @@
+//! ProposeAdmin instruction handler
+
+use {
+    crate::{error::DexError, events::AdminProposed, state::pool::Pool},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct ProposeAdmin<'info> {
+    #[account(mut,has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct ProposeAdminParams {
+    pub new_owner: Pubkey, // pool admin to hand over to, Pubkey::default() withdraws the proposal
+}
+
+pub fn propose_admin(ctx: Context<ProposeAdmin>, params: &ProposeAdminParams) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+
+    // The owner stays in charge until the proposed admin accepts
+    liquidity_pool.pending_owner = params.new_owner;
+
+    emit!(AdminProposed {
+        pool: liquidity_pool.key(),
+        owner: liquidity_pool.owner,
+        pending_owner: liquidity_pool.pending_owner,
+    });
+
+    Ok(())
+}
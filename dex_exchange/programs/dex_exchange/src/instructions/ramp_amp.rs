@@ -0,0 +1,86 @@
+//! RampAmp instruction handler
+
+use {
+    crate::{
+        curve,
+        error::DexError,
+        events::{AmpRampStarted, AmpRampStopped},
+        state::pool::{CurveType, Pool, MAX_AMP, MAX_AMP_CHANGE, MIN_AMP, MIN_RAMP_DURATION},
+    },
+    anchor_lang::prelude::*,
+};
+
+// Shared by ramp_amp and stop_ramp_amp
+#[derive(Accounts)]
+pub struct RampAmp<'info> {
+    #[account(mut,has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = liquidity_pool.curve_type == CurveType::StableSwap @ DexError::InvalidCurve
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RampAmpParams {
+    pub future_amp: u64,      // amplification coefficient at the end of the ramp
+    pub future_amp_time: i64, // unix timestamp the ramp ends
+}
+
+pub fn ramp_amp(ctx: Context<RampAmp>, params: &RampAmpParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.future_amp < MIN_AMP || params.future_amp > MAX_AMP {
+        return Err(DexError::InvalidAmp.into());
+    }
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let now = Clock::get()?.unix_timestamp;
+    let amp = curve::current_amp(liquidity_pool, now);
+
+    if params.future_amp_time < now.saturating_add(MIN_RAMP_DURATION) {
+        return Err(DexError::InvalidAmpRamp.into());
+    }
+
+    // Limit how far a single ramp may move the coefficient, in either direction
+    let future_amp = params.future_amp;
+    if future_amp > amp.saturating_mul(MAX_AMP_CHANGE)
+        || amp > future_amp.saturating_mul(MAX_AMP_CHANGE)
+    {
+        return Err(DexError::InvalidAmpRamp.into());
+    }
+
+    // The ramp starts from the current coefficient, so a ramp in progress can be redirected
+    liquidity_pool.initial_amp = amp;
+    liquidity_pool.initial_amp_time = now;
+    liquidity_pool.future_amp = future_amp;
+    liquidity_pool.future_amp_time = params.future_amp_time;
+
+    emit!(AmpRampStarted {
+        pool: liquidity_pool.key(),
+        initial_amp: liquidity_pool.initial_amp,
+        future_amp: liquidity_pool.future_amp,
+        initial_amp_time: liquidity_pool.initial_amp_time,
+        future_amp_time: liquidity_pool.future_amp_time,
+    });
+
+    Ok(())
+}
+
+pub fn stop_ramp_amp(ctx: Context<RampAmp>) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let now = Clock::get()?.unix_timestamp;
+    let amp = curve::current_amp(liquidity_pool, now);
+
+    // Freeze the coefficient where the ramp currently is
+    liquidity_pool.initial_amp = amp;
+    liquidity_pool.initial_amp_time = now;
+    liquidity_pool.future_amp = amp;
+    liquidity_pool.future_amp_time = now;
+
+    emit!(AmpRampStopped {
+        pool: liquidity_pool.key(),
+        amp,
+    });
+
+    Ok(())
+}
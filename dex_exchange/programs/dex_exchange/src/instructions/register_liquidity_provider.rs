@@ -1,7 +1,10 @@
 //! RegisterLiquidityProvider instruction handler
 
 use {
-    crate::{error::DexError, state::liquidity_provider::LiquidityProvider},
+    crate::{
+        error::DexError, events::LiquidityProviderRegistered,
+        state::liquidity_provider::LiquidityProvider,
+    },
     anchor_lang::prelude::*,
 };
 
@@ -18,6 +21,14 @@ pub struct RegisterLiquidityProvider<'info> {
         bump
     )]
     pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // a suspended wallet can't register again until the admin reactivates it
+    #[account(
+        seeds = [b"liquidity-provider-suspension", owner.key().as_ref()],
+        bump,
+        constraint = liquidity_provider_suspension.data_is_empty() @ DexError::InvalidLiquidityProviderStatus
+    )]
+    /// CHECK: suspension record pda of the wallet, must not exist
+    pub liquidity_provider_suspension: UncheckedAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -32,6 +43,12 @@ pub fn register_liquidity_provider(ctx: Context<RegisterLiquidityProvider>) -> R
     liquidity_provider.active = true;
     liquidity_provider.reserve_a_available_funds = 0; // reserve_a available funds
     liquidity_provider.reserve_b_available_funds = 0; // reserve_b available funds
+    liquidity_provider.fee_checkpoints = Vec::new(); // fee checkpoints per pool
+
+    emit!(LiquidityProviderRegistered {
+        liquidity_provider: liquidity_provider.key(),
+        owner: liquidity_provider.owner,
+    });
 
     Ok(())
 }
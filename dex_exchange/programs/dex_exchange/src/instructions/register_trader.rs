@@ -1,7 +1,7 @@
 //! RegisterTrader instruction handler
 
 use {
-    crate::{error::DexError, state::trader::Trader},
+    crate::{error::DexError, events::TraderRegistered, state::trader::Trader},
     anchor_lang::prelude::*,
 };
 
@@ -18,6 +18,14 @@ pub struct RegisterTrader<'info> {
         bump
     )]
     pub trader: Account<'info, Trader>,
+    // a suspended wallet can't register again until the admin reactivates it
+    #[account(
+        seeds = [b"trader-suspension", owner.key().as_ref()],
+        bump,
+        constraint = trader_suspension.data_is_empty() @ DexError::InvalidTraderStatus
+    )]
+    /// CHECK: suspension record pda of the wallet, must not exist
+    pub trader_suspension: UncheckedAccount<'info>,
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -30,6 +38,14 @@ pub fn register_trader(ctx: Context<RegisterTrader>) -> Result<()> {
     // * - means dereferencing
     trader.owner = *ctx.accounts.owner.key;
     trader.active = true;
+    trader.swap_count = 0; // swaps made by the trader
+    trader.last_swap_timestamp = 0; // unix timestamp of the last swap
+    trader.volumes = Vec::new(); // swap volumes per mint
+
+    emit!(TraderRegistered {
+        trader: trader.key(),
+        owner: trader.owner,
+    });
 
     Ok(())
 }
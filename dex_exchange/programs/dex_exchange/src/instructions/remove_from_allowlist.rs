@@ -0,0 +1,37 @@
+//! RemoveFromAllowlist instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::AllowlistEntryRemoved,
+        state::{allowlist_entry::AllowlistEntry, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct RemoveFromAllowlist<'info> {
+    #[account(has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    // close refunds the rent to the pool admin
+    #[account(mut, close = owner,
+        constraint = allowlist_entry.pool == liquidity_pool.key() @ DexError::NotAllowlisted
+    )]
+    pub allowlist_entry: Account<'info, AllowlistEntry>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+    let allowlist_entry = &ctx.accounts.allowlist_entry;
+
+    emit!(AllowlistEntryRemoved {
+        pool: allowlist_entry.pool,
+        wallet: allowlist_entry.wallet,
+    });
+
+    Ok(())
+}
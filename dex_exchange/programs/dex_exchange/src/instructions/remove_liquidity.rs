@@ -0,0 +1,238 @@
+//! RemoveLiquidity instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::LiquidityRemoved,
+        fees, oracle,
+        state::{
+            configs::DexConfigs, liquidity_provider::LiquidityProvider, observations::Observations,
+            pool::Pool,
+        },
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{burn, transfer_checked, Burn, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(params: RemoveLiquidityParams)]
+pub struct RemoveLiquidity<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.liquidity_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    #[account(mut, address = liquidity_pool.observations @ DexError::InvalidObservations)]
+    pub observations: Box<Account<'info, Observations>>,
+    // a suspended liquidity provider can still exit, only new deposits are blocked
+    #[account(mut,has_one = owner)]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    #[account(mut, address = liquidity_pool.vault_a @ DexError::InvalidVault)]
+    pub pool_tokens_a: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_a @ DexError::InvalidTokenMint)]
+    pub mint_token_a: Account<'info, Mint>,
+    #[account(mut, address = liquidity_pool.vault_b @ DexError::InvalidVault)]
+    pub pool_tokens_b: Account<'info, TokenAccount>,
+    #[account(address = liquidity_pool.token_b @ DexError::InvalidTokenMint)]
+    pub mint_token_b: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_tokens_a.mint == liquidity_pool.token_a @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens_a: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_tokens_b.mint == liquidity_pool.token_b @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens_b: Account<'info, TokenAccount>,
+    #[account(mut, address = liquidity_pool.lp_mint @ DexError::InvalidLpMint)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(mut,
+        constraint = sender_lp_tokens.mint == lp_mint.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_lp_tokens: Account<'info, TokenAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct RemoveLiquidityParams {
+    pub shares: u64,           // lp tokens to burn
+    pub minimum_amount_a: u64, // minimum amount of token a to receive
+    pub minimum_amount_b: u64, // minimum amount of token b to receive
+}
+
+pub fn remove_liquidity(
+    ctx: Context<RemoveLiquidity>,
+    params: &RemoveLiquidityParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.shares == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let pool_tokens_a = &ctx.accounts.pool_tokens_a;
+    let pool_tokens_b = &ctx.accounts.pool_tokens_b;
+    let mint_token_a = &ctx.accounts.mint_token_a;
+    let mint_token_b = &ctx.accounts.mint_token_b;
+    let recipient_tokens_a = &ctx.accounts.recipient_tokens_a;
+    let recipient_tokens_b = &ctx.accounts.recipient_tokens_b;
+    let lp_mint = &ctx.accounts.lp_mint;
+    let sender_lp_tokens = &ctx.accounts.sender_lp_tokens;
+    let sender = &ctx.accounts.owner;
+    let token_program = &ctx.accounts.token_program;
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+    let token_a_reserve = liquidity_pool.reserve_a;
+    let token_b_reserve = liquidity_pool.reserve_b;
+    let total_shares = liquidity_pool.total_shares;
+    let shares = params.shares;
+
+    if shares > sender_lp_tokens.amount {
+        return Err(DexError::InsufficientShares.into());
+    }
+
+    // Pro-rata share of each reserve
+    let amount_a = (token_a_reserve as u128)
+        .checked_mul(shares as u128)
+        .ok_or(DexError::InvalidArithmeticOperation)?
+        .checked_div(total_shares as u128)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let amount_a = u64::try_from(amount_a).map_err(|_| DexError::InvalidArithmeticOperation)?;
+
+    let amount_b = (token_b_reserve as u128)
+        .checked_mul(shares as u128)
+        .ok_or(DexError::InvalidArithmeticOperation)?
+        .checked_div(total_shares as u128)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let amount_b = u64::try_from(amount_b).map_err(|_| DexError::InvalidArithmeticOperation)?;
+
+    if amount_a == 0 || amount_b == 0 {
+        return Err(DexError::InsufficientLiquidityBurned.into());
+    }
+
+    if amount_a < params.minimum_amount_a || amount_b < params.minimum_amount_b {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    // Accumulate the price oracle before the reserves move
+    oracle::update(
+        liquidity_pool,
+        &mut ctx.accounts.observations,
+        Clock::get()?.unix_timestamp,
+    );
+
+    // Decrement reserve_a with amount_a
+    liquidity_pool.reserve_a = token_a_reserve
+        .checked_sub(amount_a)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Decrement reserve_b with amount_b
+    liquidity_pool.reserve_b = token_b_reserve
+        .checked_sub(amount_b)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Decrement total_shares with shares
+    liquidity_pool.total_shares = total_shares
+        .checked_sub(shares)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Credit the fees earned so far before the provider's fee shares change, lp tokens beyond
+    // the provider's fee shares were received by transfer and are redeemed without fees
+    let pool_key = liquidity_pool.key();
+    if let Some(index) = fees::checkpoint_index(liquidity_provider, &pool_key) {
+        let checkpoint = &mut liquidity_provider.fee_checkpoints[index];
+        fees::update_checkpoint(checkpoint, liquidity_pool)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Decrement the fee shares with the burned shares they cover
+        let fee_shares = shares.min(checkpoint.shares);
+        checkpoint.shares -= fee_shares;
+        liquidity_pool.fee_shares = liquidity_pool
+            .fee_shares
+            .checked_sub(fee_shares)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+    }
+    fees::prune_checkpoints(liquidity_provider);
+
+    // Swaps move the reserves, so the pro-rata amounts may exceed what was deposited
+    liquidity_provider.reserve_a_available_funds = liquidity_provider
+        .reserve_a_available_funds
+        .saturating_sub(amount_a);
+    liquidity_provider.reserve_b_available_funds = liquidity_provider
+        .reserve_b_available_funds
+        .saturating_sub(amount_b);
+
+    // lp tokens
+    burn(
+        CpiContext::new(
+            token_program.to_account_info(),
+            Burn {
+                mint: lp_mint.to_account_info(),
+                from: sender_lp_tokens.to_account_info(),
+                authority: sender.to_account_info(),
+            },
+        ),
+        shares,
+    )?;
+
+    let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+    let seeds = &[
+        b"liquidity-pool",
+        liquidity_pool.token_a.as_ref(),
+        liquidity_pool.token_b.as_ref(),
+        fee_tier.as_ref(),
+        &[liquidity_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // token a
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: pool_tokens_a.to_account_info(),
+                mint: mint_token_a.to_account_info(),
+                to: recipient_tokens_a.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_a,
+        mint_token_a.decimals,
+    )?;
+
+    // token b
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: pool_tokens_b.to_account_info(),
+                mint: mint_token_b.to_account_info(),
+                to: recipient_tokens_b.to_account_info(),
+                authority: liquidity_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_b,
+        mint_token_b.decimals,
+    )?;
+
+    emit!(LiquidityRemoved {
+        pool: pool_key,
+        liquidity_provider: ctx.accounts.liquidity_provider.key(),
+        amount_a,
+        amount_b,
+        shares,
+        reserve_a: liquidity_pool.reserve_a,
+        reserve_b: liquidity_pool.reserve_b,
+        total_shares: liquidity_pool.total_shares,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,120 @@
+//! SetPaused instruction handler
+
+use {
+    crate::{
+        curve,
+        error::DexError,
+        events::{CircuitBreakerUpdated, PauseFlagsUpdated},
+        math,
+        state::{configs::DexConfigs, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+};
+
+// Shared by set_paused and set_circuit_breaker
+#[derive(Accounts)]
+pub struct UpdateDexConfigs<'info> {
+    #[account(mut,has_one = owner,
+        seeds = [b"dex-configs"],
+        bump = dex_configs.bump,
+        constraint = dex_configs.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPausedParams {
+    pub swaps: bool,       // swap, swap_exact_out, swap_route and swap_concentrated
+    pub liquidity: bool, // add_liquidity, remove_liquidity, increase_liquidity and decrease_liquidity
+    pub flash_loans: bool, // flash_borrow
+    pub orders: bool,    // place_order
+}
+
+pub fn set_paused(ctx: Context<UpdateDexConfigs>, params: &SetPausedParams) -> Result<()> {
+    let dex_configs = &mut ctx.accounts.dex_configs;
+
+    dex_configs.swaps_paused = params.swaps;
+    dex_configs.liquidity_paused = params.liquidity;
+    dex_configs.flash_loans_paused = params.flash_loans;
+    dex_configs.orders_paused = params.orders;
+
+    emit!(PauseFlagsUpdated {
+        swaps: dex_configs.swaps_paused,
+        liquidity: dex_configs.liquidity_paused,
+        flash_loans: dex_configs.flash_loans_paused,
+        orders: dex_configs.orders_paused,
+    });
+
+    Ok(())
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetCircuitBreakerParams {
+    pub max_price_move_bps: u16, // largest price move a single swap may cause, 0 disables the circuit breaker
+}
+
+pub fn set_circuit_breaker(
+    ctx: Context<UpdateDexConfigs>,
+    params: &SetCircuitBreakerParams,
+) -> Result<()> {
+    let dex_configs = &mut ctx.accounts.dex_configs;
+
+    dex_configs.max_price_move_bps = params.max_price_move_bps;
+
+    emit!(CircuitBreakerUpdated {
+        max_price_move_bps: dex_configs.max_price_move_bps,
+    });
+
+    Ok(())
+}
+
+// Checks that a swap moving the Q64.64 price from price_before to price_after stays within the
+// circuit breaker. Shared by swap_concentrated and check_pool_price_move.
+pub fn check_price_move(
+    dex_configs: &DexConfigs,
+    price_before: u128,
+    price_after: u128,
+) -> Result<()> {
+    if dex_configs.max_price_move_bps == 0 {
+        return Ok(());
+    }
+
+    // a price pushed to zero is a move beyond any limit
+    let price_move = math::price_move_bps(price_before, price_after).unwrap_or(u128::MAX);
+    if price_move > dex_configs.max_price_move_bps as u128 {
+        return Err(DexError::PriceMoveExceeded.into());
+    }
+
+    Ok(())
+}
+
+// Checks the move of the pool's marginal price since the reserves were (reserve_a_before,
+// reserve_b_before) against the circuit breaker. Shared by swap, swap_exact_out and swap_route.
+pub fn check_pool_price_move(
+    dex_configs: &DexConfigs,
+    liquidity_pool: &Pool,
+    reserve_a_before: u64,
+    reserve_b_before: u64,
+    now: i64,
+) -> Result<()> {
+    let price_before = curve::prices(liquidity_pool, reserve_a_before, reserve_b_before, now);
+    let price_after = curve::prices(
+        liquidity_pool,
+        liquidity_pool.reserve_a,
+        liquidity_pool.reserve_b,
+        now,
+    );
+
+    match (price_before, price_after) {
+        (Some((price_before, _)), Some((price_after, _))) => {
+            check_price_move(dex_configs, price_before, price_after)
+        }
+        // a drained reserve is a move beyond any limit
+        (Some(_), None) if dex_configs.max_price_move_bps > 0 => {
+            Err(DexError::PriceMoveExceeded.into())
+        }
+        // an empty pool has no price to move
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,57 @@
+//! SetPoolAllowlist instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::PoolAllowlistUpdated,
+        state::{allowlist_entry::AllowlistEntry, pool::Pool},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetPoolAllowlist<'info> {
+    #[account(mut,has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SetPoolAllowlistParams {
+    pub enabled: bool, // only allowlisted wallets may swap, add liquidity and flash borrow
+}
+
+pub fn set_pool_allowlist(
+    ctx: Context<SetPoolAllowlist>,
+    params: &SetPoolAllowlistParams,
+) -> Result<()> {
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+
+    liquidity_pool.allowlist_enabled = params.enabled;
+
+    emit!(PoolAllowlistUpdated {
+        pool: liquidity_pool.key(),
+        enabled: liquidity_pool.allowlist_enabled,
+    });
+
+    Ok(())
+}
+
+// Checks that wallet may use the pool, allowlist_entry is only required in allowlist mode.
+// Shared by swap, swap_exact_out, add_liquidity and flash_borrow.
+pub fn check_allowlist(
+    liquidity_pool: &Account<Pool>,
+    allowlist_entry: &Option<Account<AllowlistEntry>>,
+    wallet: &Pubkey,
+) -> Result<()> {
+    if !liquidity_pool.allowlist_enabled {
+        return Ok(());
+    }
+
+    match allowlist_entry {
+        Some(entry) if entry.pool == liquidity_pool.key() && entry.wallet == *wallet => Ok(()),
+        _ => Err(DexError::NotAllowlisted.into()),
+    }
+}
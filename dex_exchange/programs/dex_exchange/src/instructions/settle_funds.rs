@@ -0,0 +1,106 @@
+//! SettleFunds instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::FundsSettled,
+        state::{market::Market, open_orders::OpenOrders},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+pub struct SettleFunds<'info> {
+    #[account(
+        constraint = market.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub market: Box<Account<'info, Market>>,
+    #[account(mut,has_one = owner,
+        constraint = open_orders.market == market.key() @ DexError::InvalidOpenOrders
+    )]
+    pub open_orders: Account<'info, OpenOrders>,
+    #[account(mut, address = market.base_vault @ DexError::InvalidVault)]
+    pub base_vault: Account<'info, TokenAccount>,
+    #[account(mut, address = market.quote_vault @ DexError::InvalidVault)]
+    pub quote_vault: Account<'info, TokenAccount>,
+    #[account(address = market.base_mint @ DexError::InvalidTokenMint)]
+    pub base_mint: Account<'info, Mint>,
+    #[account(address = market.quote_mint @ DexError::InvalidTokenMint)]
+    pub quote_mint: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_base_tokens.mint == market.base_mint @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_base_tokens: Account<'info, TokenAccount>,
+    #[account(mut,
+        constraint = recipient_quote_tokens.mint == market.quote_mint @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_quote_tokens: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn settle_funds(ctx: Context<SettleFunds>) -> Result<()> {
+    let market = &ctx.accounts.market;
+    let token_program = &ctx.accounts.token_program;
+    let open_orders = &mut ctx.accounts.open_orders;
+    let base_amount = open_orders.base_free;
+    let quote_amount = open_orders.quote_free;
+
+    open_orders.base_free = 0;
+    open_orders.quote_free = 0;
+
+    let seeds = &[
+        b"market",
+        market.base_mint.as_ref(),
+        market.quote_mint.as_ref(),
+        &[market.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // base
+    if base_amount > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.base_vault.to_account_info(),
+                    mint: ctx.accounts.base_mint.to_account_info(),
+                    to: ctx.accounts.recipient_base_tokens.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            base_amount,
+            ctx.accounts.base_mint.decimals,
+        )?;
+    }
+
+    // quote
+    if quote_amount > 0 {
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.quote_vault.to_account_info(),
+                    mint: ctx.accounts.quote_mint.to_account_info(),
+                    to: ctx.accounts.recipient_quote_tokens.to_account_info(),
+                    authority: market.to_account_info(),
+                },
+                signer,
+            ),
+            quote_amount,
+            ctx.accounts.quote_mint.decimals,
+        )?;
+    }
+
+    emit!(FundsSettled {
+        market: market.key(),
+        open_orders: ctx.accounts.open_orders.key(),
+        base_amount,
+        quote_amount,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! StakeLpTokens instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::LpTokensStaked,
+        rewards,
+        state::{reward_schedule::RewardSchedule, stake_position::StakePosition},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+// Shared by stake_lp_tokens and unstake_lp_tokens
+#[derive(Accounts)]
+pub struct ModifyStake<'info> {
+    #[account(mut,
+        constraint = reward_schedule.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub reward_schedule: Box<Account<'info, RewardSchedule>>,
+    #[account(mut,has_one = owner,
+        constraint = stake_position.reward_schedule == reward_schedule.key() @ DexError::InvalidStakePosition
+    )]
+    pub stake_position: Account<'info, StakePosition>,
+    #[account(mut, address = reward_schedule.stake_vault @ DexError::InvalidVault)]
+    pub stake_vault: Account<'info, TokenAccount>,
+    #[account(address = stake_vault.mint @ DexError::InvalidLpMint)]
+    pub lp_mint: Account<'info, Mint>,
+    #[account(mut,
+        constraint = owner_lp_tokens.owner == owner.key() @ DexError::InvalidTokenAccount,
+        constraint = owner_lp_tokens.mint == lp_mint.key() @ DexError::InvalidTokenAccount
+    )]
+    pub owner_lp_tokens: Account<'info, TokenAccount>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ModifyStake<'info> {
+    // Accumulates the schedule's rewards up to now and credits the stake's share of them
+    pub fn accrue(&mut self) -> Result<()> {
+        rewards::update_schedule(&mut self.reward_schedule, Clock::get()?.unix_timestamp)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+        rewards::update_stake(&mut self.stake_position, &self.reward_schedule)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+        Ok(())
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct StakeLpTokensParams {
+    pub amount: u64, // lp tokens to stake, in base units
+}
+
+pub fn stake_lp_tokens(ctx: Context<ModifyStake>, params: &StakeLpTokensParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    ctx.accounts.accrue()?;
+
+    let reward_schedule = &mut ctx.accounts.reward_schedule;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    // Increment amount with the staked lp tokens
+    stake_position.amount = stake_position
+        .amount
+        .checked_add(params.amount)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    // Increment total_staked with the staked lp tokens
+    reward_schedule.total_staked = reward_schedule
+        .total_staked
+        .checked_add(params.amount)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let accounts = &ctx.accounts;
+
+    // Transfer the lp tokens from the owner to the stake vault
+    transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.owner_lp_tokens.to_account_info(),
+                mint: accounts.lp_mint.to_account_info(),
+                to: accounts.stake_vault.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        params.amount,
+        accounts.lp_mint.decimals,
+    )?;
+
+    emit!(LpTokensStaked {
+        stake_position: accounts.stake_position.key(),
+        reward_schedule: accounts.reward_schedule.key(),
+        amount: params.amount,
+        total_staked: accounts.reward_schedule.total_staked,
+    });
+
+    Ok(())
+}
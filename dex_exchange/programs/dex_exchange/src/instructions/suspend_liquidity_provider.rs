@@ -0,0 +1,106 @@
+//! SuspendLiquidityProvider instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::LiquidityProviderStatusUpdated,
+        state::{
+            liquidity_provider::LiquidityProvider, pool_registry::PoolRegistry,
+            suspension::Suspension,
+        },
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SuspendLiquidityProvider<'info> {
+    // the registry owner is the dex admin
+    #[account(has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(mut)]
+    pub liquidity_provider: Account<'info, LiquidityProvider>,
+    // outlives the liquidity provider account, register_liquidity_provider is rejected while it exists
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Suspension::INIT_SPACE,
+        seeds = [b"liquidity-provider-suspension", liquidity_provider.owner.as_ref()],
+        bump
+    )]
+    pub liquidity_provider_suspension: Account<'info, Suspension>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateLiquidityProvider<'info> {
+    // the registry owner is the dex admin
+    #[account(has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    // None once the suspended liquidity provider closed its account, reactivation then only
+    // lifts the record
+    #[account(mut,
+        constraint = liquidity_provider.owner == liquidity_provider_suspension.wallet @ DexError::InvalidLiquidityProviderStatus
+    )]
+    pub liquidity_provider: Option<Account<'info, LiquidityProvider>>,
+    // close refunds the rent to the admin
+    #[account(mut, close = owner,
+        seeds = [b"liquidity-provider-suspension", liquidity_provider_suspension.wallet.as_ref()],
+        bump = liquidity_provider_suspension.bump
+    )]
+    pub liquidity_provider_suspension: Account<'info, Suspension>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn suspend_liquidity_provider(ctx: Context<SuspendLiquidityProvider>) -> Result<()> {
+    let liquidity_provider = &mut ctx.accounts.liquidity_provider;
+
+    if !liquidity_provider.active {
+        return Err(DexError::InvalidLiquidityProviderStatus.into());
+    }
+
+    liquidity_provider.active = false;
+
+    let liquidity_provider_suspension = &mut ctx.accounts.liquidity_provider_suspension;
+    liquidity_provider_suspension.wallet = liquidity_provider.owner;
+    liquidity_provider_suspension.bump = ctx.bumps.liquidity_provider_suspension;
+
+    emit!(LiquidityProviderStatusUpdated {
+        liquidity_provider: liquidity_provider.key(),
+        owner: liquidity_provider.owner,
+        active: liquidity_provider.active,
+    });
+
+    Ok(())
+}
+
+pub fn reactivate_liquidity_provider(ctx: Context<ReactivateLiquidityProvider>) -> Result<()> {
+    let wallet = ctx.accounts.liquidity_provider_suspension.wallet;
+
+    let liquidity_provider_key = match &mut ctx.accounts.liquidity_provider {
+        Some(liquidity_provider) => {
+            liquidity_provider.active = true;
+            liquidity_provider.key()
+        }
+        None => {
+            Pubkey::find_program_address(&[b"liquidity-provider", wallet.as_ref()], ctx.program_id)
+                .0
+        }
+    };
+
+    emit!(LiquidityProviderStatusUpdated {
+        liquidity_provider: liquidity_provider_key,
+        owner: wallet,
+        active: true,
+    });
+
+    Ok(())
+}
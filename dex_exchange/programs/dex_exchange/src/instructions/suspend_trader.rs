@@ -0,0 +1,99 @@
+//! SuspendTrader instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::TraderStatusUpdated,
+        state::{pool_registry::PoolRegistry, suspension::Suspension, trader::Trader},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SuspendTrader<'info> {
+    // the registry owner is the dex admin
+    #[account(has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    #[account(mut)]
+    pub trader: Account<'info, Trader>,
+    // outlives the trader account, register_trader is rejected while it exists
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Suspension::INIT_SPACE,
+        seeds = [b"trader-suspension", trader.owner.as_ref()],
+        bump
+    )]
+    pub trader_suspension: Account<'info, Suspension>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ReactivateTrader<'info> {
+    // the registry owner is the dex admin
+    #[account(has_one = owner,
+        constraint = pool_registry.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub pool_registry: Account<'info, PoolRegistry>,
+    // None once the suspended trader closed its account, reactivation then only lifts the record
+    #[account(mut,
+        constraint = trader.owner == trader_suspension.wallet @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Option<Account<'info, Trader>>,
+    // close refunds the rent to the admin
+    #[account(mut, close = owner,
+        seeds = [b"trader-suspension", trader_suspension.wallet.as_ref()],
+        bump = trader_suspension.bump
+    )]
+    pub trader_suspension: Account<'info, Suspension>,
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+}
+
+pub fn suspend_trader(ctx: Context<SuspendTrader>) -> Result<()> {
+    let trader = &mut ctx.accounts.trader;
+
+    if !trader.active {
+        return Err(DexError::InvalidTraderStatus.into());
+    }
+
+    trader.active = false;
+
+    let trader_suspension = &mut ctx.accounts.trader_suspension;
+    trader_suspension.wallet = trader.owner;
+    trader_suspension.bump = ctx.bumps.trader_suspension;
+
+    emit!(TraderStatusUpdated {
+        trader: trader.key(),
+        owner: trader.owner,
+        active: trader.active,
+    });
+
+    Ok(())
+}
+
+pub fn reactivate_trader(ctx: Context<ReactivateTrader>) -> Result<()> {
+    let wallet = ctx.accounts.trader_suspension.wallet;
+
+    let trader_key = match &mut ctx.accounts.trader {
+        Some(trader) => {
+            trader.active = true;
+            trader.key()
+        }
+        None => Pubkey::find_program_address(&[b"trader", wallet.as_ref()], ctx.program_id).0,
+    };
+
+    emit!(TraderStatusUpdated {
+        trader: trader_key,
+        owner: wallet,
+        active: true,
+    });
+
+    Ok(())
+}
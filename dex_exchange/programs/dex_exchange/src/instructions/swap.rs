@@ -2,33 +2,59 @@
 
 use {
     crate::{
+        curve,
         error::DexError,
-        state::{pool::Pool, trader::Trader},
+        events::Swapped,
+        fees,
+        instructions::{set_paused::check_pool_price_move, set_pool_allowlist::check_allowlist},
+        math, oracle,
+        state::{
+            allowlist_entry::AllowlistEntry, configs::DexConfigs, observations::Observations,
+            pool::Pool, trader::Trader,
+        },
+        stats,
     },
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{transfer, Mint, Token, TokenAccount, Transfer},
+        token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
     },
 };
 
+// Shared by swap and swap_exact_out
 #[derive(Accounts)]
-#[instruction(params: SwapParams)]
 pub struct Swap<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.swaps_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
     #[account(mut,
-        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized,
+        constraint = !liquidity_pool.flash_loan_active @ DexError::FlashLoanActive
     )]
     pub liquidity_pool: Account<'info, Pool>,
+    #[account(mut, address = liquidity_pool.observations @ DexError::InvalidObservations)]
+    pub observations: Box<Account<'info, Observations>>,
     #[account(mut,has_one = owner,
         constraint = trader.active @ DexError::InvalidTraderStatus
     )]
     pub trader: Account<'info, Trader>,
+    // required when the pool is in allowlist mode
+    pub allowlist_entry: Option<Account<'info, AllowlistEntry>>,
+    #[account(mut,
+        constraint = sender_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>, // trader's token_in account
+    #[account(mut,
+        constraint = recipient_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>, // trader's token_out account
     #[account(mut)]
-    pub sender_tokens: Account<'info, TokenAccount>,
-    #[account(mut)]
-    pub recipient_tokens: Account<'info, TokenAccount>,
+    pub pool_tokens_in: Account<'info, TokenAccount>, // pool vault receiving token_in
     #[account(mut)]
-    pub mint_token: Account<'info, Mint>,
+    pub pool_tokens_out: Account<'info, TokenAccount>, // pool vault paying out token_out
+    pub mint_token_in: Account<'info, Mint>, // mint of token_in
+    pub mint_token_out: Account<'info, Mint>, // mint of token_out
     // mut makes it changeble (mutable)
     #[account(mut)]
     pub owner: Signer<'info>,
@@ -37,87 +63,298 @@ pub struct Swap<'info> {
     pub system_program: Program<'info, System>,
 }
 
-#[derive(AnchorSerialize, AnchorDeserialize)]
-pub struct SwapParams {
-    pub amount_in: u32,
-    pub token_in: Pubkey,
-}
+impl<'info> Swap<'info> {
+    // Returns (reserve_in, reserve_out) for token_in, after checking the allowlist and that
+    // the token accounts of both legs use the pool's mints and vaults
+    pub fn reserves(&self, token_in: &Pubkey) -> Result<(u64, u64)> {
+        let liquidity_pool = &self.liquidity_pool;
 
-pub fn swap(ctx: Context<Swap>, params: &SwapParams) -> Result<()> {
-    msg!("Validate inputs");
-    if params.amount_in == 0 {
-        return Err(DexError::InvalidAmount.into());
+        check_allowlist(liquidity_pool, &self.allowlist_entry, self.owner.key)?;
+
+        let (reserve_in, reserve_out, token_out, vault_in, vault_out) =
+            if *token_in == liquidity_pool.token_a {
+                (
+                    liquidity_pool.reserve_a,
+                    liquidity_pool.reserve_b,
+                    liquidity_pool.token_b,
+                    liquidity_pool.vault_a,
+                    liquidity_pool.vault_b,
+                )
+            } else if *token_in == liquidity_pool.token_b {
+                (
+                    liquidity_pool.reserve_b,
+                    liquidity_pool.reserve_a,
+                    liquidity_pool.token_a,
+                    liquidity_pool.vault_b,
+                    liquidity_pool.vault_a,
+                )
+            } else {
+                return Err(DexError::InvalidTokenIn.into());
+            };
+
+        // Both legs must use the pool's token mints
+        if self.mint_token_in.key() != *token_in || self.mint_token_out.key() != token_out {
+            return Err(DexError::InvalidTokenMint.into());
+        }
+
+        if self.sender_tokens.mint != *token_in || self.recipient_tokens.mint != token_out {
+            return Err(DexError::InvalidTokenAccount.into());
+        }
+
+        if self.pool_tokens_in.key() != vault_in || self.pool_tokens_out.key() != vault_out {
+            return Err(DexError::InvalidVault.into());
+        }
+
+        Ok((reserve_in, reserve_out))
     }
 
-    let sender = &ctx.accounts.owner;
-    let sender_tokens = &ctx.accounts.sender_tokens;
-    let recipient_tokens = &ctx.accounts.recipient_tokens;
-    let token_program = &ctx.accounts.token_program;
-    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
-    let trader = &mut ctx.accounts.trader;
+    // Pulls amount_in of token_in into the pool vault and pays amount_out from the other vault.
+    // The fee is set aside from the reserves, protocol_fee for the protocol and the rest for the
+    // liquidity providers.
+    pub fn settle(
+        &mut self,
+        token_in: &Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+        fee: u64,
+        protocol_fee: u64,
+    ) -> Result<()> {
+        let sender = &self.owner;
+        let sender_tokens = &self.sender_tokens;
+        let recipient_tokens = &self.recipient_tokens;
+        let pool_tokens_in = &self.pool_tokens_in;
+        let pool_tokens_out = &self.pool_tokens_out;
+        let mint_token_in = &self.mint_token_in;
+        let mint_token_out = &self.mint_token_out;
+        let token_program = &self.token_program;
+        let liquidity_pool = &mut self.liquidity_pool;
+        let trader = &mut self.trader;
+        let now = Clock::get()?.unix_timestamp;
+
+        // Accumulate the price oracle before the reserves move
+        oracle::update(liquidity_pool, &mut self.observations, now);
+
+        let reserve_a_before = liquidity_pool.reserve_a;
+        let reserve_b_before = liquidity_pool.reserve_b;
+
+        update_reserves(
+            liquidity_pool,
+            token_in,
+            amount_in,
+            amount_out,
+            fee,
+            protocol_fee,
+        )?;
+
+        check_pool_price_move(
+            &self.dex_configs,
+            liquidity_pool,
+            reserve_a_before,
+            reserve_b_before,
+            now,
+        )?;
+
+        let token_out = if *token_in == liquidity_pool.token_a {
+            liquidity_pool.token_b
+        } else {
+            liquidity_pool.token_a
+        };
+
+        stats::record_swap(trader, token_in, amount_in, &token_out, amount_out, now)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Transfer token_in from the trader to the pool vault
+        transfer_checked(
+            CpiContext::new(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: sender_tokens.to_account_info(),
+                    mint: mint_token_in.to_account_info(),
+                    to: pool_tokens_in.to_account_info(),
+                    authority: sender.to_account_info(),
+                },
+            ),
+            amount_in,
+            mint_token_in.decimals,
+        )?;
+
+        let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+        let seeds = &[
+            b"liquidity-pool",
+            liquidity_pool.token_a.as_ref(),
+            liquidity_pool.token_b.as_ref(),
+            fee_tier.as_ref(),
+            &[liquidity_pool.bump],
+        ];
+
+        let signer = &[&seeds[..]];
+
+        // Transfer token_out from the pool vault to the trader
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_tokens_out.to_account_info(),
+                    mint: mint_token_out.to_account_info(),
+                    to: recipient_tokens.to_account_info(),
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_out,
+            mint_token_out.decimals,
+        )?;
+
+        emit!(Swapped {
+            pool: liquidity_pool.key(),
+            trader: trader.key(),
+            token_in: *token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            fee,
+            protocol_fee,
+            reserve_a: liquidity_pool.reserve_a,
+            reserve_b: liquidity_pool.reserve_b,
+        });
+
+        Ok(())
+    }
+}
+
+// Credits amount_in less fee to the token_in reserve, debits amount_out from the other reserve,
+// accrues protocol_fee and credits the rest of the fee to the liquidity providers.
+// Shared by swap, swap_exact_out and swap_route.
+pub fn update_reserves(
+    liquidity_pool: &mut Pool,
+    token_in: &Pubkey,
+    amount_in: u64,
+    amount_out: u64,
+    fee: u64,
+    protocol_fee: u64,
+) -> Result<()> {
     let token_a_reserve = liquidity_pool.reserve_a;
     let token_b_reserve = liquidity_pool.reserve_b;
-    let decimals = liquidity_pool.decimals as u64;
-    let amount_in = params.amount_in;
-    let mut is_token_a = false;
 
-    // Determine the token being swapped and adjust reserves accordingly
-    let (reserve_in, reserve_out) = if params.token_in == liquidity_pool.token_a {
-        is_token_a = true;
-        (token_a_reserve, token_b_reserve)
-    } else {
-        (token_b_reserve, token_a_reserve)
-    };
-
-    let amount_out = (reserve_out * amount_in) / (reserve_in + amount_in); // Simplified AMM calculation
+    let reserve_in_amount = amount_in
+        .checked_sub(fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let lp_fee = fee
+        .checked_sub(protocol_fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
 
-    if is_token_a {
+    if *token_in == liquidity_pool.token_a {
         // Increment reserve_a with amount
         liquidity_pool.reserve_a = token_a_reserve
-            .checked_add(amount_in)
+            .checked_add(reserve_in_amount)
             .ok_or(DexError::InvalidArithmeticOperation)?;
 
         // Decrement reserve_b with amount
         liquidity_pool.reserve_b = token_b_reserve
             .checked_sub(amount_out)
             .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Increment protocol_fees_a with protocol_fee
+        liquidity_pool.protocol_fees_a = liquidity_pool
+            .protocol_fees_a
+            .checked_add(protocol_fee)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
     } else {
         // Increment reserve_b with amount
         liquidity_pool.reserve_b = token_b_reserve
-            .checked_add(amount_in)
+            .checked_add(reserve_in_amount)
             .ok_or(DexError::InvalidArithmeticOperation)?;
 
         // Decrement reserve_a with amount
         liquidity_pool.reserve_a = token_a_reserve
             .checked_sub(amount_out)
             .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Increment protocol_fees_b with protocol_fee
+        liquidity_pool.protocol_fees_b = liquidity_pool
+            .protocol_fees_b
+            .checked_add(protocol_fee)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
     }
 
-    let base: u32 = 10;
-    let exponent = liquidity_pool.decimals as u32;
+    fees::accrue_lp_fee(liquidity_pool, token_in, lp_fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    Ok(())
+}
 
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (amount_out as u64)
-        .checked_mul(result as u64)
+// Returns (amount_out, fee, protocol_fee) for amount_in of token_in priced on the pool's curve
+// against reserve_in and reserve_out, after the pool's swap fee.
+pub fn quote_amount_out(
+    liquidity_pool: &Pool,
+    token_in: &Pubkey,
+    amount_in: u64,
+    reserve_in: u64,
+    reserve_out: u64,
+) -> Result<(u64, u64, u64)> {
+    // Swap fee is deducted from amount_in before pricing, the protocol keeps
+    // protocol_fee_bps of it and the rest goes to the liquidity providers
+    let fee = math::swap_fee(amount_in, liquidity_pool.fee_bps)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let protocol_fee = math::protocol_fee(fee, liquidity_pool.protocol_fee_bps)
         .ok_or(DexError::InvalidArithmeticOperation)?;
+    let amount_in_after_fee = amount_in
+        .checked_sub(fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let amount_out = curve::get_amount_out(
+        liquidity_pool,
+        token_in,
+        amount_in_after_fee,
+        reserve_in,
+        reserve_out,
+        Clock::get()?.unix_timestamp,
+    )
+    .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    if amount_out == 0 {
+        return Err(DexError::InsufficientOutputAmount.into());
+    }
+
+    Ok((amount_out, fee, protocol_fee))
+}
 
-    // tests
-    trader.amount_out = amount_out;
-    trader.amount_out_2 = _amount;
-
-    transfer(
-        CpiContext::new(
-            token_program.to_account_info(),
-            Transfer {
-                from: sender_tokens.to_account_info(),
-                to: recipient_tokens.to_account_info(),
-                authority: sender.to_account_info(),
-            },
-        ),
-        _amount,
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapParams {
+    pub amount_in: u64, // amount of token_in, in base units
+    pub token_in: Pubkey,
+    pub minimum_amount_out: u64, // least amount of token_out the trader accepts
+    pub deadline: Option<i64>,   // unix timestamp after which the swap is rejected
+}
+
+pub fn swap(ctx: Context<Swap>, params: &SwapParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount_in == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if let Some(deadline) = params.deadline {
+        if Clock::get()?.unix_timestamp > deadline {
+            return Err(DexError::DeadlineExceeded.into());
+        }
+    }
+
+    let liquidity_pool = &ctx.accounts.liquidity_pool;
+    let amount_in = params.amount_in;
+    let (reserve_in, reserve_out) = ctx.accounts.reserves(&params.token_in)?;
+
+    let (amount_out, fee, protocol_fee) = quote_amount_out(
+        liquidity_pool,
+        &params.token_in,
+        amount_in,
+        reserve_in,
+        reserve_out,
     )?;
 
-    Ok(())
+    if amount_out < params.minimum_amount_out {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    ctx.accounts
+        .settle(&params.token_in, amount_in, amount_out, fee, protocol_fee)
 }
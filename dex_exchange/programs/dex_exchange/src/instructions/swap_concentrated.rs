@@ -0,0 +1,358 @@
+//! SwapConcentrated instruction handler
+
+use {
+    crate::{
+        concentrated,
+        error::DexError,
+        events::ConcentratedSwapped,
+        instructions::set_paused::check_price_move,
+        state::{
+            concentrated_pool::ConcentratedPool, configs::DexConfigs, tick_array::TickArray,
+            trader::Trader,
+        },
+        stats,
+        tick_math::{self, MAX_SQRT_PRICE, MAX_TICK, MIN_SQRT_PRICE, MIN_TICK, Q64},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+// Most tick arrays a swap can cross
+pub const MAX_SWAP_TICK_ARRAYS: usize = 3;
+
+// The tick arrays the swap may cross are passed as remaining accounts (mut), in any order
+#[derive(Accounts)]
+pub struct SwapConcentrated<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.swaps_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,
+        constraint = concentrated_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub concentrated_pool: Box<Account<'info, ConcentratedPool>>,
+    #[account(mut,has_one = owner,
+        constraint = trader.active @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Account<'info, Trader>,
+    #[account(mut,
+        constraint = sender_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>, // trader's token_in account
+    #[account(mut,
+        constraint = recipient_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>, // trader's token_out account
+    #[account(mut)]
+    pub pool_tokens_in: Account<'info, TokenAccount>, // pool vault receiving token_in
+    #[account(mut)]
+    pub pool_tokens_out: Account<'info, TokenAccount>, // pool vault paying out token_out
+    pub mint_token_in: Account<'info, Mint>, // mint of token_in
+    pub mint_token_out: Account<'info, Mint>, // mint of token_out
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapConcentratedParams {
+    pub amount_in: u64, // most amount of token_in to swap, in base units
+    pub token_in: Pubkey,
+    pub minimum_amount_out: u64, // least amount of token_out the trader accepts
+    pub sqrt_price_limit: Option<u128>, // Q64.64 sqrt price the swap stops at, defaults to the price bound
+    pub deadline: Option<i64>,          // unix timestamp after which the swap is rejected
+}
+
+pub fn swap_concentrated<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapConcentrated<'info>>,
+    params: &SwapConcentratedParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount_in == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if let Some(deadline) = params.deadline {
+        if Clock::get()?.unix_timestamp > deadline {
+            return Err(DexError::DeadlineExceeded.into());
+        }
+    }
+
+    let concentrated_pool = &mut ctx.accounts.concentrated_pool;
+    let tick_spacing = concentrated_pool.tick_spacing;
+
+    // Selling token a moves the price down, selling token b moves it up
+    let (a_to_b, token_out, vault_in, vault_out) = if params.token_in == concentrated_pool.token_a {
+        (
+            true,
+            concentrated_pool.token_b,
+            concentrated_pool.vault_a,
+            concentrated_pool.vault_b,
+        )
+    } else if params.token_in == concentrated_pool.token_b {
+        (
+            false,
+            concentrated_pool.token_a,
+            concentrated_pool.vault_b,
+            concentrated_pool.vault_a,
+        )
+    } else {
+        return Err(DexError::InvalidTokenIn.into());
+    };
+
+    if ctx.accounts.mint_token_in.key() != params.token_in
+        || ctx.accounts.mint_token_out.key() != token_out
+    {
+        return Err(DexError::InvalidTokenMint.into());
+    }
+
+    if ctx.accounts.sender_tokens.mint != params.token_in
+        || ctx.accounts.recipient_tokens.mint != token_out
+    {
+        return Err(DexError::InvalidTokenAccount.into());
+    }
+
+    if ctx.accounts.pool_tokens_in.key() != vault_in
+        || ctx.accounts.pool_tokens_out.key() != vault_out
+    {
+        return Err(DexError::InvalidVault.into());
+    }
+
+    let sqrt_price_limit = params.sqrt_price_limit.unwrap_or(if a_to_b {
+        MIN_SQRT_PRICE
+    } else {
+        MAX_SQRT_PRICE
+    });
+    if a_to_b
+        && (sqrt_price_limit >= concentrated_pool.sqrt_price || sqrt_price_limit < MIN_SQRT_PRICE)
+        || !a_to_b
+            && (sqrt_price_limit <= concentrated_pool.sqrt_price
+                || sqrt_price_limit > MAX_SQRT_PRICE)
+    {
+        return Err(DexError::InvalidSqrtPriceLimit.into());
+    }
+
+    let remaining_accounts = ctx.remaining_accounts;
+    if remaining_accounts.is_empty() || remaining_accounts.len() > MAX_SWAP_TICK_ARRAYS {
+        return Err(DexError::InvalidTickArray.into());
+    }
+
+    let mut tick_arrays = Vec::with_capacity(remaining_accounts.len());
+    for (i, account) in remaining_accounts.iter().enumerate() {
+        // the same tick array twice would be written back twice
+        if remaining_accounts[..i].iter().any(|x| x.key == account.key) {
+            return Err(DexError::InvalidTickArray.into());
+        }
+
+        let tick_array = Box::new(Account::<TickArray>::try_from(account)?);
+        if tick_array.pool != concentrated_pool.key() {
+            return Err(DexError::InvalidTickArray.into());
+        }
+        tick_arrays.push(tick_array);
+    }
+
+    let mut sqrt_price = concentrated_pool.sqrt_price;
+    let mut tick_current = concentrated_pool.tick_current;
+    let mut liquidity = concentrated_pool.liquidity;
+    let mut fee_growth_global_a = concentrated_pool.fee_growth_global_a;
+    let mut fee_growth_global_b = concentrated_pool.fee_growth_global_b;
+    let mut amount_remaining = params.amount_in;
+    let mut amount_out: u64 = 0;
+
+    while amount_remaining > 0 && sqrt_price != sqrt_price_limit {
+        // First usable tick at or below tick_current going down, above it going up
+        let aligned_tick = tick_current.div_euclid(tick_spacing as i32) * tick_spacing as i32;
+        let search_tick = if a_to_b {
+            aligned_tick
+        } else {
+            aligned_tick + tick_spacing as i32
+        };
+
+        let array_index = tick_arrays
+            .iter()
+            .position(|x| concentrated::tick_offset(x, search_tick, tick_spacing).is_some())
+            .ok_or(DexError::TickArrayMissing)?;
+        let (tick_next, initialized) = concentrated::next_initialized_tick(
+            &tick_arrays[array_index],
+            search_tick,
+            tick_spacing,
+            a_to_b,
+        )
+        .ok_or(DexError::TickArrayMissing)?;
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+
+        let sqrt_price_next_tick =
+            tick_math::sqrt_price_at_tick(tick_next).ok_or(DexError::InvalidTick)?;
+        let sqrt_price_target = if a_to_b {
+            sqrt_price_next_tick.max(sqrt_price_limit)
+        } else {
+            sqrt_price_next_tick.min(sqrt_price_limit)
+        };
+
+        let step = concentrated::compute_swap_step(
+            sqrt_price,
+            sqrt_price_target,
+            liquidity,
+            amount_remaining,
+            concentrated_pool.fee_bps,
+            a_to_b,
+        )
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Decrement amount_remaining with the step's input and fee
+        amount_remaining = amount_remaining
+            .checked_sub(step.amount_in)
+            .ok_or(DexError::InvalidArithmeticOperation)?
+            .checked_sub(step.fee)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // Increment amount_out with the step's output
+        amount_out = amount_out
+            .checked_add(step.amount_out)
+            .ok_or(DexError::InvalidArithmeticOperation)?;
+
+        // The fee is shared by the liquidity in range
+        if liquidity > 0 {
+            let fee_growth = tick_math::mul_div(step.fee as u128, Q64, liquidity, false)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+            if a_to_b {
+                fee_growth_global_a = fee_growth_global_a.wrapping_add(fee_growth);
+            } else {
+                fee_growth_global_b = fee_growth_global_b.wrapping_add(fee_growth);
+            }
+        }
+
+        if step.sqrt_price_next == sqrt_price_next_tick {
+            // Crossing an initialized tick moves its liquidity in or out of range
+            if initialized {
+                let offset =
+                    concentrated::tick_offset(&tick_arrays[array_index], tick_next, tick_spacing)
+                        .ok_or(DexError::TickArrayMissing)?;
+                let liquidity_net = concentrated::cross_tick(
+                    &mut tick_arrays[array_index].ticks[offset],
+                    fee_growth_global_a,
+                    fee_growth_global_b,
+                );
+
+                let liquidity_delta = if a_to_b {
+                    liquidity_net
+                        .checked_neg()
+                        .ok_or(DexError::InvalidArithmeticOperation)?
+                } else {
+                    liquidity_net
+                };
+                liquidity = liquidity
+                    .checked_add_signed(liquidity_delta)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+            }
+
+            tick_current = if a_to_b { tick_next - 1 } else { tick_next };
+        } else if step.sqrt_price_next != sqrt_price {
+            tick_current = tick_math::tick_at_sqrt_price(step.sqrt_price_next)
+                .ok_or(DexError::InvalidSqrtPrice)?;
+        }
+
+        sqrt_price = step.sqrt_price_next;
+    }
+
+    let amount_in = params
+        .amount_in
+        .checked_sub(amount_remaining)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    if amount_out == 0 {
+        return Err(DexError::InsufficientOutputAmount.into());
+    }
+
+    if amount_out < params.minimum_amount_out {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    // Price move of the swap as (sqrt_price / sqrt_price_before)^2 in Q64.64, too large to
+    // represent is beyond any limit
+    let price_ratio = tick_math::mul_div(sqrt_price, Q64, concentrated_pool.sqrt_price, false)
+        .and_then(|x| tick_math::mul_div(x, x, Q64, false))
+        .unwrap_or(u128::MAX);
+    check_price_move(&ctx.accounts.dex_configs, Q64, price_ratio)?;
+
+    concentrated_pool.sqrt_price = sqrt_price;
+    concentrated_pool.tick_current = tick_current;
+    concentrated_pool.liquidity = liquidity;
+    concentrated_pool.fee_growth_global_a = fee_growth_global_a;
+    concentrated_pool.fee_growth_global_b = fee_growth_global_b;
+
+    for tick_array in tick_arrays.iter() {
+        tick_array.exit(&crate::ID)?;
+    }
+
+    let trader = &mut ctx.accounts.trader;
+    stats::record_swap(
+        trader,
+        &params.token_in,
+        amount_in,
+        &token_out,
+        amount_out,
+        Clock::get()?.unix_timestamp,
+    )
+    .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let accounts = &ctx.accounts;
+
+    // Transfer token_in from the trader to the pool vault
+    transfer_checked(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.sender_tokens.to_account_info(),
+                mint: accounts.mint_token_in.to_account_info(),
+                to: accounts.pool_tokens_in.to_account_info(),
+                authority: accounts.owner.to_account_info(),
+            },
+        ),
+        amount_in,
+        accounts.mint_token_in.decimals,
+    )?;
+
+    let concentrated_pool = &accounts.concentrated_pool;
+    let tick_spacing = concentrated_pool.tick_spacing.to_le_bytes();
+    let seeds = &[
+        b"concentrated-pool",
+        concentrated_pool.token_a.as_ref(),
+        concentrated_pool.token_b.as_ref(),
+        tick_spacing.as_ref(),
+        &[concentrated_pool.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer token_out from the pool vault to the trader
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.pool_tokens_out.to_account_info(),
+                mint: accounts.mint_token_out.to_account_info(),
+                to: accounts.recipient_tokens.to_account_info(),
+                authority: concentrated_pool.to_account_info(),
+            },
+            signer,
+        ),
+        amount_out,
+        accounts.mint_token_out.decimals,
+    )?;
+
+    emit!(ConcentratedSwapped {
+        pool: concentrated_pool.key(),
+        trader: accounts.trader.key(),
+        token_in: params.token_in,
+        token_out,
+        amount_in,
+        amount_out,
+        sqrt_price: concentrated_pool.sqrt_price,
+        tick_current: concentrated_pool.tick_current,
+        liquidity: concentrated_pool.liquidity,
+    });
+
+    Ok(())
+}
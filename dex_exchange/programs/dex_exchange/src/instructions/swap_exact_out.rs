@@ -0,0 +1,57 @@
+//! SwapExactOut instruction handler
+
+use {
+    crate::{curve, error::DexError, instructions::swap::Swap, math},
+    anchor_lang::prelude::*,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapExactOutParams {
+    pub amount_out: u64, // amount of token_out, in base units
+    pub token_in: Pubkey,
+    pub maximum_amount_in: u64, // most amount of token_in the trader is willing to pay
+    pub deadline: Option<i64>,  // unix timestamp after which the swap is rejected
+}
+
+pub fn swap_exact_out(ctx: Context<Swap>, params: &SwapExactOutParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount_out == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if let Some(deadline) = params.deadline {
+        if Clock::get()?.unix_timestamp > deadline {
+            return Err(DexError::DeadlineExceeded.into());
+        }
+    }
+
+    let liquidity_pool = &ctx.accounts.liquidity_pool;
+    let amount_out = params.amount_out;
+    let (reserve_in, reserve_out) = ctx.accounts.reserves(&params.token_in)?;
+
+    // Input needed at the curve, then grossed up so the swap fee can be deducted from it
+    let amount_in_after_fee = curve::get_amount_in(
+        liquidity_pool,
+        &params.token_in,
+        amount_out,
+        reserve_in,
+        reserve_out,
+        Clock::get()?.unix_timestamp,
+    )
+    .ok_or(DexError::InsufficientLiquidity)?;
+    let amount_in = math::amount_in_with_fee(amount_in_after_fee, liquidity_pool.fee_bps)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let fee = amount_in
+        .checked_sub(amount_in_after_fee)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    let protocol_fee = math::protocol_fee(fee, liquidity_pool.protocol_fee_bps)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    if amount_in > params.maximum_amount_in {
+        return Err(DexError::ExcessiveInputAmount.into());
+    }
+
+    ctx.accounts
+        .settle(&params.token_in, amount_in, amount_out, fee, protocol_fee)
+}
@@ -0,0 +1,282 @@
+//! SwapRoute instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::{RouteSwapped, Swapped},
+        instructions::{
+            set_paused::check_pool_price_move,
+            swap::{quote_amount_out, update_reserves},
+        },
+        oracle,
+        state::{configs::DexConfigs, observations::Observations, pool::Pool, trader::Trader},
+        stats,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+pub const ROUTE_HOP_ACCOUNTS: usize = 5; // accounts per leg in remaining accounts
+pub const MAX_ROUTE_HOPS: usize = 4;
+
+// The legs of the route are passed as remaining accounts, ROUTE_HOP_ACCOUNTS per leg in order:
+// liquidity_pool (mut), observations (mut), pool_tokens_in (mut), pool_tokens_out (mut), mint_token_out
+#[derive(Accounts)]
+pub struct SwapRoute<'info> {
+    #[account(seeds = [b"dex-configs"], bump = dex_configs.bump,
+        constraint = !dex_configs.swaps_paused @ DexError::Paused
+    )]
+    pub dex_configs: Account<'info, DexConfigs>,
+    #[account(mut,has_one = owner,
+        constraint = trader.active @ DexError::InvalidTraderStatus
+    )]
+    pub trader: Account<'info, Trader>,
+    #[account(mut,
+        constraint = sender_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub sender_tokens: Account<'info, TokenAccount>, // trader's token_in account
+    #[account(mut,
+        constraint = recipient_tokens.owner == owner.key() @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>, // trader's account for the last leg's token_out
+    pub mint_token_in: Account<'info, Mint>, // mint of token_in
+    // mut makes it changeble (mutable)
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SwapRouteParams {
+    pub amount_in: u64, // amount of token_in paid into the first pool, in base units
+    pub token_in: Pubkey,
+    pub minimum_amount_out: u64, // least amount of the last leg's token_out the trader accepts
+    pub deadline: Option<i64>,   // unix timestamp after which the swap is rejected
+}
+
+pub fn swap_route<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+    params: &SwapRouteParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount_in == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if let Some(deadline) = params.deadline {
+        if Clock::get()?.unix_timestamp > deadline {
+            return Err(DexError::DeadlineExceeded.into());
+        }
+    }
+
+    let remaining_accounts = ctx.remaining_accounts;
+    let hops = remaining_accounts.len() / ROUTE_HOP_ACCOUNTS;
+    if hops == 0 || hops > MAX_ROUTE_HOPS || hops * ROUTE_HOP_ACCOUNTS != remaining_accounts.len() {
+        return Err(DexError::InvalidRoute.into());
+    }
+
+    let sender = &ctx.accounts.owner;
+    let sender_tokens = &ctx.accounts.sender_tokens;
+    let recipient_tokens = &ctx.accounts.recipient_tokens;
+    let mint_token_in = &ctx.accounts.mint_token_in;
+    let token_program = &ctx.accounts.token_program;
+    let dex_configs = &ctx.accounts.dex_configs;
+    let trader = &mut ctx.accounts.trader;
+
+    if mint_token_in.key() != params.token_in || sender_tokens.mint != params.token_in {
+        return Err(DexError::InvalidTokenMint.into());
+    }
+
+    let now = Clock::get()?.unix_timestamp;
+    let mut token_in = params.token_in;
+    let mut amount_in = params.amount_in;
+
+    for (hop, accounts) in remaining_accounts.chunks(ROUTE_HOP_ACCOUNTS).enumerate() {
+        let mut liquidity_pool = Account::<Pool>::try_from(&accounts[0])?;
+        let mut observations = Box::new(Account::<Observations>::try_from(&accounts[1])?);
+        let pool_tokens_in = &accounts[2];
+        let pool_tokens_out = &accounts[3];
+        let mint_token_out = Account::<Mint>::try_from(&accounts[4])?;
+
+        if !liquidity_pool.is_initialized {
+            return Err(DexError::AccountNotInitialized.into());
+        }
+
+        if liquidity_pool.flash_loan_active {
+            return Err(DexError::FlashLoanActive.into());
+        }
+
+        // Routes carry no allowlist entries, pools in allowlist mode are swapped directly
+        if liquidity_pool.allowlist_enabled {
+            return Err(DexError::NotAllowlisted.into());
+        }
+
+        if observations.key() != liquidity_pool.observations {
+            return Err(DexError::InvalidObservations.into());
+        }
+
+        // Each leg trades the previous leg's token_out
+        let (reserve_in, reserve_out, token_out, vault_in, vault_out) =
+            if token_in == liquidity_pool.token_a {
+                (
+                    liquidity_pool.reserve_a,
+                    liquidity_pool.reserve_b,
+                    liquidity_pool.token_b,
+                    liquidity_pool.vault_a,
+                    liquidity_pool.vault_b,
+                )
+            } else if token_in == liquidity_pool.token_b {
+                (
+                    liquidity_pool.reserve_b,
+                    liquidity_pool.reserve_a,
+                    liquidity_pool.token_a,
+                    liquidity_pool.vault_b,
+                    liquidity_pool.vault_a,
+                )
+            } else {
+                return Err(DexError::InvalidRoute.into());
+            };
+
+        if mint_token_out.key() != token_out {
+            return Err(DexError::InvalidTokenMint.into());
+        }
+
+        if pool_tokens_in.key() != vault_in || pool_tokens_out.key() != vault_out {
+            return Err(DexError::InvalidVault.into());
+        }
+
+        let (amount_out, fee, protocol_fee) = quote_amount_out(
+            &liquidity_pool,
+            &token_in,
+            amount_in,
+            reserve_in,
+            reserve_out,
+        )?;
+
+        // Accumulate the price oracle before the reserves move
+        oracle::update(&mut liquidity_pool, &mut observations, now);
+
+        let reserve_a_before = liquidity_pool.reserve_a;
+        let reserve_b_before = liquidity_pool.reserve_b;
+
+        update_reserves(
+            &mut liquidity_pool,
+            &token_in,
+            amount_in,
+            amount_out,
+            fee,
+            protocol_fee,
+        )?;
+
+        // Each leg is held to the circuit breaker on its own pool
+        check_pool_price_move(
+            dex_configs,
+            &liquidity_pool,
+            reserve_a_before,
+            reserve_b_before,
+            now,
+        )?;
+
+        // Only the first leg is paid by the trader, later legs are paid by the previous pool
+        if hop == 0 {
+            transfer_checked(
+                CpiContext::new(
+                    token_program.to_account_info(),
+                    TransferChecked {
+                        from: sender_tokens.to_account_info(),
+                        mint: mint_token_in.to_account_info(),
+                        to: pool_tokens_in.clone(),
+                        authority: sender.to_account_info(),
+                    },
+                ),
+                amount_in,
+                mint_token_in.decimals,
+            )?;
+        }
+
+        // The last leg pays the trader, the others pay the next pool's vault, which is
+        // validated by the next leg
+        let recipient = if hop + 1 == hops {
+            if recipient_tokens.mint != token_out {
+                return Err(DexError::InvalidTokenAccount.into());
+            }
+            recipient_tokens.to_account_info()
+        } else {
+            remaining_accounts[(hop + 1) * ROUTE_HOP_ACCOUNTS + 2].clone()
+        };
+
+        let fee_tier = liquidity_pool.fee_tier.to_le_bytes();
+        let seeds = &[
+            b"liquidity-pool",
+            liquidity_pool.token_a.as_ref(),
+            liquidity_pool.token_b.as_ref(),
+            fee_tier.as_ref(),
+            &[liquidity_pool.bump],
+        ];
+
+        let signer = &[&seeds[..]];
+
+        // Transfer token_out from the pool vault to the recipient
+        transfer_checked(
+            CpiContext::new_with_signer(
+                token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_tokens_out.clone(),
+                    mint: mint_token_out.to_account_info(),
+                    to: recipient,
+                    authority: liquidity_pool.to_account_info(),
+                },
+                signer,
+            ),
+            amount_out,
+            mint_token_out.decimals,
+        )?;
+
+        emit!(Swapped {
+            pool: liquidity_pool.key(),
+            trader: trader.key(),
+            token_in,
+            token_out,
+            amount_in,
+            amount_out,
+            fee,
+            protocol_fee,
+            reserve_a: liquidity_pool.reserve_a,
+            reserve_b: liquidity_pool.reserve_b,
+        });
+
+        // Persist before the next leg, which may route through the same pool again
+        liquidity_pool.exit(&crate::ID)?;
+        observations.exit(&crate::ID)?;
+
+        token_in = token_out;
+        amount_in = amount_out;
+    }
+
+    // amount_in now holds the last leg's output
+    if amount_in < params.minimum_amount_out {
+        return Err(DexError::SlippageExceeded.into());
+    }
+
+    // The route counts as a single swap of the first leg's token_in for the last leg's token_out
+    stats::record_swap(
+        trader,
+        &params.token_in,
+        params.amount_in,
+        &token_in,
+        amount_in,
+        now,
+    )
+    .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    emit!(RouteSwapped {
+        trader: trader.key(),
+        token_in: params.token_in,
+        token_out: token_in,
+        amount_in: params.amount_in,
+        amount_out: amount_in,
+        hops: hops as u8,
+    });
+
+    Ok(())
+}
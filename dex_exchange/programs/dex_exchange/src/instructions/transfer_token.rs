@@ -1,7 +1,7 @@
 //! TransferToken instruction handler
 
 use {
-    crate::{error::DexError, state::pool::Pool},
+    crate::{error::DexError, events::TokenTransferred, state::pool::Pool},
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token,
@@ -33,7 +33,7 @@ pub struct TransferToken<'info> {
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
 pub struct TransferTokenParams {
-    pub amount: u32,
+    pub amount: u64, // amount in base units
 }
 
 pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams) -> Result<()> {
@@ -42,20 +42,8 @@ pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams)
         return Err(DexError::InvalidAmount.into());
     }
 
-    let liquidity_pool = &ctx.accounts.liquidity_pool;
-    let decimals = liquidity_pool.decimals;
     let _amount = params.amount;
 
-    let base: u32 = 10;
-    let exponent = liquidity_pool.decimals as u32;
-
-    // lets get the amount in decimal format
-    // 10 ** 9 * 3(base 10, 9 decimals, 3 amount), // 3 amount of token to transfer (in smallest unit i.e 9 decimals)
-    let result = (base).pow(exponent);
-    let _amount = (_amount as u64)
-        .checked_mul(result as u64)
-        .ok_or(DexError::InvalidArithmeticOperation)?;
-
     transfer(
         CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
@@ -68,5 +56,11 @@ pub fn transfer_token(ctx: Context<TransferToken>, params: &TransferTokenParams)
         _amount,
     )?;
 
+    emit!(TokenTransferred {
+        from: ctx.accounts.from_account.key(),
+        to: ctx.accounts.to_account.key(),
+        amount: _amount,
+    });
+
     Ok(())
 }
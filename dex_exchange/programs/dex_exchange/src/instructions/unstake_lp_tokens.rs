@@ -0,0 +1,75 @@
+//! UnstakeLpTokens instruction handler
+
+use {
+    crate::{
+        error::DexError, events::LpTokensUnstaked, instructions::stake_lp_tokens::ModifyStake,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, TransferChecked},
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UnstakeLpTokensParams {
+    pub amount: u64, // lp tokens to unstake, in base units
+}
+
+pub fn unstake_lp_tokens(ctx: Context<ModifyStake>, params: &UnstakeLpTokensParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    if params.amount > ctx.accounts.stake_position.amount {
+        return Err(DexError::InsufficientStake.into());
+    }
+
+    ctx.accounts.accrue()?;
+
+    let reward_schedule = &mut ctx.accounts.reward_schedule;
+    let stake_position = &mut ctx.accounts.stake_position;
+
+    // Decrement amount with the unstaked lp tokens
+    stake_position.amount -= params.amount;
+
+    // Decrement total_staked with the unstaked lp tokens
+    reward_schedule.total_staked = reward_schedule
+        .total_staked
+        .checked_sub(params.amount)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let accounts = &ctx.accounts;
+    let reward_schedule = &accounts.reward_schedule;
+    let seeds = &[
+        b"reward-schedule",
+        reward_schedule.pool.as_ref(),
+        reward_schedule.reward_mint.as_ref(),
+        &[reward_schedule.bump],
+    ];
+
+    let signer = &[&seeds[..]];
+
+    // Transfer the lp tokens from the stake vault back to the owner
+    transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: accounts.stake_vault.to_account_info(),
+                mint: accounts.lp_mint.to_account_info(),
+                to: accounts.owner_lp_tokens.to_account_info(),
+                authority: reward_schedule.to_account_info(),
+            },
+            signer,
+        ),
+        params.amount,
+        accounts.lp_mint.decimals,
+    )?;
+
+    emit!(LpTokensUnstaked {
+        stake_position: accounts.stake_position.key(),
+        reward_schedule: reward_schedule.key(),
+        amount: params.amount,
+        total_staked: reward_schedule.total_staked,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+//! UpdatePoolFees instruction handler
+
+use {
+    crate::{
+        error::DexError,
+        events::PoolFeesUpdated,
+        state::pool::{Pool, BPS_DENOMINATOR},
+    },
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+#[instruction(params: UpdatePoolFeesParams)]
+pub struct UpdatePoolFees<'info> {
+    #[account(mut,has_one = owner,
+        constraint = liquidity_pool.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub liquidity_pool: Account<'info, Pool>,
+    pub owner: Signer<'info>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UpdatePoolFeesParams {
+    pub fee_bps: u16,          // swap fee charged on amount_in, in basis points
+    pub protocol_fee_bps: u16, // share of the swap fee kept by the protocol, in basis points of the fee
+}
+
+pub fn update_pool_fees(ctx: Context<UpdatePoolFees>, params: &UpdatePoolFeesParams) -> Result<()> {
+    msg!("Validate inputs");
+    if params.fee_bps as u64 >= BPS_DENOMINATOR {
+        return Err(DexError::InvalidFee.into());
+    }
+
+    if params.protocol_fee_bps as u64 > BPS_DENOMINATOR {
+        return Err(DexError::InvalidFee.into());
+    }
+
+    let liquidity_pool = &mut ctx.accounts.liquidity_pool;
+
+    liquidity_pool.fee_bps = params.fee_bps;
+    liquidity_pool.protocol_fee_bps = params.protocol_fee_bps;
+
+    emit!(PoolFeesUpdated {
+        pool: liquidity_pool.key(),
+        fee_bps: liquidity_pool.fee_bps,
+        protocol_fee_bps: liquidity_pool.protocol_fee_bps,
+    });
+
+    Ok(())
+}
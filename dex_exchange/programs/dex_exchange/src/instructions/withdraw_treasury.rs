@@ -0,0 +1,90 @@
+//! WithdrawTreasury instruction handler
+
+use {
+    crate::{error::DexError, events::TreasuryWithdrawn, state::deposit_base::DepositBase},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer_checked, Mint, Token, TokenAccount, TransferChecked},
+};
+
+#[derive(Accounts)]
+#[instruction(params: WithdrawTreasuryParams)]
+pub struct WithdrawTreasury<'info> {
+    // the dex admin that created the treasury through init
+    #[account(has_one = owner,
+        constraint = deposit_account.is_initialized @ DexError::AccountNotInitialized
+    )]
+    pub deposit_account: Account<'info, DepositBase>,
+    #[account(seeds = [b"auth", deposit_account.key().as_ref()], bump = deposit_account.admin_auth_bump)]
+    /// CHECK: no need to check this.
+    pub pda_auth: UncheckedAccount<'info>,
+    // signs for the treasury token accounts
+    #[account(seeds = [b"treasury-vault", pda_auth.key().as_ref()],
+        bump = deposit_account.admin_treasury_vault_bump.ok_or(DexError::AccountNotInitialized)?
+    )]
+    pub treasury_vault: SystemAccount<'info>,
+    #[account(mut,
+        constraint = treasury_tokens.owner == treasury_vault.key() @ DexError::InvalidTokenAccount
+    )]
+    pub treasury_tokens: Account<'info, TokenAccount>,
+    #[account(address = treasury_tokens.mint @ DexError::InvalidTokenMint)]
+    pub mint_token: Account<'info, Mint>,
+    #[account(mut,
+        constraint = recipient_tokens.mint == treasury_tokens.mint @ DexError::InvalidTokenAccount
+    )]
+    pub recipient_tokens: Account<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct WithdrawTreasuryParams {
+    pub amount: u64, // amount of the treasury's tokens to withdraw
+}
+
+pub fn withdraw_treasury(
+    ctx: Context<WithdrawTreasury>,
+    params: &WithdrawTreasuryParams,
+) -> Result<()> {
+    msg!("Validate inputs");
+    if params.amount == 0 {
+        return Err(DexError::InvalidAmount.into());
+    }
+
+    let treasury_tokens = &ctx.accounts.treasury_tokens;
+    if params.amount > treasury_tokens.amount {
+        return Err(DexError::InsufficientFunds.into());
+    }
+
+    let pda_auth = ctx.accounts.pda_auth.key();
+    let bump = ctx
+        .accounts
+        .deposit_account
+        .admin_treasury_vault_bump
+        .ok_or(DexError::AccountNotInitialized)?;
+    let seeds = &[b"treasury-vault", pda_auth.as_ref(), &[bump]];
+
+    let signer = &[&seeds[..]];
+
+    transfer_checked(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TransferChecked {
+                from: treasury_tokens.to_account_info(),
+                mint: ctx.accounts.mint_token.to_account_info(),
+                to: ctx.accounts.recipient_tokens.to_account_info(),
+                authority: ctx.accounts.treasury_vault.to_account_info(),
+            },
+            signer,
+        ),
+        params.amount,
+        ctx.accounts.mint_token.decimals,
+    )?;
+
+    emit!(TreasuryWithdrawn {
+        mint: treasury_tokens.mint,
+        recipient: ctx.accounts.recipient_tokens.key(),
+        amount: params.amount,
+    });
+
+    Ok(())
+}
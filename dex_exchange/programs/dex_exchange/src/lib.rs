@@ -1,6 +1,16 @@
+pub mod concentrated;
+pub mod curve;
 pub mod error;
+pub mod events;
+pub mod fees;
 pub mod instructions;
+pub mod matching;
+pub mod math;
+pub mod oracle;
+pub mod rewards;
 pub mod state;
+pub mod stats;
+pub mod tick_math;
 
 use {anchor_lang::prelude::*, instructions::*};
 
@@ -11,11 +21,112 @@ pub mod dex_exchange {
     use super::*;
 
     // admin instructions
-    pub fn init(ctx: Context<Init>, params: InitParams) -> Result<()> {
-        instructions::init(ctx, &params)
+    pub fn init(ctx: Context<Init>) -> Result<()> {
+        instructions::init(ctx)
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>, params: CreatePoolParams) -> Result<()> {
+        instructions::create_pool(ctx, &params)
+    }
+
+    pub fn create_concentrated_pool(
+        ctx: Context<CreateConcentratedPool>,
+        params: CreateConcentratedPoolParams,
+    ) -> Result<()> {
+        instructions::create_concentrated_pool(ctx, &params)
+    }
+
+    pub fn create_market(ctx: Context<CreateMarket>, params: CreateMarketParams) -> Result<()> {
+        instructions::create_market(ctx, &params)
+    }
+
+    pub fn update_pool_fees(
+        ctx: Context<UpdatePoolFees>,
+        params: UpdatePoolFeesParams,
+    ) -> Result<()> {
+        instructions::update_pool_fees(ctx, &params)
+    }
+
+    pub fn ramp_amp(ctx: Context<RampAmp>, params: RampAmpParams) -> Result<()> {
+        instructions::ramp_amp(ctx, &params)
+    }
+
+    pub fn stop_ramp_amp(ctx: Context<RampAmp>) -> Result<()> {
+        instructions::stop_ramp_amp(ctx)
+    }
+
+    pub fn create_reward_schedule(
+        ctx: Context<CreateRewardSchedule>,
+        params: CreateRewardScheduleParams,
+    ) -> Result<()> {
+        instructions::create_reward_schedule(ctx, &params)
+    }
+
+    pub fn suspend_trader(ctx: Context<SuspendTrader>) -> Result<()> {
+        instructions::suspend_trader(ctx)
+    }
+
+    pub fn reactivate_trader(ctx: Context<ReactivateTrader>) -> Result<()> {
+        instructions::reactivate_trader(ctx)
+    }
+
+    pub fn suspend_liquidity_provider(ctx: Context<SuspendLiquidityProvider>) -> Result<()> {
+        instructions::suspend_liquidity_provider(ctx)
+    }
+
+    pub fn reactivate_liquidity_provider(ctx: Context<ReactivateLiquidityProvider>) -> Result<()> {
+        instructions::reactivate_liquidity_provider(ctx)
+    }
+
+    pub fn set_pool_allowlist(
+        ctx: Context<SetPoolAllowlist>,
+        params: SetPoolAllowlistParams,
+    ) -> Result<()> {
+        instructions::set_pool_allowlist(ctx, &params)
+    }
+
+    pub fn add_to_allowlist(
+        ctx: Context<AddToAllowlist>,
+        params: AddToAllowlistParams,
+    ) -> Result<()> {
+        instructions::add_to_allowlist(ctx, &params)
+    }
+
+    pub fn remove_from_allowlist(ctx: Context<RemoveFromAllowlist>) -> Result<()> {
+        instructions::remove_from_allowlist(ctx)
+    }
+
+    pub fn set_paused(ctx: Context<UpdateDexConfigs>, params: SetPausedParams) -> Result<()> {
+        instructions::set_paused(ctx, &params)
+    }
+
+    pub fn set_circuit_breaker(
+        ctx: Context<UpdateDexConfigs>,
+        params: SetCircuitBreakerParams,
+    ) -> Result<()> {
+        instructions::set_circuit_breaker(ctx, &params)
+    }
+
+    pub fn propose_admin(ctx: Context<ProposeAdmin>, params: ProposeAdminParams) -> Result<()> {
+        instructions::propose_admin(ctx, &params)
+    }
+
+    pub fn collect_protocol_fees(ctx: Context<CollectProtocolFees>) -> Result<()> {
+        instructions::collect_protocol_fees(ctx)
+    }
+
+    pub fn withdraw_treasury(
+        ctx: Context<WithdrawTreasury>,
+        params: WithdrawTreasuryParams,
+    ) -> Result<()> {
+        instructions::withdraw_treasury(ctx, &params)
     }
 
     // public instructions
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        instructions::accept_admin(ctx)
+    }
+
     pub fn register_liquidity_provider(ctx: Context<RegisterLiquidityProvider>) -> Result<()> {
         instructions::register_liquidity_provider(ctx)
     }
@@ -24,14 +135,134 @@ pub mod dex_exchange {
         instructions::register_trader(ctx)
     }
 
+    pub fn close_liquidity_provider(ctx: Context<CloseLiquidityProvider>) -> Result<()> {
+        instructions::close_liquidity_provider(ctx)
+    }
+
+    pub fn close_trader(ctx: Context<CloseTrader>) -> Result<()> {
+        instructions::close_trader(ctx)
+    }
+
     pub fn add_liquidity(ctx: Context<AddLiquidity>, params: AddLiquidityParams) -> Result<()> {
         instructions::add_liquidity(ctx, &params)
     }
 
+    pub fn remove_liquidity(
+        ctx: Context<RemoveLiquidity>,
+        params: RemoveLiquidityParams,
+    ) -> Result<()> {
+        instructions::remove_liquidity(ctx, &params)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees(ctx)
+    }
+
+    pub fn open_stake(ctx: Context<OpenStake>) -> Result<()> {
+        instructions::open_stake(ctx)
+    }
+
+    pub fn stake_lp_tokens(ctx: Context<ModifyStake>, params: StakeLpTokensParams) -> Result<()> {
+        instructions::stake_lp_tokens(ctx, &params)
+    }
+
+    pub fn unstake_lp_tokens(
+        ctx: Context<ModifyStake>,
+        params: UnstakeLpTokensParams,
+    ) -> Result<()> {
+        instructions::unstake_lp_tokens(ctx, &params)
+    }
+
+    pub fn claim_rewards(ctx: Context<ClaimRewards>) -> Result<()> {
+        instructions::claim_rewards(ctx)
+    }
+
     pub fn swap(ctx: Context<Swap>, params: SwapParams) -> Result<()> {
         instructions::swap(ctx, &params)
     }
 
+    pub fn swap_exact_out(ctx: Context<Swap>, params: SwapExactOutParams) -> Result<()> {
+        instructions::swap_exact_out(ctx, &params)
+    }
+
+    pub fn swap_route<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapRoute<'info>>,
+        params: SwapRouteParams,
+    ) -> Result<()> {
+        instructions::swap_route(ctx, &params)
+    }
+
+    pub fn flash_borrow(ctx: Context<FlashBorrow>, params: FlashBorrowParams) -> Result<()> {
+        instructions::flash_borrow(ctx, &params)
+    }
+
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
+        instructions::flash_repay(ctx)
+    }
+
+    pub fn initialize_tick_array(
+        ctx: Context<InitializeTickArray>,
+        params: InitializeTickArrayParams,
+    ) -> Result<()> {
+        instructions::initialize_tick_array(ctx, &params)
+    }
+
+    pub fn open_position(ctx: Context<OpenPosition>, params: OpenPositionParams) -> Result<()> {
+        instructions::open_position(ctx, &params)
+    }
+
+    pub fn increase_liquidity(
+        ctx: Context<ModifyLiquidity>,
+        params: IncreaseLiquidityParams,
+    ) -> Result<()> {
+        instructions::increase_liquidity(ctx, &params)
+    }
+
+    pub fn decrease_liquidity(
+        ctx: Context<ModifyLiquidity>,
+        params: DecreaseLiquidityParams,
+    ) -> Result<()> {
+        instructions::decrease_liquidity(ctx, &params)
+    }
+
+    pub fn collect_position_fees(ctx: Context<ModifyLiquidity>) -> Result<()> {
+        instructions::collect_position_fees(ctx)
+    }
+
+    pub fn swap_concentrated<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SwapConcentrated<'info>>,
+        params: SwapConcentratedParams,
+    ) -> Result<()> {
+        instructions::swap_concentrated(ctx, &params)
+    }
+
+    pub fn init_open_orders(ctx: Context<InitOpenOrders>) -> Result<()> {
+        instructions::init_open_orders(ctx)
+    }
+
+    pub fn place_order(ctx: Context<PlaceOrder>, params: PlaceOrderParams) -> Result<()> {
+        instructions::place_order(ctx, &params)
+    }
+
+    pub fn cancel_order(ctx: Context<CancelOrder>, params: CancelOrderParams) -> Result<()> {
+        instructions::cancel_order(ctx, &params)
+    }
+
+    pub fn match_orders<'info>(
+        ctx: Context<'_, '_, 'info, 'info, MatchOrders<'info>>,
+        params: MatchOrdersParams,
+    ) -> Result<()> {
+        instructions::match_orders(ctx, &params)
+    }
+
+    pub fn settle_funds(ctx: Context<SettleFunds>) -> Result<()> {
+        instructions::settle_funds(ctx)
+    }
+
+    pub fn get_twap(ctx: Context<GetTwap>, params: GetTwapParams) -> Result<TwapPrice> {
+        instructions::get_twap(ctx, &params)
+    }
+
     pub fn create_token(ctx: Context<CreateToken>, params: CreateTokenParams) -> Result<()> {
         instructions::create_token(ctx, &params)
     }
@@ -0,0 +1,203 @@
+// Order book matching for the markets.
+//
+// Prices are quote base units per lot and quantities are lots of base_lot_size base units. A bid
+// locks price * quantity quote units and an ask locks quantity * base_lot_size base units in the
+// trader's open orders until the order is filled or cancelled. Takers are credited when their
+// order matches, makers when match_orders consumes the fill from the event queue.
+
+use crate::{
+    error::DexError,
+    state::{
+        event_queue::{EventQueue, FillEvent, EVENT_QUEUE_LEN},
+        market::Side,
+        open_orders::OpenOrders,
+        order_book::{Order, OrderBook, MAX_BOOK_ORDERS},
+    },
+};
+use anchor_lang::prelude::*;
+
+/// Quote units for quantity lots at price.
+pub fn quote_amount(price: u64, quantity: u64) -> Result<u64> {
+    let amount = price
+        .checked_mul(quantity)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    Ok(amount)
+}
+
+/// Base units for quantity lots.
+pub fn base_amount(quantity: u64, base_lot_size: u64) -> Result<u64> {
+    let amount = quantity
+        .checked_mul(base_lot_size)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+    Ok(amount)
+}
+
+/// Amount an order locks: quote units for a bid, base units for an ask.
+pub fn locked_amount(side: Side, price: u64, quantity: u64, base_lot_size: u64) -> Result<u64> {
+    match side {
+        Side::Bid => quote_amount(price, quantity),
+        Side::Ask => base_amount(quantity, base_lot_size),
+    }
+}
+
+/// Whether an order at price would match the best order of the opposite book.
+pub fn crosses(opposite: &OrderBook, side: Side, price: u64) -> bool {
+    match (opposite.orders.first(), side) {
+        (Some(best), Side::Bid) => best.price <= price,
+        (Some(best), Side::Ask) => best.price >= price,
+        (None, _) => false,
+    }
+}
+
+/// Rests an order on the book, after the orders with the same or a better price.
+pub fn insert_order(book: &mut OrderBook, order: Order) -> Result<()> {
+    if book.orders.len() >= MAX_BOOK_ORDERS {
+        return Err(DexError::OrderBookFull.into());
+    }
+
+    let index = match book.side {
+        Side::Bid => book.orders.iter().position(|x| x.price < order.price),
+        Side::Ask => book.orders.iter().position(|x| x.price > order.price),
+    }
+    .unwrap_or(book.orders.len());
+
+    book.orders.insert(index, order);
+    Ok(())
+}
+
+/// Matches a taker order against the opposite book at the makers' prices.
+/// Credits the taker's open orders and queues a fill for each maker. Returns the lots filled.
+pub fn match_order(
+    opposite: &mut OrderBook,
+    event_queue: &mut EventQueue,
+    taker: &mut OpenOrders,
+    side: Side,
+    price: u64,
+    quantity: u64,
+    base_lot_size: u64,
+) -> Result<u64> {
+    let mut remaining = quantity;
+
+    while remaining > 0 && crosses(opposite, side, price) {
+        if event_queue.events.len() >= EVENT_QUEUE_LEN {
+            return Err(DexError::EventQueueFull.into());
+        }
+
+        let maker = &mut opposite.orders[0];
+        let fill_quantity = remaining.min(maker.quantity);
+        let fill_price = maker.price;
+
+        event_queue.events.push(FillEvent {
+            maker: maker.open_orders,
+            maker_order_id: maker.order_id,
+            maker_side: opposite.side,
+            price: fill_price,
+            quantity: fill_quantity,
+        });
+
+        maker.quantity -= fill_quantity;
+        remaining -= fill_quantity;
+        if maker.quantity == 0 {
+            opposite.orders.remove(0);
+        }
+
+        let base = base_amount(fill_quantity, base_lot_size)?;
+        let quote = quote_amount(fill_price, fill_quantity)?;
+
+        match side {
+            Side::Bid => {
+                // the bid locked its own price, the difference to the maker's price is refunded
+                let locked = quote_amount(price, fill_quantity)?;
+                taker.quote_locked = taker
+                    .quote_locked
+                    .checked_sub(locked)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+                taker.quote_free = taker
+                    .quote_free
+                    .checked_add(locked - quote)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+                taker.base_free = taker
+                    .base_free
+                    .checked_add(base)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+            }
+            Side::Ask => {
+                taker.base_locked = taker
+                    .base_locked
+                    .checked_sub(base)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+                taker.quote_free = taker
+                    .quote_free
+                    .checked_add(quote)
+                    .ok_or(DexError::InvalidArithmeticOperation)?;
+            }
+        }
+    }
+
+    Ok(quantity - remaining)
+}
+
+/// Credits a queued fill to the maker's open orders.
+pub fn credit_maker(maker: &mut OpenOrders, event: &FillEvent, base_lot_size: u64) -> Result<()> {
+    let base = base_amount(event.quantity, base_lot_size)?;
+    let quote = quote_amount(event.price, event.quantity)?;
+
+    match event.maker_side {
+        Side::Bid => {
+            maker.quote_locked = maker
+                .quote_locked
+                .checked_sub(quote)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+            maker.base_free = maker
+                .base_free
+                .checked_add(base)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+        Side::Ask => {
+            maker.base_locked = maker
+                .base_locked
+                .checked_sub(base)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+            maker.quote_free = maker
+                .quote_free
+                .checked_add(quote)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+    }
+    Ok(())
+}
+
+/// Releases the funds locked by quantity lots of an order back to the free balances.
+pub fn unlock(
+    open_orders: &mut OpenOrders,
+    side: Side,
+    price: u64,
+    quantity: u64,
+    base_lot_size: u64,
+) -> Result<()> {
+    let amount = locked_amount(side, price, quantity, base_lot_size)?;
+
+    match side {
+        Side::Bid => {
+            open_orders.quote_locked = open_orders
+                .quote_locked
+                .checked_sub(amount)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+            open_orders.quote_free = open_orders
+                .quote_free
+                .checked_add(amount)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+        Side::Ask => {
+            open_orders.base_locked = open_orders
+                .base_locked
+                .checked_sub(amount)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+            open_orders.base_free = open_orders
+                .base_free
+                .checked_add(amount)
+                .ok_or(DexError::InvalidArithmeticOperation)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,164 @@
+// Integer math helpers for the AMM.
+
+use crate::{state::pool::BPS_DENOMINATOR, tick_math};
+
+/// Integer square root, rounded down (Babylonian method).
+pub fn sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    let mut x = value;
+    let mut y = value / 2 + 1;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Swap fee charged on amount_in, rounded down.
+pub fn swap_fee(amount_in: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (amount_in as u128).checked_mul(fee_bps as u128)? / BPS_DENOMINATOR as u128;
+    u64::try_from(fee).ok()
+}
+
+/// Flash loan fee charged on amount, rounded up.
+pub fn flash_fee(amount: u64, fee_bps: u16) -> Option<u64> {
+    let fee = (amount as u128)
+        .checked_mul(fee_bps as u128)?
+        .checked_add(BPS_DENOMINATOR as u128 - 1)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(fee).ok()
+}
+
+/// Protocol's share of a swap fee; protocol_fee_bps is expressed in basis points of the fee.
+pub fn protocol_fee(fee: u64, protocol_fee_bps: u16) -> Option<u64> {
+    let protocol_fee =
+        (fee as u128).checked_mul(protocol_fee_bps as u128)? / BPS_DENOMINATOR as u128;
+    u64::try_from(protocol_fee).ok()
+}
+
+/// Grosses an amount up so that, after the swap fee is deducted, amount_in_after_fee remains.
+/// Rounds up in favour of the pool.
+pub fn amount_in_with_fee(amount_in_after_fee: u64, fee_bps: u16) -> Option<u64> {
+    let denominator = (BPS_DENOMINATOR as u128).checked_sub(fee_bps as u128)?;
+    let amount_in = (amount_in_after_fee as u128)
+        .checked_mul(BPS_DENOMINATOR as u128)?
+        .checked_add(denominator.checked_sub(1)?)?
+        .checked_div(denominator)?;
+    u64::try_from(amount_in).ok()
+}
+
+/// Move between two Q64.64 prices in basis points of the lower one, rounded up, so a move counts
+/// the same for both tokens of the pair. None if either price is zero.
+pub fn price_move_bps(price_before: u128, price_after: u128) -> Option<u128> {
+    tick_math::mul_div(
+        price_before.abs_diff(price_after),
+        BPS_DENOMINATOR as u128,
+        price_before.min(price_after),
+        true,
+    )
+}
+
+/// Constant product output for an (after fee) input amount, rounded down.
+pub fn get_amount_out(amount_in: u64, reserve_in: u64, reserve_out: u64) -> Option<u64> {
+    let amount_out = (reserve_out as u128)
+        .checked_mul(amount_in as u128)?
+        .checked_div((reserve_in as u128).checked_add(amount_in as u128)?)?;
+    u64::try_from(amount_out).ok()
+}
+
+/// Constant product (after fee) input required for an exact output amount, rounded up.
+pub fn get_amount_in(amount_out: u64, reserve_in: u64, reserve_out: u64) -> Option<u64> {
+    if amount_out >= reserve_out {
+        return None;
+    }
+
+    let amount_in = (reserve_in as u128)
+        .checked_mul(amount_out as u128)?
+        .checked_div((reserve_out - amount_out) as u128)?
+        .checked_add(1)?;
+    u64::try_from(amount_in).ok()
+}
+
+// StableSwap invariant for two coins with amplification amp:
+// A * n^n * (x + y) + D = A * D * n^n + D^(n+1) / (n^n * x * y), with n = 2.
+// Balances are expected in a common precision. Products of D are taken step by step through
+// mul_div, so that balances up to the u128 range don't overflow.
+const STABLE_SWAP_ITERATIONS: u32 = 255;
+
+// D^3 / (4 * x * y)
+fn stable_d_p(d: u128, x: u128, y: u128) -> Option<u128> {
+    let d_p = tick_math::mul_div(d, d, x.checked_mul(2)?, false)?;
+    tick_math::mul_div(d_p, d, y.checked_mul(2)?, false)
+}
+
+/// StableSwap invariant D for balances x and y, by Newton's method.
+pub fn stable_invariant(amp: u64, x: u128, y: u128) -> Option<u128> {
+    let sum = x.checked_add(y)?;
+    if sum == 0 {
+        return Some(0);
+    }
+
+    let ann = (amp as u128).checked_mul(4)?;
+    let mut d = sum;
+    for _ in 0..STABLE_SWAP_ITERATIONS {
+        let d_p = stable_d_p(d, x, y)?;
+
+        // D = (ann * S + 2 * d_p) * D / ((ann - 1) * D + 3 * d_p)
+        let previous = d;
+        let numerator = ann.checked_mul(sum)?.checked_add(d_p.checked_mul(2)?)?;
+        let denominator = ann
+            .checked_sub(1)?
+            .checked_mul(d)?
+            .checked_add(d_p.checked_mul(3)?)?;
+        d = tick_math::mul_div(numerator, d, denominator, false)?;
+
+        if d.abs_diff(previous) <= 1 {
+            return Some(d);
+        }
+    }
+    None
+}
+
+/// StableSwap balance of the other coin that keeps the invariant d when one coin's balance is x.
+pub fn stable_balance(amp: u64, x: u128, d: u128) -> Option<u128> {
+    let ann = (amp as u128).checked_mul(4)?;
+
+    // c = D^3 / (4 * x * ann) = d_x * D / (2 * ann), b = x + D / ann
+    let d_x = tick_math::mul_div(d, d, x.checked_mul(2)?, false)?;
+    let b = x.checked_add(d.checked_div(ann)?)?;
+
+    let mut y = d;
+    for _ in 0..STABLE_SWAP_ITERATIONS {
+        // y = (y^2 + c) / (2 * y + b - D), each term divided on its own, c rounded up so that y
+        // doesn't come out lower than with a single division
+        let previous = y;
+        let denominator = y.checked_mul(2)?.checked_add(b)?.checked_sub(d)?;
+        let c_term =
+            tick_math::mul_div(d_x, d, ann.checked_mul(2)?.checked_mul(denominator)?, true)?;
+        y = tick_math::mul_div(y, y, denominator, false)?.checked_add(c_term)?;
+
+        if y.abs_diff(previous) <= 1 {
+            return Some(y);
+        }
+    }
+    None
+}
+
+/// Q64.64 marginal StableSwap price of coin x in coin y, -dy/dx, at balances x and y:
+/// (ann * x + d_p) * y / ((ann * y + d_p) * x), with d_p = D^3 / (4 * x * y).
+pub fn stable_price(amp: u64, x: u128, y: u128) -> Option<u128> {
+    let d = stable_invariant(amp, x, y)?;
+    let d_p = stable_d_p(d, x, y)?;
+    let ann = (amp as u128).checked_mul(4)?;
+
+    let ratio = tick_math::mul_div(
+        ann.checked_mul(x)?.checked_add(d_p)?,
+        tick_math::Q64,
+        ann.checked_mul(y)?.checked_add(d_p)?,
+        false,
+    )?;
+    tick_math::mul_div(ratio, y, x, false)
+}
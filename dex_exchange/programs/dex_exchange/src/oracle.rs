@@ -0,0 +1,95 @@
+// Time-weighted average price oracle for the pools.
+//
+// Prices are Q64.64 fixed point: price_a is the price of token a in token b and price_b the price
+// of token b in token a, the marginal prices on the pool's curve (reserve_b / reserve_a for
+// constant product pools). The cumulative prices are allowed to wrap, only differences between
+// two points in time are meaningful.
+
+use crate::{
+    curve,
+    error::DexError,
+    state::{
+        observations::{Observations, OBSERVATIONS_LEN},
+        pool::Pool,
+    },
+};
+use anchor_lang::prelude::*;
+
+/// Current Q64.64 prices (price_a, price_b) from the reserves, None if either reserve is empty.
+pub fn prices(reserve_a: u64, reserve_b: u64) -> Option<(u128, u128)> {
+    if reserve_a == 0 || reserve_b == 0 {
+        return None;
+    }
+
+    let price_a = ((reserve_b as u128) << 64) / reserve_a as u128;
+    let price_b = ((reserve_a as u128) << 64) / reserve_b as u128;
+    Some((price_a, price_b))
+}
+
+/// Cumulative prices (price_a_cumulative, price_b_cumulative) of the pool extrapolated to now.
+pub fn cumulative_prices(pool: &Pool, now: i64) -> (u128, u128) {
+    let elapsed = now.saturating_sub(pool.last_update_timestamp);
+
+    match curve::prices(pool, pool.reserve_a, pool.reserve_b, now) {
+        Some((price_a, price_b)) if elapsed > 0 => (
+            pool.price_a_cumulative
+                .wrapping_add(price_a.wrapping_mul(elapsed as u128)),
+            pool.price_b_cumulative
+                .wrapping_add(price_b.wrapping_mul(elapsed as u128)),
+        ),
+        _ => (pool.price_a_cumulative, pool.price_b_cumulative),
+    }
+}
+
+/// Accumulates the prices up to now and records an observation.
+/// Must be called before the pool reserves change.
+pub fn update(pool: &mut Pool, observations: &mut Observations, now: i64) {
+    let (price_a_cumulative, price_b_cumulative) = cumulative_prices(pool, now);
+
+    pool.price_a_cumulative = price_a_cumulative;
+    pool.price_b_cumulative = price_b_cumulative;
+    pool.last_update_timestamp = now;
+
+    // at most one observation per timestamp
+    let index = observations.index as usize;
+    if now > observations.observations[index].timestamp {
+        let index = (index + 1) % OBSERVATIONS_LEN;
+        let observation = &mut observations.observations[index];
+
+        observation.timestamp = now;
+        observation.price_a_cumulative = price_a_cumulative;
+        observation.price_b_cumulative = price_b_cumulative;
+        observations.index = index as u16;
+    }
+}
+
+/// Time-weighted average prices (price_a, price_b) over at least the last window seconds,
+/// measured from the newest observation that is at least window seconds old.
+pub fn twap(
+    pool: &Pool,
+    observations: &Observations,
+    now: i64,
+    window: i64,
+) -> Result<(u128, u128)> {
+    let target = now
+        .checked_sub(window)
+        .ok_or(DexError::InvalidArithmeticOperation)?;
+
+    let observation = observations
+        .observations
+        .iter()
+        .filter(|x| x.timestamp > 0 && x.timestamp <= target)
+        .max_by_key(|x| x.timestamp)
+        .ok_or(DexError::OracleWindowUnavailable)?;
+
+    let elapsed = now - observation.timestamp;
+    if elapsed <= 0 {
+        return Err(DexError::OracleWindowUnavailable.into());
+    }
+
+    let (price_a_cumulative, price_b_cumulative) = cumulative_prices(pool, now);
+    let price_a = price_a_cumulative.wrapping_sub(observation.price_a_cumulative) / elapsed as u128;
+    let price_b = price_b_cumulative.wrapping_sub(observation.price_b_cumulative) / elapsed as u128;
+
+    Ok((price_a, price_b))
+}
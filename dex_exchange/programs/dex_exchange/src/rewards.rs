@@ -0,0 +1,49 @@
+// Liquidity mining reward accounting.
+//
+// A reward schedule emits emission_rate reward tokens per second between start_time and end_time,
+// shared by the staked lp tokens in proportion to their amount. Rewards per staked lp token are
+// tracked in Q64.64; emission pauses while nothing is staked, pushing end_time back by the idle
+// seconds, so the whole deposit is still emitted once lp tokens are staked again.
+
+use crate::{
+    state::{reward_schedule::RewardSchedule, stake_position::StakePosition},
+    tick_math::{self, Q64},
+};
+
+/// Accumulates the rewards emitted up to now into the schedule's reward growth.
+pub fn update_schedule(schedule: &mut RewardSchedule, now: i64) -> Option<()> {
+    let from = schedule.last_update_time.max(schedule.start_time);
+    let to = now.min(schedule.end_time);
+
+    if schedule.total_staked == 0 {
+        // Nothing earns the rewards, resume the remaining emission from now on
+        if now > from && from < schedule.end_time {
+            schedule.end_time = schedule.end_time.checked_add(now - from)?;
+        }
+    } else if to > from {
+        let emitted = (schedule.emission_rate as u128).checked_mul((to - from) as u128)?;
+        let reward_growth = tick_math::mul_div(emitted, Q64, schedule.total_staked as u128, false)?;
+        schedule.reward_growth = schedule.reward_growth.checked_add(reward_growth)?;
+    }
+
+    schedule.last_update_time = schedule.last_update_time.max(now);
+    Some(())
+}
+
+/// Credits the rewards earned by the stake since it was last updated.
+pub fn update_stake(stake: &mut StakePosition, schedule: &RewardSchedule) -> Option<()> {
+    let rewards = tick_math::mul_div(
+        stake.amount as u128,
+        schedule
+            .reward_growth
+            .checked_sub(stake.reward_growth_last)?,
+        Q64,
+        false,
+    )?;
+
+    stake.rewards_owed = stake
+        .rewards_owed
+        .checked_add(u64::try_from(rewards).ok()?)?;
+    stake.reward_growth_last = schedule.reward_growth;
+    Some(())
+}
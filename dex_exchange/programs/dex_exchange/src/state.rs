@@ -1,7 +1,20 @@
 // Program state handling.
 
+pub mod allowlist_entry;
+pub mod concentrated_pool;
 pub mod configs;
 pub mod deposit_base;
+pub mod event_queue;
 pub mod liquidity_provider;
+pub mod market;
+pub mod observations;
+pub mod open_orders;
+pub mod order_book;
 pub mod pool;
+pub mod pool_registry;
+pub mod position;
+pub mod reward_schedule;
+pub mod stake_position;
+pub mod suspension;
+pub mod tick_array;
 pub mod trader;
@@ -0,0 +1,8 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct AllowlistEntry {
+    pub pool: Pubkey,   // publickey of the liquidity pool
+    pub wallet: Pubkey, // publickey of the wallet allowed to trade and provide liquidity
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+// Bounds of the tick spacing of a concentrated liquidity pool
+pub const MIN_TICK_SPACING: u16 = 1;
+pub const MAX_TICK_SPACING: u16 = 16_384;
+
+#[account]
+#[derive(InitSpace)]
+pub struct ConcentratedPool {
+    pub owner: Pubkey, // publickey of the pool admin
+    pub token_a: Pubkey,
+    pub token_b: Pubkey,
+    pub vault_a: Pubkey, // token a vault, associated token account of the pool pda
+    pub vault_b: Pubkey, // token b vault, associated token account of the pool pda
+    pub bump: u8,        // bump of the pool pda, which signs for the pool vaults
+    pub tick_spacing: u16, // positions and initialized ticks are multiples of it, part of the pool pda seeds
+    pub fee_bps: u16, // swap fee charged on amount_in, in basis points, paid to the in-range positions
+    pub sqrt_price: u128, // Q64.64 square root of the price of token a in token b
+    pub tick_current: i32, // greatest tick at or below sqrt_price
+    pub liquidity: u128, // liquidity of the positions in range at tick_current
+    pub fee_growth_global_a: u128, // Q64.64 token a fees earned per unit of liquidity, wraps
+    pub fee_growth_global_b: u128, // Q64.64 token b fees earned per unit of liquidity, wraps
+    pub is_initialized: bool, // is pool initialized
+}
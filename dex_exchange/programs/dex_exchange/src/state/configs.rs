@@ -4,7 +4,14 @@ use anchor_lang::prelude::*;
 #[account]
 #[derive(Default, InitSpace)]
 pub struct DexConfigs {
+    pub owner: Pubkey, // publickey of the dex admin
     //#[max_len(5)]
     //pub operators: Vec<LotteryOperator>,
+    pub swaps_paused: bool, // swap, swap_exact_out, swap_route and swap_concentrated
+    pub liquidity_paused: bool, // add_liquidity, remove_liquidity, increase_liquidity and decrease_liquidity
+    pub flash_loans_paused: bool, // flash_borrow
+    pub orders_paused: bool,    // place_order
+    pub max_price_move_bps: u16, // largest price move a single swap may cause, in basis points, 0 disables the circuit breaker
+    pub bump: u8,                // bump of the dex configs pda
     pub is_initialized: bool,
 }
@@ -0,0 +1,21 @@
+use {crate::state::market::Side, anchor_lang::prelude::*};
+
+// Most fills waiting in the queue, matches max_len of EventQueue.events
+pub const EVENT_QUEUE_LEN: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct FillEvent {
+    pub maker: Pubkey,       // open orders account of the resting order
+    pub maker_order_id: u64, // id of the resting order
+    pub maker_side: Side,    // side of the resting order
+    pub price: u64,          // price of the resting order, quote base units per lot
+    pub quantity: u64,       // lots filled
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct EventQueue {
+    pub market: Pubkey, // publickey of the market
+    #[max_len(64)]
+    pub events: Vec<FillEvent>, // oldest first
+}
@@ -1,10 +1,25 @@
 use anchor_lang::prelude::*;
 
+// Most pools a liquidity provider can hold fee checkpoints for, matches the length of LiquidityProvider.fee_checkpoints
+pub const MAX_PROVIDER_POOLS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct FeeCheckpoint {
+    pub pool: Pubkey,            // publickey of the liquidity pool
+    pub shares: u64,             // shares deposited through add_liquidity that earn fees
+    pub fee_growth_a_last: u128, // pool fee_growth_a when the fees were last credited
+    pub fee_growth_b_last: u128, // pool fee_growth_b when the fees were last credited
+    pub fees_owed_a: u64,        // fees of token a credited and not yet claimed
+    pub fees_owed_b: u64,        // fees of token b credited and not yet claimed
+}
+
 #[account]
 #[derive(Default, Debug, InitSpace)]
 pub struct LiquidityProvider {
     pub owner: Pubkey,                  // publickey of the liquidity provider
     pub active: bool,                   // status of liquidity provider
-    pub reserve_a_available_funds: u32, // reserve_a available funds
-    pub reserve_b_available_funds: u32, // reserve_b available funds
+    pub reserve_a_available_funds: u64, // reserve_a available funds
+    pub reserve_b_available_funds: u64, // reserve_b available funds
+    #[max_len(8)]
+    pub fee_checkpoints: Vec<FeeCheckpoint>, // fee checkpoints, one per pool the provider deposited into
 }
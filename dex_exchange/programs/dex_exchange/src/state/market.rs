@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub enum Side {
+    #[default]
+    Bid, // buy base with quote
+    Ask, // sell base for quote
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OrderType {
+    Limit,             // matches what it can, the rest rests on the book
+    ImmediateOrCancel, // matches what it can, the rest is cancelled
+    PostOnly,          // rests on the book, rejected if it would match
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Market {
+    pub owner: Pubkey,        // publickey of the market admin
+    pub base_mint: Pubkey,    // mint of the traded token
+    pub quote_mint: Pubkey,   // mint prices are quoted in
+    pub base_vault: Pubkey,   // base vault, associated token account of the market pda
+    pub quote_vault: Pubkey,  // quote vault, associated token account of the market pda
+    pub bids: Pubkey,         // order book of the bids
+    pub asks: Pubkey,         // order book of the asks
+    pub event_queue: Pubkey,  // fills waiting for match_orders to credit the makers
+    pub base_lot_size: u64,   // base units per lot, order quantities are in lots
+    pub next_order_id: u64,   // id of the next order placed, increases monotonically
+    pub bump: u8,             // bump of the market pda, which signs for the market vaults
+    pub is_initialized: bool, // is market initialized
+}
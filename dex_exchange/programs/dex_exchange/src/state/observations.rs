@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+// Number of observations kept in the ring buffer, matches the length of Observations.observations
+pub const OBSERVATIONS_LEN: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct Observation {
+    pub timestamp: i64,           // unix timestamp of the observation, 0 if unused
+    pub price_a_cumulative: u128, // pool price_a_cumulative at timestamp
+    pub price_b_cumulative: u128, // pool price_b_cumulative at timestamp
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Observations {
+    pub pool: Pubkey,                    // publickey of the liquidity pool
+    pub index: u16,                      // index of the most recent observation
+    pub observations: [Observation; 32], // ring buffer of price observations
+}
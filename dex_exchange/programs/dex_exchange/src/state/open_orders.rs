@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct OpenOrders {
+    pub owner: Pubkey,     // publickey of the trader
+    pub market: Pubkey,    // publickey of the market
+    pub base_free: u64,    // base units settle_funds can withdraw
+    pub base_locked: u64,  // base units backing resting asks
+    pub quote_free: u64,   // quote units settle_funds can withdraw
+    pub quote_locked: u64, // quote units backing resting bids
+}
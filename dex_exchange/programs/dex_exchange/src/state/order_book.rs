@@ -0,0 +1,21 @@
+use {crate::state::market::Side, anchor_lang::prelude::*};
+
+// Most orders resting on one side of the book, matches max_len of OrderBook.orders
+pub const MAX_BOOK_ORDERS: usize = 64;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct Order {
+    pub order_id: u64,       // id assigned by the market
+    pub open_orders: Pubkey, // open orders account of the trader that placed the order
+    pub price: u64,          // quote base units per lot
+    pub quantity: u64,       // lots left to fill
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct OrderBook {
+    pub market: Pubkey, // publickey of the market
+    pub side: Side,     // side of the orders in the book
+    #[max_len(64)]
+    pub orders: Vec<Order>, // best price first, then oldest first
+}
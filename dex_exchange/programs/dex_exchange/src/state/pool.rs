@@ -1,15 +1,74 @@
 use anchor_lang::prelude::*;
 
+// Liquidity locked forever on the first deposit, so total_shares never returns to zero
+pub const MINIMUM_LIQUIDITY: u64 = 1_000;
+
+// Decimals of the pool's lp token mint
+pub const LP_MINT_DECIMALS: u8 = 9;
+
+// Denominator for fees expressed in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Bounds of the StableSwap amplification coefficient
+pub const MIN_AMP: u64 = 1;
+pub const MAX_AMP: u64 = 1_000_000;
+
+// Largest factor the amplification coefficient may change by in a single ramp
+pub const MAX_AMP_CHANGE: u64 = 10;
+
+// Shortest amplification ramp, in seconds
+pub const MIN_RAMP_DURATION: i64 = 86_400;
+
+// Fee charged on flash loans, in basis points of the amount borrowed
+pub const FLASH_FEE_BPS: u16 = 9;
+
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub enum CurveType {
+    #[default]
+    ConstantProduct, // x * y = k
+    StableSwap, // StableSwap invariant, for pegged pairs
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct Pool {
     pub owner: Pubkey, // publickey of the pool admin
     pub token_a: Pubkey,
     pub token_b: Pubkey,
-    pub reserve_a: u32,
-    pub reserve_b: u32,
-    pub is_initialized: bool, // is pool initialized
-    #[max_len(10)]
-    pub liquidity_providers: Vec<Pubkey>, // list of the liquidity providers
-    pub decimals: u8,         // decimals for the token mint
+    pub reserve_a: u64,             // reserve of token a, in base units
+    pub reserve_b: u64,             // reserve of token b, in base units
+    pub is_initialized: bool,       // is pool initialized
+    pub decimals_a: u8,             // decimals of the token a mint
+    pub decimals_b: u8,             // decimals of the token b mint
+    pub lp_mint: Pubkey,            // mint of the pool's lp tokens
+    pub vault_a: Pubkey,            // token a vault, associated token account of the pool pda
+    pub vault_b: Pubkey,            // token b vault, associated token account of the pool pda
+    pub total_shares: u64,          // lp token supply plus the locked minimum liquidity
+    pub bump: u8,                   // bump of the pool pda, which signs for the pool vaults
+    pub fee_tier: u16, // fee tier the pool was created with, part of the pool pda seeds
+    pub fee_bps: u16,  // swap fee charged on amount_in, in basis points
+    pub protocol_fee_bps: u16, // share of the swap fee kept by the protocol, in basis points of the fee
+    pub protocol_fees_a: u64, // accrued protocol fees of token a, held in the pool vault until collected
+    pub protocol_fees_b: u64, // accrued protocol fees of token b, held in the pool vault until collected
+    pub price_a_cumulative: u128, // time-weighted sum of the token a price (Q64.64), see oracle
+    pub price_b_cumulative: u128, // time-weighted sum of the token b price (Q64.64), see oracle
+    pub last_update_timestamp: i64, // unix timestamp the cumulative prices were last updated
+    pub observations: Pubkey, // ring buffer of cumulative price observations
+    pub curve_type: CurveType, // pricing curve of the pool
+    pub initial_amp: u64,     // StableSwap amplification coefficient at the start of the ramp
+    pub future_amp: u64,      // StableSwap amplification coefficient at the end of the ramp
+    pub initial_amp_time: i64, // unix timestamp the ramp started
+    pub future_amp_time: i64, // unix timestamp the ramp ends
+    pub flash_loan_active: bool, // a flash loan is outstanding, the pool is locked until it is repaid
+    pub flash_loan_token: Pubkey, // mint of the outstanding flash loan
+    pub flash_loan_amount: u64,  // amount of the outstanding flash loan, in base units
+    pub fee_growth_a: u128, // liquidity providers' fees of token a per fee share (Q64.64), see fees
+    pub fee_growth_b: u128, // liquidity providers' fees of token b per fee share (Q64.64), see fees
+    pub fee_shares: u64,    // shares checkpointed by liquidity providers, which earn the fees
+    pub lp_fees_a: u64, // unclaimed liquidity provider fees of token a, held in the pool vault outside the reserves
+    pub lp_fees_b: u64, // unclaimed liquidity provider fees of token b, held in the pool vault outside the reserves
+    pub allowlist_enabled: bool, // only allowlisted wallets may swap, add liquidity and flash borrow
+    pub pending_owner: Pubkey,   // pool admin proposed by the owner, takes over once it accepts
 }
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+// Most pools a registry can list, matches max_len of PoolRegistry.pools
+pub const MAX_POOLS: usize = 100;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct PoolRegistry {
+    pub owner: Pubkey,        // publickey of the dex admin
+    pub is_initialized: bool, // is registry initialized
+    #[max_len(100)]
+    pub pools: Vec<Pubkey>, // list of the created pools
+}
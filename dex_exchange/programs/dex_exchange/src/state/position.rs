@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Position {
+    pub owner: Pubkey,                  // publickey of the liquidity provider
+    pub pool: Pubkey,                   // publickey of the concentrated pool
+    pub tick_lower: i32,                // lower tick of the price range
+    pub tick_upper: i32,                // upper tick of the price range
+    pub liquidity: u128,                // liquidity provided over the range
+    pub fee_growth_inside_a_last: u128, // token a fee growth inside the range at the last update
+    pub fee_growth_inside_b_last: u128, // token b fee growth inside the range at the last update
+    pub tokens_owed_a: u64,             // token a fees owed to the position
+    pub tokens_owed_b: u64,             // token b fees owed to the position
+}
@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct RewardSchedule {
+    pub owner: Pubkey,         // publickey of the pool admin who created the schedule
+    pub pool: Pubkey,          // publickey of the liquidity pool whose lp tokens are staked
+    pub reward_mint: Pubkey,   // mint of the reward token
+    pub reward_vault: Pubkey,  // reward token vault, owned by the schedule pda
+    pub stake_vault: Pubkey,   // staked lp token vault, owned by the schedule pda
+    pub emission_rate: u64,    // reward tokens emitted per second, in base units
+    pub start_time: i64,       // unix timestamp emission starts
+    pub end_time: i64,         // unix timestamp emission ends, pushed back while nothing is staked
+    pub reward_growth: u128,   // rewards per staked lp token (Q64.64), see rewards
+    pub last_update_time: i64, // unix timestamp reward_growth was last updated
+    pub total_staked: u64,     // lp tokens staked, in base units
+    pub bump: u8,              // bump of the schedule pda, which signs for the vaults
+    pub is_initialized: bool,  // is reward schedule initialized
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct StakePosition {
+    pub owner: Pubkey,            // publickey of the liquidity provider
+    pub reward_schedule: Pubkey,  // publickey of the reward schedule staked into
+    pub amount: u64,              // lp tokens staked, in base units
+    pub reward_growth_last: u128, // schedule reward_growth when the rewards were last credited
+    pub rewards_owed: u64,        // rewards credited and not yet claimed, in base units
+}
@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+// Record of a suspended trader or liquidity provider wallet, kept from suspension until the admin
+// reactivates the wallet so that closing the account and registering again can't lift it
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct Suspension {
+    pub wallet: Pubkey, // publickey of the suspended owner
+    pub bump: u8,       // bump of the suspension pda
+}
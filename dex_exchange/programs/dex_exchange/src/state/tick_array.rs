@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+// Number of ticks per tick array, matches the length of TickArray.ticks
+pub const TICK_ARRAY_SIZE: usize = 32;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct Tick {
+    pub liquidity_net: i128, // liquidity added when the price crosses the tick upwards
+    pub liquidity_gross: u128, // total liquidity of the positions using the tick, 0 if uninitialized
+    pub fee_growth_outside_a: u128, // token a fee growth on the other side of the tick from tick_current
+    pub fee_growth_outside_b: u128, // token b fee growth on the other side of the tick from tick_current
+}
+
+#[account]
+#[derive(Default, Debug, InitSpace)]
+pub struct TickArray {
+    pub pool: Pubkey,          // publickey of the concentrated pool
+    pub start_tick_index: i32, // tick of ticks[0], a multiple of tick_spacing * TICK_ARRAY_SIZE
+    pub ticks: [Tick; 32],     // ticks start_tick_index + i * tick_spacing
+}
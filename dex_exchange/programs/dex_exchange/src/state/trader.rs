@@ -1,10 +1,22 @@
 use anchor_lang::prelude::*;
 
+// Most mints a trader's volumes are kept for, matches the length of Trader.volumes
+pub const MAX_TRADER_TOKENS: usize = 8;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, InitSpace)]
+pub struct TokenVolume {
+    pub mint: Pubkey,     // mint the volume is in
+    pub volume_in: u128,  // amount of the mint paid into swaps, in base units
+    pub volume_out: u128, // amount of the mint received from swaps, in base units
+}
+
 #[account]
 #[derive(Default, Debug, InitSpace)]
 pub struct Trader {
-    pub owner: Pubkey, // publickey of the trader
-    pub active: bool,  // status of trader
-    pub amount_out: u32,
-    pub amount_out_2: u64,
+    pub owner: Pubkey,            // publickey of the trader
+    pub active: bool,             // status of trader
+    pub swap_count: u64,          // swaps made by the trader, a routed swap counts once
+    pub last_swap_timestamp: i64, // unix timestamp of the trader's last swap
+    #[max_len(8)]
+    pub volumes: Vec<TokenVolume>, // swap volumes, one per mint the trader swapped
 }
@@ -0,0 +1,49 @@
+// Per-trader swap statistics.
+//
+// Volumes are kept per mint in base units for the first MAX_TRADER_TOKENS mints a trader swaps,
+// swaps in further mints are still counted by swap_count.
+
+use crate::state::trader::{TokenVolume, Trader, MAX_TRADER_TOKENS};
+use anchor_lang::prelude::*;
+
+/// Index of the volume of mint, inserted if the trader has room for another mint.
+pub fn volume_index_or_insert(trader: &mut Trader, mint: &Pubkey) -> Option<usize> {
+    if let Some(index) = trader.volumes.iter().position(|x| x.mint == *mint) {
+        return Some(index);
+    }
+
+    if trader.volumes.len() >= MAX_TRADER_TOKENS {
+        return None;
+    }
+
+    trader.volumes.push(TokenVolume {
+        mint: *mint,
+        ..Default::default()
+    });
+    Some(trader.volumes.len() - 1)
+}
+
+/// Records a swap of amount_in of token_in for amount_out of token_out made at now.
+pub fn record_swap(
+    trader: &mut Trader,
+    token_in: &Pubkey,
+    amount_in: u64,
+    token_out: &Pubkey,
+    amount_out: u64,
+    now: i64,
+) -> Option<()> {
+    trader.swap_count = trader.swap_count.checked_add(1)?;
+    trader.last_swap_timestamp = now;
+
+    if let Some(index) = volume_index_or_insert(trader, token_in) {
+        let volume = &mut trader.volumes[index];
+        volume.volume_in = volume.volume_in.checked_add(amount_in as u128)?;
+    }
+
+    if let Some(index) = volume_index_or_insert(trader, token_out) {
+        let volume = &mut trader.volumes[index];
+        volume.volume_out = volume.volume_out.checked_add(amount_out as u128)?;
+    }
+
+    Some(())
+}
@@ -0,0 +1,189 @@
+// Tick and sqrt price math for the concentrated liquidity pools.
+//
+// Sqrt prices are Q64.64 fixed point: sqrt_price = sqrt(1.0001^tick) * 2^64 is the square root of
+// the price of token a in token b. Liquidity L relates the two token amounts of a position over a
+// price range [lower, upper]: amount_a = L * (1 / lower - 1 / upper), amount_b = L * (upper - lower).
+
+pub const MIN_TICK: i32 = -443_636;
+pub const MAX_TICK: i32 = 443_636;
+
+// sqrt_price_at_tick(MIN_TICK) and sqrt_price_at_tick(MAX_TICK)
+pub const MIN_SQRT_PRICE: u128 = 4_295_048_016;
+pub const MAX_SQRT_PRICE: u128 = 79_226_673_521_066_979_257_578_248_091;
+
+// 1.0 in Q64.64
+pub const Q64: u128 = 1 << 64;
+const LOW_64: u128 = u64::MAX as u128;
+
+// 1 / sqrt(1.0001)^(2^i) in Q64.64, for the bits of |tick|
+const TICK_FACTORS: [u128; 19] = [
+    18_445_821_805_675_392_311,
+    18_444_899_583_751_176_498,
+    18_443_055_278_223_354_162,
+    18_439_367_220_385_604_838,
+    18_431_993_317_065_449_817,
+    18_417_254_355_718_160_513,
+    18_387_811_781_193_591_352,
+    18_329_067_761_203_520_168,
+    18_212_142_134_806_087_854,
+    17_980_523_815_641_551_639,
+    17_526_086_738_831_147_013,
+    16_651_378_430_235_024_244,
+    15_030_750_278_693_429_944,
+    12_247_334_978_882_834_399,
+    8_131_365_268_884_726_200,
+    3_584_323_654_723_342_297,
+    696_457_651_847_595_233,
+    26_294_789_957_452_057,
+    37_481_735_321_082,
+];
+
+// Full 256 bit product of a and b as (high, low) 128 bit halves
+fn full_mul(a: u128, b: u128) -> (u128, u128) {
+    let (a1, a0) = (a >> 64, a & LOW_64);
+    let (b1, b0) = (b >> 64, b & LOW_64);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let middle = (p00 >> 64) + (p01 & LOW_64) + (p10 & LOW_64);
+    let low = (p00 & LOW_64) | (middle << 64);
+    let high = p11 + (p01 >> 64) + (p10 >> 64) + (middle >> 64);
+    (high, low)
+}
+
+/// a * b / denominator without intermediate overflow, None if the result does not fit a u128.
+pub fn mul_div(a: u128, b: u128, denominator: u128, round_up: bool) -> Option<u128> {
+    if denominator == 0 {
+        return None;
+    }
+
+    let (high, low) = full_mul(a, b);
+    if high >= denominator {
+        return None;
+    }
+
+    let (quotient, remainder) = if high == 0 {
+        (low / denominator, low % denominator)
+    } else {
+        // long division of the 256 bit product, one bit at a time
+        let mut remainder = high;
+        let mut quotient = 0u128;
+        for i in (0..128).rev() {
+            let carry = remainder >> 127;
+            remainder = (remainder << 1) | ((low >> i) & 1);
+            quotient <<= 1;
+            if carry == 1 || remainder >= denominator {
+                remainder = remainder.wrapping_sub(denominator);
+                quotient |= 1;
+            }
+        }
+        (quotient, remainder)
+    };
+
+    if round_up && remainder > 0 {
+        quotient.checked_add(1)
+    } else {
+        Some(quotient)
+    }
+}
+
+/// Q64.64 sqrt price at tick.
+pub fn sqrt_price_at_tick(tick: i32) -> Option<u128> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return None;
+    }
+
+    let abs_tick = tick.unsigned_abs();
+    let mut ratio = Q64;
+    for (i, factor) in TICK_FACTORS.iter().enumerate() {
+        if abs_tick & (1 << i) != 0 {
+            ratio = mul_div(ratio, *factor, Q64, false)?;
+        }
+    }
+
+    // the factors are for negative ticks, invert for positive ones
+    if tick > 0 {
+        ratio = u128::MAX / ratio;
+    }
+    Some(ratio)
+}
+
+/// Greatest tick whose sqrt price is at or below sqrt_price.
+pub fn tick_at_sqrt_price(sqrt_price: u128) -> Option<i32> {
+    if !(MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price) {
+        return None;
+    }
+
+    let mut low = MIN_TICK;
+    let mut high = MAX_TICK;
+    while low < high {
+        let middle = low + (high - low + 1) / 2;
+        if sqrt_price_at_tick(middle)? <= sqrt_price {
+            low = middle;
+        } else {
+            high = middle - 1;
+        }
+    }
+    Some(low)
+}
+
+/// Amount of token a covered by liquidity between two sqrt prices.
+pub fn amount_a_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u64> {
+    let (lower, upper) = if sqrt_price_0 < sqrt_price_1 {
+        (sqrt_price_0, sqrt_price_1)
+    } else {
+        (sqrt_price_1, sqrt_price_0)
+    };
+
+    // L * (upper - lower) / upper / lower, with the prices in Q64.64
+    let amount = mul_div(liquidity, upper - lower, upper, round_up)?;
+    let amount = mul_div(amount, Q64, lower, round_up)?;
+    u64::try_from(amount).ok()
+}
+
+/// Amount of token b covered by liquidity between two sqrt prices.
+pub fn amount_b_delta(
+    sqrt_price_0: u128,
+    sqrt_price_1: u128,
+    liquidity: u128,
+    round_up: bool,
+) -> Option<u64> {
+    let difference = sqrt_price_0.abs_diff(sqrt_price_1);
+    let amount = mul_div(liquidity, difference, Q64, round_up)?;
+    u64::try_from(amount).ok()
+}
+
+/// Sqrt price after amount_in of token a is added, the price moves down. Rounded up.
+pub fn next_sqrt_price_from_amount_a(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+) -> Option<u128> {
+    if amount_in == 0 {
+        return Some(sqrt_price);
+    }
+
+    // L * sqrt_price / (L + amount_in * sqrt_price)
+    let product = mul_div(amount_in as u128, sqrt_price, Q64, false)?;
+    let denominator = liquidity.checked_add(product)?;
+    mul_div(liquidity, sqrt_price, denominator, true)
+}
+
+/// Sqrt price after amount_in of token b is added, the price moves up. Rounded down.
+pub fn next_sqrt_price_from_amount_b(
+    sqrt_price: u128,
+    liquidity: u128,
+    amount_in: u64,
+) -> Option<u128> {
+    // sqrt_price + amount_in / L
+    let delta = mul_div(amount_in as u128, Q64, liquidity, false)?;
+    sqrt_price.checked_add(delta)
+}
@@ -4,9 +4,12 @@ import { DexExchange } from "../target/types/dex_exchange";
 import {
   Account,
   createAccount,
+  getAccount,
   getOrCreateAssociatedTokenAccount,
+  transfer,
 } from "@solana/spl-token";
 import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
+import { assert } from "chai";
 
 describe("dex_exchange", () => {
   // Configure the client to use the local cluster.
@@ -22,15 +25,22 @@ describe("dex_exchange", () => {
   ); // USDC devnet */
 
   const payer = wallet.payer;
+  // token amounts are passed in base units i.e 9 decimals
+  const toBaseUnits = (amount: number) =>
+    new anchor.BN(amount).mul(new anchor.BN(10).pow(new anchor.BN(9)));
   const associateTokenProgram = new anchor.web3.PublicKey(
     "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
   );
-  const mintTokenA = anchor.web3.Keypair.generate(); // dummy usdc token created for test purposes
+  // pools store their pair in canonical order, so token A is the mint that sorts first
+  const [mintTokenA, mintTokenB] = [
+    anchor.web3.Keypair.generate(), // dummy usdc token created for test purposes
+    anchor.web3.Keypair.generate(), // dummy wbtc token created for test purposes
+  ].sort((x, y) => x.publicKey.toBuffer().compare(y.publicKey.toBuffer()));
+  const feeBps = 30; // 0.3% swap fee, also the pool's fee tier
   const tokenAccountA = anchor.utils.token.associatedAddress({
     mint: mintTokenA.publicKey,
     owner: payer.publicKey,
   });
-  const mintTokenB = anchor.web3.Keypair.generate(); // dummy wbtc token created for test purposes
   const tokenAccountB = anchor.utils.token.associatedAddress({
     mint: mintTokenB.publicKey,
     owner: payer.publicKey,
@@ -50,9 +60,13 @@ describe("dex_exchange", () => {
   let secondTraderOwner = anchor.web3.Keypair.generate();
   let secondTraderOwnerATA = anchor.web3.Keypair.generate();
 
+  let firstTraderOwnerATATokenB: Account;
+  let secondTraderOwnerATATokenA: Account;
+
   let treasuryVaultATA: Account;
   let treasuryVaultATATokenB: Account;
 
+
   // pdaAuth
   let [pdaAuth, adminPdaBump] = anchor.web3.PublicKey.findProgramAddressSync(
     [
@@ -67,14 +81,240 @@ describe("dex_exchange", () => {
       program.programId
     );
 
+  let [poolRegistry] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("pool-registry")],
+    program.programId
+  );
+
+  let [dexConfigs] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("dex-configs")],
+    program.programId
+  );
+
+  let feeTier = Buffer.alloc(2);
+  feeTier.writeUInt16LE(feeBps);
+
   let [liquidityPool] = anchor.web3.PublicKey.findProgramAddressSync(
     [
       anchor.utils.bytes.utf8.encode("liquidity-pool"),
-      adminOwner.publicKey.toBuffer(),
+      mintTokenA.publicKey.toBuffer(),
+      mintTokenB.publicKey.toBuffer(),
+      feeTier,
+    ],
+    program.programId
+  );
+
+  // pool vaults, created by create pool
+  const poolVaultA = anchor.utils.token.associatedAddress({
+    mint: mintTokenA.publicKey,
+    owner: liquidityPool,
+  });
+  const poolVaultB = anchor.utils.token.associatedAddress({
+    mint: mintTokenB.publicKey,
+    owner: liquidityPool,
+  });
+
+  let [lpMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("lp-mint"), liquidityPool.toBuffer()],
+    program.programId
+  );
+
+  let [observations] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("observations"), liquidityPool.toBuffer()],
+    program.programId
+  );
+
+  // stable swap pool of the same pair, in its own fee tier
+  const stableFeeBps = 4;
+  let stableFeeTier = Buffer.alloc(2);
+  stableFeeTier.writeUInt16LE(stableFeeBps);
+
+  let [stablePool] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("liquidity-pool"),
+      mintTokenA.publicKey.toBuffer(),
+      mintTokenB.publicKey.toBuffer(),
+      stableFeeTier,
+    ],
+    program.programId
+  );
+  const stableVaultA = anchor.utils.token.associatedAddress({
+    mint: mintTokenA.publicKey,
+    owner: stablePool,
+  });
+  const stableVaultB = anchor.utils.token.associatedAddress({
+    mint: mintTokenB.publicKey,
+    owner: stablePool,
+  });
+  let [stableLpMint] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("lp-mint"), stablePool.toBuffer()],
+    program.programId
+  );
+  let [stableObservations] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("observations"), stablePool.toBuffer()],
+    program.programId
+  );
+
+  // concentrated liquidity pool, price 1.0 with ticks spaced by 64
+  const tickSpacing = 64;
+  const tickArraySize = 32;
+  const tickLower = -640;
+  const tickUpper = 640;
+  const tickArrayStart = (tick: number) =>
+    Math.floor(tick / (tickSpacing * tickArraySize)) * tickSpacing * tickArraySize;
+  const i32Buffer = (value: number) => {
+    const buffer = Buffer.alloc(4);
+    buffer.writeInt32LE(value);
+    return buffer;
+  };
+  const tickSpacingBuffer = Buffer.alloc(2);
+  tickSpacingBuffer.writeUInt16LE(tickSpacing);
+
+  let [concentratedPool] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("concentrated-pool"),
+      mintTokenA.publicKey.toBuffer(),
+      mintTokenB.publicKey.toBuffer(),
+      tickSpacingBuffer,
+    ],
+    program.programId
+  );
+
+  const concentratedVaultA = anchor.utils.token.associatedAddress({
+    mint: mintTokenA.publicKey,
+    owner: concentratedPool,
+  });
+  const concentratedVaultB = anchor.utils.token.associatedAddress({
+    mint: mintTokenB.publicKey,
+    owner: concentratedPool,
+  });
+
+  let [tickArrayLower] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("tick-array"),
+      concentratedPool.toBuffer(),
+      i32Buffer(tickArrayStart(tickLower)),
+    ],
+    program.programId
+  );
+
+  let [tickArrayUpper] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("tick-array"),
+      concentratedPool.toBuffer(),
+      i32Buffer(tickArrayStart(tickUpper)),
+    ],
+    program.programId
+  );
+
+  let [secondLiquidityProviderPosition] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("position"),
+        concentratedPool.toBuffer(),
+        secondLiquidityProviderOwner.publicKey.toBuffer(),
+        i32Buffer(tickLower),
+        i32Buffer(tickUpper),
+      ],
+      program.programId
+    );
+
+  // order book market, token A is the base and token B the quote
+  const baseLotSize = new anchor.BN(1_000_000); // 0.001 token A per lot
+  let [market] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("market"),
+      mintTokenA.publicKey.toBuffer(),
+      mintTokenB.publicKey.toBuffer(),
     ],
     program.programId
   );
 
+  let [bids] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("bids"), market.toBuffer()],
+    program.programId
+  );
+
+  let [asks] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("asks"), market.toBuffer()],
+    program.programId
+  );
+
+  let [eventQueue] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("event-queue"), market.toBuffer()],
+    program.programId
+  );
+
+  const marketBaseVault = anchor.utils.token.associatedAddress({
+    mint: mintTokenA.publicKey,
+    owner: market,
+  });
+  const marketQuoteVault = anchor.utils.token.associatedAddress({
+    mint: mintTokenB.publicKey,
+    owner: market,
+  });
+
+  let [firstTraderOpenOrders] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("open-orders"),
+      market.toBuffer(),
+      firstTraderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [secondTraderOpenOrders] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("open-orders"),
+      market.toBuffer(),
+      secondTraderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  // liquidity mining, token B rewards for staked lp tokens of the pool
+  let adminOwnerATATokenB: Account;
+  let [rewardSchedule] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("reward-schedule"),
+      liquidityPool.toBuffer(),
+      mintTokenB.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [rewardVault] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("reward-vault"), rewardSchedule.toBuffer()],
+    program.programId
+  );
+
+  let [stakeVault] = anchor.web3.PublicKey.findProgramAddressSync(
+    [anchor.utils.bytes.utf8.encode("stake-vault"), rewardSchedule.toBuffer()],
+    program.programId
+  );
+
+  let [firstLiquidityProviderStakePosition] =
+    anchor.web3.PublicKey.findProgramAddressSync(
+      [
+        anchor.utils.bytes.utf8.encode("stake-position"),
+        rewardSchedule.toBuffer(),
+        firstLiquidityProviderOwner.publicKey.toBuffer(),
+      ],
+      program.programId
+    );
+
+  let [secondTraderAllowlistEntry] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("allowlist"),
+      liquidityPool.toBuffer(),
+      secondTraderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let firstLiquidityProviderLpTokens: Account;
+  let secondLiquidityProviderLpTokens: Account;
+
   let [firstLiquidityProvider] = anchor.web3.PublicKey.findProgramAddressSync(
     [
       anchor.utils.bytes.utf8.encode("liquidity-provider"),
@@ -107,6 +347,49 @@ describe("dex_exchange", () => {
     program.programId
   );
 
+  let [firstLiquidityProviderSuspension] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("liquidity-provider-suspension"),
+      firstLiquidityProviderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [secondLiquidityProviderSuspension] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("liquidity-provider-suspension"),
+      secondLiquidityProviderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [firstTraderSuspension] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("trader-suspension"),
+      firstTraderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  let [secondTraderSuspension] = anchor.web3.PublicKey.findProgramAddressSync(
+    [
+      anchor.utils.bytes.utf8.encode("trader-suspension"),
+      secondTraderOwner.publicKey.toBuffer(),
+    ],
+    program.programId
+  );
+
+  // asserts that the call is rejected with the given DexError
+  const assertDexError = async (call: Promise<string>, code: string) => {
+    try {
+      await call;
+    } catch (error) {
+      assert.equal(error.error?.errorCode?.code, code);
+      return;
+    }
+    assert.fail(`expected the call to fail with ${code}`);
+  };
+
   // admin owner
   before(async () => {
     let res = await provider.connection.requestAirdrop(
@@ -187,25 +470,27 @@ describe("dex_exchange", () => {
     });
   });
 
-  it("Is initialized!", async () => {
+  it("Is create token A!", async () => {
+    console.log("mint token a: ", mintTokenA.publicKey.toBase58());
+    console.log("token account: ", tokenAccountA.toBase58());
+
     try {
       let requestParams = {
-        tokenA: mintTokenA.publicKey, // token A
-        tokenB: mintTokenB.publicKey, // token B
+        amount: toBaseUnits(200),
         decimals: 9, // token mint in smallest unit i.e 9 decimals
       };
 
       const tx = await program.methods
-        .init(requestParams)
+        .createToken(requestParams)
         .accounts({
-          owner: adminOwner.publicKey,
-          liquidityPool: liquidityPool,
-          depositAccount: depositAccount.publicKey,
-          pdaAuth: pdaAuth,
-          treasuryVault: treasuryVault,
+          owner: payer.publicKey,
+          mintToken: mintTokenA.publicKey,
+          tokenAccount: tokenAccountA,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([adminOwner, depositAccount])
+        .signers([mintTokenA])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
@@ -213,27 +498,27 @@ describe("dex_exchange", () => {
     }
   });
 
-  it("Is create token A!", async () => {
-    console.log("mint token a: ", mintTokenA.publicKey.toBase58());
-    console.log("token account: ", tokenAccountA.toBase58());
+  it("Is create token B!", async () => {
+    console.log("mint token b: ", mintTokenB.publicKey.toBase58());
+    console.log("token account: ", tokenAccountB.toBase58());
 
     try {
       let requestParams = {
-        amount: new anchor.BN(200),
+        amount: toBaseUnits(100),
+        decimals: 9, // token mint in smallest unit i.e 9 decimals
       };
 
       const tx = await program.methods
         .createToken(requestParams)
         .accounts({
           owner: payer.publicKey,
-          liquidityPool: liquidityPool,
-          mintToken: mintTokenA.publicKey,
-          tokenAccount: tokenAccountA,
+          mintToken: mintTokenB.publicKey,
+          tokenAccount: tokenAccountB,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
-        .signers([mintTokenA])
+        .signers([mintTokenB])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
@@ -241,32 +526,66 @@ describe("dex_exchange", () => {
     }
   });
 
-  it("Is create token B!", async () => {
-    console.log("mint token b: ", mintTokenB.publicKey.toBase58());
-    console.log("token account: ", tokenAccountB.toBase58());
+  it("Is initialized!", async () => {
+    try {
+      const tx = await program.methods
+        .init()
+        .accounts({
+          owner: adminOwner.publicKey,
+          dexConfigs: dexConfigs,
+          poolRegistry: poolRegistry,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner, depositAccount])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
 
+  it("Is create pool!", async () => {
     try {
       let requestParams = {
-        amount: new anchor.BN(100),
+        feeBps: feeBps,
+        protocolFeeBps: 1667, // protocol keeps 1/6 of the swap fee
+        curveType: { constantProduct: {} }, // { stableSwap: {} } for pegged pairs
+        amp: new anchor.BN(0), // only used by stable swap pools
       };
 
       const tx = await program.methods
-        .createToken(requestParams)
+        .createPool(requestParams)
         .accounts({
-          owner: payer.publicKey,
+          owner: adminOwner.publicKey,
+          poolRegistry: poolRegistry,
           liquidityPool: liquidityPool,
-          mintToken: mintTokenB.publicKey,
-          tokenAccount: tokenAccountB,
+          lpMint: lpMint,
+          observations: observations,
+          mintTokenA: mintTokenA.publicKey, // token A
+          mintTokenB: mintTokenB.publicKey, // token B
+          vaultA: poolVaultA,
+          vaultB: poolVaultB,
           tokenProgram: TOKEN_PROGRAM_ID,
-          associateTokenProgram: associateTokenProgram,
+          associatedTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
         })
-        .signers([mintTokenB])
+        .signers([adminOwner])
         .rpc();
       console.log("Your transaction signature", tx);
     } catch (error) {
       console.log(error);
     }
+
+    try {
+      let result = await program.account.poolRegistry.fetch(poolRegistry);
+      console.log("pool registry: ", result);
+    } catch (error) {
+      console.log(error);
+    }
   });
 
   it("Is token transfer A - first liquidity provider", async () => {
@@ -289,7 +608,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(70),
+        amount: toBaseUnits(70),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -332,7 +651,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(30),
+        amount: toBaseUnits(30),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -375,7 +694,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(100),
+        amount: toBaseUnits(100),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -418,7 +737,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(40),
+        amount: toBaseUnits(40),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -461,7 +780,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(10),
+        amount: toBaseUnits(10),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -504,7 +823,7 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        amount: new anchor.BN(20),
+        amount: toBaseUnits(20),
       };
       const tx = await program.methods
         .transferToken(requestParams)
@@ -534,6 +853,7 @@ describe("dex_exchange", () => {
         .accounts({
           owner: firstLiquidityProviderOwner.publicKey,
           liquidityProvider: firstLiquidityProvider,
+          liquidityProviderSuspension: firstLiquidityProviderSuspension,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([firstLiquidityProviderOwner])
@@ -551,6 +871,7 @@ describe("dex_exchange", () => {
         .accounts({
           owner: secondLiquidityProviderOwner.publicKey,
           liquidityProvider: secondLiquidityProvider,
+          liquidityProviderSuspension: secondLiquidityProviderSuspension,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([secondLiquidityProviderOwner])
@@ -568,6 +889,7 @@ describe("dex_exchange", () => {
         .accounts({
           owner: firstTraderOwner.publicKey,
           trader: firstTrader,
+          traderSuspension: firstTraderSuspension,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([firstTraderOwner])
@@ -585,6 +907,7 @@ describe("dex_exchange", () => {
         .accounts({
           owner: secondTraderOwner.publicKey,
           trader: secondTrader,
+          traderSuspension: secondTraderSuspension,
           systemProgram: anchor.web3.SystemProgram.programId,
         })
         .signers([secondTraderOwner])
@@ -597,31 +920,11 @@ describe("dex_exchange", () => {
 
   it("Is add liquidity - first liquidity provider!", async () => {
     try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintTokenA.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
-    } catch (error) {
-      console.log(error);
-    }
-
-    try {
-      treasuryVaultATATokenB = await getOrCreateAssociatedTokenAccount(
+      firstLiquidityProviderLpTokens = await getOrCreateAssociatedTokenAccount(
         provider.connection,
         payer,
-        mintTokenB.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA token b address: " +
-          treasuryVaultATATokenB.address.toBase58()
+        lpMint,
+        firstLiquidityProviderOwner.publicKey
       );
     } catch (error) {
       console.log(error);
@@ -629,22 +932,28 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amountA: new anchor.BN(10),
-        amountB: new anchor.BN(10),
+        // amount of token to transfer (in smallest unit i.e 9 decimals)
+        amountA: toBaseUnits(10),
+        amountB: toBaseUnits(10),
       };
 
       const tx = await program.methods
         .addLiquidity(requestParams)
         .accounts({
           owner: firstLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
           liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
           liquidityProvider: firstLiquidityProvider,
           senderTokensA: firstLiquidityProviderOwnerATA.publicKey,
           senderTokensB: firstLiquidityProviderOwnerATATokenB.publicKey,
-          recipientTokensA: treasuryVaultATA.address,
-          recipientTokensB: treasuryVaultATATokenB.address,
-          mintToken: mintTokenA.publicKey,
+          poolTokensA: poolVaultA,
+          poolTokensB: poolVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          lpMint: lpMint,
+          recipientLpTokens: firstLiquidityProviderLpTokens.address,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -671,31 +980,11 @@ describe("dex_exchange", () => {
 
   it("Is add liquidity - second liquidity provider!", async () => {
     try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
-        provider.connection,
-        payer,
-        mintTokenA.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
-      );
-    } catch (error) {
-      console.log(error);
-    }
-
-    try {
-      treasuryVaultATATokenB = await getOrCreateAssociatedTokenAccount(
+      secondLiquidityProviderLpTokens = await getOrCreateAssociatedTokenAccount(
         provider.connection,
         payer,
-        mintTokenB.publicKey,
-        treasuryVault,
-        true
-      );
-      console.log(
-        "treasuryVaultATA token b address: " +
-          treasuryVaultATATokenB.address.toBase58()
+        lpMint,
+        secondLiquidityProviderOwner.publicKey
       );
     } catch (error) {
       console.log(error);
@@ -703,22 +992,28 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amountA: new anchor.BN(20),
-        amountB: new anchor.BN(20),
+        // amount of token to transfer (in smallest unit i.e 9 decimals)
+        amountA: toBaseUnits(20),
+        amountB: toBaseUnits(20),
       };
 
       const tx = await program.methods
         .addLiquidity(requestParams)
         .accounts({
           owner: secondLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
           liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
           liquidityProvider: secondLiquidityProvider,
           senderTokensA: secondLiquidityProviderOwnerATA.publicKey,
           senderTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
-          recipientTokensA: treasuryVaultATA.address,
-          recipientTokensB: treasuryVaultATATokenB.address,
-          mintToken: mintTokenA.publicKey,
+          poolTokensA: poolVaultA,
+          poolTokensB: poolVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          lpMint: lpMint,
+          recipientLpTokens: secondLiquidityProviderLpTokens.address,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -744,16 +1039,21 @@ describe("dex_exchange", () => {
   });
 
   it("Is swap token - first trader!", async () => {
+    // indexers follow the swaps through the emitted events
+    const swappedListener = program.addEventListener("swapped", (event) => {
+      console.log("swapped: ", event);
+    });
+
     try {
-      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
+      firstTraderOwnerATATokenB = await getOrCreateAssociatedTokenAccount(
         provider.connection,
         payer,
-        mintTokenA.publicKey,
-        treasuryVault,
-        true
+        mintTokenB.publicKey,
+        firstTraderOwner.publicKey
       );
       console.log(
-        "treasuryVaultATA address: " + treasuryVaultATA.address.toBase58()
+        "first trader token b address: " +
+          firstTraderOwnerATATokenB.address.toBase58()
       );
     } catch (error) {
       console.log(error);
@@ -761,20 +1061,28 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amountIn: new anchor.BN(2),
+        // amount of token to transfer (in smallest unit i.e 9 decimals)
+        amountIn: toBaseUnits(2),
         tokenIn: mintTokenA.publicKey,
+        minimumAmountOut: new anchor.BN(1),
+        deadline: new anchor.BN(Math.floor(Date.now() / 1000) + 60), // valid for 60 seconds
       };
 
       const tx = await program.methods
         .swap(requestParams)
         .accounts({
           owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
           liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
           trader: firstTrader,
           senderTokens: firstTraderOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATA.address,
-          mintToken: mintTokenA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -795,19 +1103,21 @@ describe("dex_exchange", () => {
     } catch (error) {
       console.log(error);
     }
+
+    await program.removeEventListener(swappedListener);
   });
 
   it("Is swap token - second trader!", async () => {
     try {
-      treasuryVaultATATokenB = await getOrCreateAssociatedTokenAccount(
+      secondTraderOwnerATATokenA = await getOrCreateAssociatedTokenAccount(
         provider.connection,
         payer,
-        mintTokenB.publicKey,
-        treasuryVault,
-        true
+        mintTokenA.publicKey,
+        secondTraderOwner.publicKey
       );
       console.log(
-        "treasuryVaultATA address: " + treasuryVaultATATokenB.address.toBase58()
+        "second trader token a address: " +
+          secondTraderOwnerATATokenA.address.toBase58()
       );
     } catch (error) {
       console.log(error);
@@ -815,20 +1125,28 @@ describe("dex_exchange", () => {
 
     try {
       let requestParams = {
-        // 1 amount of token to transfer (in smallest unit i.e 9 decimals)
-        amountIn: new anchor.BN(3),
+        // amount of token to transfer (in smallest unit i.e 9 decimals)
+        amountIn: toBaseUnits(3),
         tokenIn: mintTokenB.publicKey,
+        minimumAmountOut: new anchor.BN(1),
+        deadline: null,
       };
 
       const tx = await program.methods
         .swap(requestParams)
         .accounts({
           owner: secondTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
           liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
           trader: secondTrader,
           senderTokens: secondTraderOwnerATA.publicKey,
-          recipientTokens: treasuryVaultATATokenB.address,
-          mintToken: mintTokenB.publicKey,
+          recipientTokens: secondTraderOwnerATATokenA.address,
+          poolTokensIn: poolVaultB,
+          poolTokensOut: poolVaultA,
+          mintTokenIn: mintTokenB.publicKey,
+          mintTokenOut: mintTokenA.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
           associateTokenProgram: associateTokenProgram,
           systemProgram: anchor.web3.SystemProgram.programId,
@@ -850,4 +1168,2034 @@ describe("dex_exchange", () => {
       console.log(error);
     }
   });
+
+  it("Is swap exact out token - first trader!", async () => {
+    try {
+      let requestParams = {
+        // amount of token to transfer (in smallest unit i.e 9 decimals)
+        amountOut: toBaseUnits(1),
+        tokenIn: mintTokenA.publicKey,
+        maximumAmountIn: toBaseUnits(3),
+        deadline: null,
+      };
+
+      const tx = await program.methods
+        .swapExactOut(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.pool.fetch(liquidityPool);
+      console.log("liquidity pool: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is swap route - first trader!", async () => {
+    try {
+      let requestParams = {
+        amountIn: toBaseUnits(1),
+        tokenIn: mintTokenA.publicKey,
+        minimumAmountOut: new anchor.BN(1), // end-to-end, checked against the last leg only
+        deadline: new anchor.BN(Math.floor(Date.now() / 1000) + 60), // valid for 60 seconds
+      };
+
+      // one leg per pool: liquidity pool, observations, vault in, vault out, mint out
+      const route = [
+        { pubkey: liquidityPool, isSigner: false, isWritable: true },
+        { pubkey: observations, isSigner: false, isWritable: true },
+        { pubkey: poolVaultA, isSigner: false, isWritable: true },
+        { pubkey: poolVaultB, isSigner: false, isWritable: true },
+        { pubkey: mintTokenB.publicKey, isSigner: false, isWritable: false },
+      ];
+
+      const tx = await program.methods
+        .swapRoute(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          mintTokenIn: mintTokenA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(route)
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is flash loan - first trader!", async () => {
+    try {
+      // the repay must follow the borrow in the same transaction
+      const repayInstruction = await program.methods
+        .flashRepay()
+        .accounts({
+          liquidityPool: liquidityPool,
+          observations: observations,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          poolTokens: poolVaultA,
+          mint: mintTokenA.publicKey,
+          owner: firstTraderOwner.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .instruction();
+
+      let requestParams = {
+        token: mintTokenA.publicKey,
+        amount: toBaseUnits(1),
+      };
+
+      const tx = await program.methods
+        .flashBorrow(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          trader: firstTrader,
+          recipientTokens: firstTraderOwnerATA.publicKey,
+          poolTokens: poolVaultA,
+          mint: mintTokenA.publicKey,
+          instructions: anchor.web3.SYSVAR_INSTRUCTIONS_PUBKEY,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .postInstructions([repayInstruction])
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.pool.fetch(liquidityPool);
+      console.log("liquidity pool: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is claim fees - first liquidity provider!", async () => {
+    try {
+      const tx = await program.methods
+        .claimFees()
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          liquidityPool: liquidityPool,
+          liquidityProvider: firstLiquidityProvider,
+          poolTokensA: poolVaultA,
+          mintTokenA: mintTokenA.publicKey,
+          poolTokensB: poolVaultB,
+          mintTokenB: mintTokenB.publicKey,
+          recipientTokensA: firstLiquidityProviderOwnerATA.publicKey,
+          recipientTokensB: firstLiquidityProviderOwnerATATokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.liquidityProvider.fetch(
+        firstLiquidityProvider
+      );
+      console.log("first liquidity provider: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is create reward schedule!", async () => {
+    try {
+      adminOwnerATATokenB = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintTokenB.publicKey,
+        adminOwner.publicKey
+      );
+
+      let requestParams = {
+        amount: toBaseUnits(10),
+      };
+      const tx = await program.methods
+        .transferToken(requestParams)
+        .accounts({
+          owner: payer.publicKey,
+          liquidityPool: liquidityPool,
+          mintToken: mintTokenB.publicKey,
+          fromAccount: tokenAccountB,
+          toAccount: adminOwnerATATokenB.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([mintTokenB])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const now = Math.floor(Date.now() / 1000);
+      let requestParams = {
+        emissionRate: new anchor.BN(100_000_000), // 0.1 token B per second
+        startTime: new anchor.BN(now),
+        endTime: new anchor.BN(now + 100), // at most 10 token B, emission starts no earlier than the schedule
+      };
+
+      const tx = await program.methods
+        .createRewardSchedule(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+          rewardSchedule: rewardSchedule,
+          rewardMint: mintTokenB.publicKey,
+          lpMint: lpMint,
+          rewardVault: rewardVault,
+          stakeVault: stakeVault,
+          senderTokens: adminOwnerATATokenB.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is open stake - first liquidity provider!", async () => {
+    try {
+      const tx = await program.methods
+        .openStake()
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          rewardSchedule: rewardSchedule,
+          liquidityProvider: firstLiquidityProvider,
+          stakePosition: firstLiquidityProviderStakePosition,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is stake lp tokens - first liquidity provider!", async () => {
+    try {
+      let requestParams = {
+        amount: toBaseUnits(1),
+      };
+
+      const tx = await program.methods
+        .stakeLpTokens(requestParams)
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          rewardSchedule: rewardSchedule,
+          stakePosition: firstLiquidityProviderStakePosition,
+          stakeVault: stakeVault,
+          lpMint: lpMint,
+          ownerLpTokens: firstLiquidityProviderLpTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // nothing was staked until now, so the whole deposit is still left to emit
+    const schedule = await program.account.rewardSchedule.fetch(rewardSchedule);
+    const vault = await getAccount(provider.connection, rewardVault);
+    assert.equal(
+      vault.amount.toString(),
+      schedule.emissionRate
+        .mul(schedule.endTime.sub(schedule.lastUpdateTime))
+        .toString()
+    );
+  });
+
+  it("Is claim rewards - first liquidity provider!", async () => {
+    // let some rewards accrue
+    await new Promise((resolve) => setTimeout(resolve, 2000));
+
+    try {
+      const tx = await program.methods
+        .claimRewards()
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          rewardSchedule: rewardSchedule,
+          stakePosition: firstLiquidityProviderStakePosition,
+          rewardVault: rewardVault,
+          rewardMint: mintTokenB.publicKey,
+          recipientTokens: firstLiquidityProviderOwnerATATokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.stakePosition.fetch(
+        firstLiquidityProviderStakePosition
+      );
+      console.log("stake position: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is unstake lp tokens - first liquidity provider!", async () => {
+    try {
+      let requestParams = {
+        amount: toBaseUnits(1),
+      };
+
+      const tx = await program.methods
+        .unstakeLpTokens(requestParams)
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          rewardSchedule: rewardSchedule,
+          stakePosition: firstLiquidityProviderStakePosition,
+          stakeVault: stakeVault,
+          lpMint: lpMint,
+          ownerLpTokens: firstLiquidityProviderLpTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is remove transferred lp tokens - second liquidity provider!", async () => {
+    // lp tokens are transferable, the holder redeems them for a share of the reserves
+    const transferred = BigInt(1_000_000_000);
+    const lpTokensBefore = await getAccount(
+      provider.connection,
+      secondLiquidityProviderLpTokens.address
+    );
+
+    await transfer(
+      provider.connection,
+      payer,
+      firstLiquidityProviderLpTokens.address,
+      secondLiquidityProviderLpTokens.address,
+      firstLiquidityProviderOwner,
+      transferred
+    );
+
+    const poolBefore = await program.account.pool.fetch(liquidityPool);
+
+    const tx = await program.methods
+      .removeLiquidity({
+        shares: new anchor.BN(transferred.toString()),
+        minimumAmountA: new anchor.BN(1),
+        minimumAmountB: new anchor.BN(1),
+      })
+      .accounts({
+        owner: secondLiquidityProviderOwner.publicKey,
+        dexConfigs: dexConfigs,
+        liquidityPool: liquidityPool,
+        observations: observations,
+        liquidityProvider: secondLiquidityProvider,
+        poolTokensA: poolVaultA,
+        mintTokenA: mintTokenA.publicKey,
+        poolTokensB: poolVaultB,
+        mintTokenB: mintTokenB.publicKey,
+        recipientTokensA: secondLiquidityProviderOwnerATA.publicKey,
+        recipientTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+        lpMint: lpMint,
+        senderLpTokens: secondLiquidityProviderLpTokens.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([secondLiquidityProviderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const lpTokensAfter = await getAccount(
+      provider.connection,
+      secondLiquidityProviderLpTokens.address
+    );
+    assert.equal(lpTokensAfter.amount, lpTokensBefore.amount);
+
+    const poolAfter = await program.account.pool.fetch(liquidityPool);
+    assert.ok(
+      poolBefore.totalShares
+        .sub(poolAfter.totalShares)
+        .eq(new anchor.BN(transferred.toString()))
+    );
+  });
+
+  it("Is remove liquidity - first liquidity provider!", async () => {
+    try {
+      let lpTokens = await getAccount(
+        provider.connection,
+        firstLiquidityProviderLpTokens.address
+      );
+
+      let requestParams = {
+        shares: new anchor.BN(lpTokens.amount.toString()), // burn all lp tokens held by the liquidity provider
+        minimumAmountA: new anchor.BN(1),
+        minimumAmountB: new anchor.BN(1),
+      };
+
+      const tx = await program.methods
+        .removeLiquidity(requestParams)
+        .accounts({
+          owner: firstLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          observations: observations,
+          liquidityProvider: firstLiquidityProvider,
+          poolTokensA: poolVaultA,
+          mintTokenA: mintTokenA.publicKey,
+          poolTokensB: poolVaultB,
+          mintTokenB: mintTokenB.publicKey,
+          recipientTokensA: firstLiquidityProviderOwnerATA.publicKey,
+          recipientTokensB: firstLiquidityProviderOwnerATATokenB.publicKey,
+          lpMint: lpMint,
+          senderLpTokens: firstLiquidityProviderLpTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.liquidityProvider.fetch(
+        firstLiquidityProvider
+      );
+      console.log("liquidity provider: ", result);
+
+      let result2 = await program.account.pool.fetch(liquidityPool);
+      console.log("liquidity pool: ", result2);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is suspend liquidity provider - second liquidity provider!", async () => {
+    const tx = await program.methods
+      .suspendLiquidityProvider()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        liquidityProvider: secondLiquidityProvider,
+        liquidityProviderSuspension: secondLiquidityProviderSuspension,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    // new deposits are rejected while suspended
+    await assertDexError(
+      program.methods
+        .addLiquidity({ amountA: toBaseUnits(1), amountB: toBaseUnits(1) })
+        .accounts({
+          owner: secondLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
+          liquidityProvider: secondLiquidityProvider,
+          senderTokensA: secondLiquidityProviderOwnerATA.publicKey,
+          senderTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+          poolTokensA: poolVaultA,
+          poolTokensB: poolVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          lpMint: lpMint,
+          recipientLpTokens: secondLiquidityProviderLpTokens.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondLiquidityProviderOwner])
+        .rpc(),
+      "InvalidLiquidityProviderStatus"
+    );
+
+    // but the suspended liquidity provider can still withdraw
+    const sharesBefore = (
+      await getAccount(provider.connection, secondLiquidityProviderLpTokens.address)
+    ).amount;
+
+    const tx2 = await program.methods
+      .removeLiquidity({
+        shares: toBaseUnits(1),
+        minimumAmountA: new anchor.BN(1),
+        minimumAmountB: new anchor.BN(1),
+      })
+      .accounts({
+        owner: secondLiquidityProviderOwner.publicKey,
+        dexConfigs: dexConfigs,
+        liquidityPool: liquidityPool,
+        observations: observations,
+        liquidityProvider: secondLiquidityProvider,
+        poolTokensA: poolVaultA,
+        mintTokenA: mintTokenA.publicKey,
+        poolTokensB: poolVaultB,
+        mintTokenB: mintTokenB.publicKey,
+        recipientTokensA: secondLiquidityProviderOwnerATA.publicKey,
+        recipientTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+        lpMint: lpMint,
+        senderLpTokens: secondLiquidityProviderLpTokens.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([secondLiquidityProviderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+
+    const sharesAfter = (
+      await getAccount(provider.connection, secondLiquidityProviderLpTokens.address)
+    ).amount;
+    assert.equal(sharesBefore - sharesAfter, BigInt(1_000_000_000));
+
+    const tx3 = await program.methods
+      .reactivateLiquidityProvider()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        liquidityProvider: secondLiquidityProvider,
+        liquidityProviderSuspension: secondLiquidityProviderSuspension,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx3);
+  });
+
+  it("Is get twap!", async () => {
+    try {
+      // the first observation is written by the first swap or liquidity change after creation
+      let requestParams = {
+        window: new anchor.BN(1),
+      };
+
+      const twap = await program.methods
+        .getTwap(requestParams)
+        .accounts({
+          liquidityPool: liquidityPool,
+          observations: observations,
+        })
+        .view();
+      console.log("twap price a: ", twap.priceA.toString());
+      console.log("twap price b: ", twap.priceB.toString());
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is update pool fees!", async () => {
+    try {
+      let requestParams = {
+        feeBps: 25, // 0.25% swap fee
+        protocolFeeBps: 2000, // protocol keeps 1/5 of the swap fee
+      };
+
+      const tx = await program.methods
+        .updatePoolFees(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is collect protocol fees!", async () => {
+    try {
+      treasuryVaultATA = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintTokenA.publicKey,
+        treasuryVault,
+        true
+      );
+      treasuryVaultATATokenB = await getOrCreateAssociatedTokenAccount(
+        provider.connection,
+        payer,
+        mintTokenB.publicKey,
+        treasuryVault,
+        true
+      );
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const tx = await program.methods
+        .collectProtocolFees()
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+          depositAccount: depositAccount.publicKey,
+          pdaAuth: pdaAuth,
+          treasuryVault: treasuryVault,
+          poolTokensA: poolVaultA,
+          mintTokenA: mintTokenA.publicKey,
+          poolTokensB: poolVaultB,
+          mintTokenB: mintTokenB.publicKey,
+          treasuryTokensA: treasuryVaultATA.address,
+          treasuryTokensB: treasuryVaultATATokenB.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.pool.fetch(liquidityPool);
+      console.log("liquidity pool: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is withdraw treasury!", async () => {
+    const adminTokensA = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      mintTokenA.publicKey,
+      adminOwner.publicKey
+    );
+    const treasury = (
+      await getAccount(provider.connection, treasuryVaultATA.address)
+    ).amount;
+    assert.ok(treasury > BigInt(0));
+
+    const withdrawAccounts = {
+      owner: adminOwner.publicKey,
+      depositAccount: depositAccount.publicKey,
+      pdaAuth: pdaAuth,
+      treasuryVault: treasuryVault,
+      treasuryTokens: treasuryVaultATA.address,
+      mintToken: mintTokenA.publicKey,
+      recipientTokens: adminTokensA.address,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    // only the dex admin can withdraw
+    await assertDexError(
+      program.methods
+        .withdrawTreasury({ amount: new anchor.BN(treasury.toString()) })
+        .accounts({
+          ...withdrawAccounts,
+          owner: firstTraderOwner.publicKey,
+          recipientTokens: firstTraderOwnerATA.publicKey,
+        })
+        .signers([firstTraderOwner])
+        .rpc(),
+      "ConstraintHasOne"
+    );
+
+    const tx = await program.methods
+      .withdrawTreasury({ amount: new anchor.BN(treasury.toString()) })
+      .accounts(withdrawAccounts)
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const treasuryAfter = (
+      await getAccount(provider.connection, treasuryVaultATA.address)
+    ).amount;
+    const adminAfter = (
+      await getAccount(provider.connection, adminTokensA.address)
+    ).amount;
+    assert.equal(treasuryAfter, BigInt(0));
+    assert.equal(adminAfter - adminTokensA.amount, treasury);
+  });
+
+  it("Is create concentrated pool!", async () => {
+    try {
+      let requestParams = {
+        tickSpacing: tickSpacing,
+        feeBps: feeBps,
+        sqrtPrice: new anchor.BN(1).shln(64), // Q64.64 sqrt price of 1.0
+      };
+
+      const tx = await program.methods
+        .createConcentratedPool(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          poolRegistry: poolRegistry,
+          concentratedPool: concentratedPool,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          vaultA: concentratedVaultA,
+          vaultB: concentratedVaultB,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is initialize tick arrays!", async () => {
+    for (const [tickArray, tick] of [
+      [tickArrayLower, tickLower],
+      [tickArrayUpper, tickUpper],
+    ] as [anchor.web3.PublicKey, number][]) {
+      try {
+        let requestParams = {
+          startTickIndex: tickArrayStart(tick),
+        };
+
+        const tx = await program.methods
+          .initializeTickArray(requestParams)
+          .accounts({
+            owner: secondLiquidityProviderOwner.publicKey,
+            concentratedPool: concentratedPool,
+            tickArray: tickArray,
+            systemProgram: anchor.web3.SystemProgram.programId,
+          })
+          .signers([secondLiquidityProviderOwner])
+          .rpc();
+        console.log("Your transaction signature", tx);
+      } catch (error) {
+        console.log(error);
+      }
+    }
+  });
+
+  it("Is open position - second liquidity provider!", async () => {
+    try {
+      let requestParams = {
+        tickLower: tickLower,
+        tickUpper: tickUpper,
+      };
+
+      const tx = await program.methods
+        .openPosition(requestParams)
+        .accounts({
+          owner: secondLiquidityProviderOwner.publicKey,
+          concentratedPool: concentratedPool,
+          liquidityProvider: secondLiquidityProvider,
+          position: secondLiquidityProviderPosition,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is increase liquidity - second liquidity provider!", async () => {
+    try {
+      let requestParams = {
+        liquidity: new anchor.BN("100000000000"),
+        maximumAmountA: toBaseUnits(10),
+        maximumAmountB: toBaseUnits(10),
+      };
+
+      const tx = await program.methods
+        .increaseLiquidity(requestParams)
+        .accounts({
+          owner: secondLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          concentratedPool: concentratedPool,
+          liquidityProvider: secondLiquidityProvider,
+          position: secondLiquidityProviderPosition,
+          tickArrayLower: tickArrayLower,
+          tickArrayUpper: tickArrayUpper,
+          ownerTokensA: secondLiquidityProviderOwnerATA.publicKey,
+          ownerTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+          poolTokensA: concentratedVaultA,
+          poolTokensB: concentratedVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([secondLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.concentratedPool.fetch(
+        concentratedPool
+      );
+      console.log("concentrated pool: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is swap concentrated - second trader!", async () => {
+    try {
+      let requestParams = {
+        amountIn: toBaseUnits(1),
+        tokenIn: mintTokenB.publicKey,
+        minimumAmountOut: new anchor.BN(1),
+        sqrtPriceLimit: null,
+        deadline: null,
+      };
+
+      const tx = await program.methods
+        .swapConcentrated(requestParams)
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          concentratedPool: concentratedPool,
+          trader: secondTrader,
+          senderTokens: secondTraderOwnerATA.publicKey,
+          recipientTokens: secondTraderOwnerATATokenA.address,
+          poolTokensIn: concentratedVaultB,
+          poolTokensOut: concentratedVaultA,
+          mintTokenIn: mintTokenB.publicKey,
+          mintTokenOut: mintTokenA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          { pubkey: tickArrayUpper, isSigner: false, isWritable: true },
+        ])
+        .signers([secondTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.concentratedPool.fetch(
+        concentratedPool
+      );
+      console.log("concentrated pool: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is decrease liquidity - second liquidity provider!", async () => {
+    try {
+      let requestParams = {
+        liquidity: new anchor.BN("50000000000"),
+        minimumAmountA: new anchor.BN(0),
+        minimumAmountB: new anchor.BN(0),
+      };
+
+      const tx = await program.methods
+        .decreaseLiquidity(requestParams)
+        .accounts({
+          owner: secondLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          concentratedPool: concentratedPool,
+          liquidityProvider: secondLiquidityProvider,
+          position: secondLiquidityProviderPosition,
+          tickArrayLower: tickArrayLower,
+          tickArrayUpper: tickArrayUpper,
+          ownerTokensA: secondLiquidityProviderOwnerATA.publicKey,
+          ownerTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+          poolTokensA: concentratedVaultA,
+          poolTokensB: concentratedVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([secondLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is collect position fees - second liquidity provider!", async () => {
+    try {
+      const tx = await program.methods
+        .collectPositionFees()
+        .accounts({
+          owner: secondLiquidityProviderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          concentratedPool: concentratedPool,
+          liquidityProvider: secondLiquidityProvider,
+          position: secondLiquidityProviderPosition,
+          tickArrayLower: tickArrayLower,
+          tickArrayUpper: tickArrayUpper,
+          ownerTokensA: secondLiquidityProviderOwnerATA.publicKey,
+          ownerTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+          poolTokensA: concentratedVaultA,
+          poolTokensB: concentratedVaultB,
+          mintTokenA: mintTokenA.publicKey,
+          mintTokenB: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([secondLiquidityProviderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.position.fetch(
+        secondLiquidityProviderPosition
+      );
+      console.log("position: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is suspend liquidity provider - concentrated position!", async () => {
+    const positionAccounts = {
+      owner: secondLiquidityProviderOwner.publicKey,
+      dexConfigs: dexConfigs,
+      concentratedPool: concentratedPool,
+      liquidityProvider: secondLiquidityProvider,
+      position: secondLiquidityProviderPosition,
+      tickArrayLower: tickArrayLower,
+      tickArrayUpper: tickArrayUpper,
+      ownerTokensA: secondLiquidityProviderOwnerATA.publicKey,
+      ownerTokensB: secondLiquidityProviderOwnerATATokenB.publicKey,
+      poolTokensA: concentratedVaultA,
+      poolTokensB: concentratedVaultB,
+      mintTokenA: mintTokenA.publicKey,
+      mintTokenB: mintTokenB.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    };
+
+    const tx = await program.methods
+      .suspendLiquidityProvider()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        liquidityProvider: secondLiquidityProvider,
+        liquidityProviderSuspension: secondLiquidityProviderSuspension,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    // new liquidity is rejected while suspended
+    await assertDexError(
+      program.methods
+        .increaseLiquidity({
+          liquidity: new anchor.BN("10000000000"),
+          maximumAmountA: toBaseUnits(10),
+          maximumAmountB: toBaseUnits(10),
+        })
+        .accounts(positionAccounts)
+        .signers([secondLiquidityProviderOwner])
+        .rpc(),
+      "InvalidLiquidityProviderStatus"
+    );
+
+    // but the position can still be drawn down and its fees collected
+    const positionBefore = await program.account.position.fetch(
+      secondLiquidityProviderPosition
+    );
+
+    const tx2 = await program.methods
+      .decreaseLiquidity({
+        liquidity: new anchor.BN("10000000000"),
+        minimumAmountA: new anchor.BN(0),
+        minimumAmountB: new anchor.BN(0),
+      })
+      .accounts(positionAccounts)
+      .signers([secondLiquidityProviderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+
+    const tx3 = await program.methods
+      .collectPositionFees()
+      .accounts(positionAccounts)
+      .signers([secondLiquidityProviderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx3);
+
+    const positionAfter = await program.account.position.fetch(
+      secondLiquidityProviderPosition
+    );
+    assert.ok(
+      positionBefore.liquidity
+        .sub(positionAfter.liquidity)
+        .eq(new anchor.BN("10000000000"))
+    );
+    assert.ok(positionAfter.tokensOwedA.isZero());
+    assert.ok(positionAfter.tokensOwedB.isZero());
+
+    const tx4 = await program.methods
+      .reactivateLiquidityProvider()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        liquidityProvider: secondLiquidityProvider,
+        liquidityProviderSuspension: secondLiquidityProviderSuspension,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx4);
+  });
+
+  it("Is create market!", async () => {
+    try {
+      let requestParams = {
+        baseLotSize: baseLotSize,
+      };
+
+      const tx = await program.methods
+        .createMarket(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          poolRegistry: poolRegistry,
+          market: market,
+          bids: bids,
+          asks: asks,
+          eventQueue: eventQueue,
+          baseMint: mintTokenA.publicKey,
+          quoteMint: mintTokenB.publicKey,
+          baseVault: marketBaseVault,
+          quoteVault: marketQuoteVault,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associatedTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+          rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is init open orders - first trader!", async () => {
+    try {
+      const tx = await program.methods
+        .initOpenOrders()
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          market: market,
+          trader: firstTrader,
+          openOrders: firstTraderOpenOrders,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is init open orders - second trader!", async () => {
+    try {
+      const tx = await program.methods
+        .initOpenOrders()
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          market: market,
+          trader: secondTrader,
+          openOrders: secondTraderOpenOrders,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is place post only ask - first trader!", async () => {
+    try {
+      let requestParams = {
+        side: { ask: {} },
+        price: new anchor.BN(1_000_000), // 1 token B per token A
+        quantity: new anchor.BN(1000), // 1 token A
+        orderType: { postOnly: {} },
+      };
+
+      const tx = await program.methods
+        .placeOrder(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          market: market,
+          bids: bids,
+          asks: asks,
+          eventQueue: eventQueue,
+          openOrders: firstTraderOpenOrders,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          marketVault: marketBaseVault,
+          mint: mintTokenA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is place limit bid - second trader!", async () => {
+    try {
+      let requestParams = {
+        side: { bid: {} },
+        price: new anchor.BN(1_010_000), // crosses the ask, fills at the ask's price
+        quantity: new anchor.BN(400),
+        orderType: { limit: {} },
+      };
+
+      const tx = await program.methods
+        .placeOrder(requestParams)
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          market: market,
+          bids: bids,
+          asks: asks,
+          eventQueue: eventQueue,
+          openOrders: secondTraderOpenOrders,
+          trader: secondTrader,
+          senderTokens: secondTraderOwnerATA.publicKey,
+          marketVault: marketQuoteVault,
+          mint: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([secondTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.eventQueue.fetch(eventQueue);
+      console.log("event queue: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is match orders!", async () => {
+    try {
+      let requestParams = {
+        limit: 10,
+      };
+
+      const tx = await program.methods
+        .matchOrders(requestParams)
+        .accounts({
+          market: market,
+          eventQueue: eventQueue,
+        })
+        .remainingAccounts([
+          { pubkey: firstTraderOpenOrders, isSigner: false, isWritable: true },
+        ])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.openOrders.fetch(
+        firstTraderOpenOrders
+      );
+      console.log("first trader open orders: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is cancel order - first trader!", async () => {
+    try {
+      let requestParams = {
+        side: { ask: {} },
+        orderId: new anchor.BN(0),
+      };
+
+      const tx = await program.methods
+        .cancelOrder(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          market: market,
+          bids: bids,
+          asks: asks,
+          openOrders: firstTraderOpenOrders,
+        })
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is settle funds - first trader!", async () => {
+    try {
+      const tx = await program.methods
+        .settleFunds()
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          market: market,
+          openOrders: firstTraderOpenOrders,
+          baseVault: marketBaseVault,
+          quoteVault: marketQuoteVault,
+          baseMint: mintTokenA.publicKey,
+          quoteMint: mintTokenB.publicKey,
+          recipientBaseTokens: firstTraderOwnerATA.publicKey,
+          recipientQuoteTokens: firstTraderOwnerATATokenB.address,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([firstTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is settle funds - second trader!", async () => {
+    try {
+      const tx = await program.methods
+        .settleFunds()
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          market: market,
+          openOrders: secondTraderOpenOrders,
+          baseVault: marketBaseVault,
+          quoteVault: marketQuoteVault,
+          baseMint: mintTokenA.publicKey,
+          quoteMint: mintTokenB.publicKey,
+          recipientBaseTokens: secondTraderOwnerATATokenA.address,
+          recipientQuoteTokens: secondTraderOwnerATA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([secondTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let result = await program.account.openOrders.fetch(
+        secondTraderOpenOrders
+      );
+      console.log("second trader open orders: ", result);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+  it("Is suspend trader - second trader!", async () => {
+    try {
+      const tx = await program.methods
+        .suspendTrader()
+        .accounts({
+          owner: adminOwner.publicKey,
+          poolRegistry: poolRegistry,
+          trader: secondTrader,
+          traderSuspension: secondTraderSuspension,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is reactivate trader - second trader!", async () => {
+    try {
+      const tx = await program.methods
+        .reactivateTrader()
+        .accounts({
+          owner: adminOwner.publicKey,
+          poolRegistry: poolRegistry,
+          trader: secondTrader,
+          traderSuspension: secondTraderSuspension,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is pool allowlist mode!", async () => {
+    try {
+      let requestParams = {
+        enabled: true,
+      };
+
+      const tx = await program.methods
+        .setPoolAllowlist(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let requestParams = {
+        wallet: secondTraderOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .addToAllowlist(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+          allowlistEntry: secondTraderAllowlistEntry,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // wallets without an allowlist entry are rejected
+    await assertDexError(
+      program.methods
+        .swap({
+          amountIn: toBaseUnits(1),
+          tokenIn: mintTokenA.publicKey,
+          minimumAmountOut: new anchor.BN(1),
+          deadline: null,
+        })
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc(),
+      "NotAllowlisted"
+    );
+
+    // so is another wallet's entry
+    await assertDexError(
+      program.methods
+        .swap({
+          amountIn: toBaseUnits(1),
+          tokenIn: mintTokenA.publicKey,
+          minimumAmountOut: new anchor.BN(1),
+          deadline: null,
+        })
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: secondTraderAllowlistEntry,
+          observations: observations,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc(),
+      "NotAllowlisted"
+    );
+
+    try {
+      let requestParams = {
+        amountIn: toBaseUnits(1),
+        tokenIn: mintTokenB.publicKey,
+        minimumAmountOut: new anchor.BN(1),
+        deadline: null,
+      };
+
+      const tx = await program.methods
+        .swap(requestParams)
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: secondTraderAllowlistEntry,
+          observations: observations,
+          trader: secondTrader,
+          senderTokens: secondTraderOwnerATA.publicKey,
+          recipientTokens: secondTraderOwnerATATokenA.address,
+          poolTokensIn: poolVaultB,
+          poolTokensOut: poolVaultA,
+          mintTokenIn: mintTokenB.publicKey,
+          mintTokenOut: mintTokenA.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondTraderOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const tx = await program.methods
+        .removeFromAllowlist()
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+          allowlistEntry: secondTraderAllowlistEntry,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      let requestParams = {
+        enabled: false,
+      };
+
+      const tx = await program.methods
+        .setPoolAllowlist(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is close trader - second trader!", async () => {
+    // a suspended trader can still close its account for the rent
+    const tx = await program.methods
+      .suspendTrader()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        trader: secondTrader,
+        traderSuspension: secondTraderSuspension,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const tx2 = await program.methods
+      .closeTrader()
+      .accounts({
+        owner: secondTraderOwner.publicKey,
+        trader: secondTrader,
+      })
+      .signers([secondTraderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+    assert.isNull(await provider.connection.getAccountInfo(secondTrader));
+
+    // the suspension record outlives the account, registering again is rejected
+    await assertDexError(
+      program.methods
+        .registerTrader()
+        .accounts({
+          owner: secondTraderOwner.publicKey,
+          trader: secondTrader,
+          traderSuspension: secondTraderSuspension,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([secondTraderOwner])
+        .rpc(),
+      "InvalidTraderStatus"
+    );
+
+    // until the admin reactivates the wallet
+    const tx3 = await program.methods
+      .reactivateTrader()
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        trader: null,
+        traderSuspension: secondTraderSuspension,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx3);
+
+    const tx4 = await program.methods
+      .registerTrader()
+      .accounts({
+        owner: secondTraderOwner.publicKey,
+        trader: secondTrader,
+        traderSuspension: secondTraderSuspension,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([secondTraderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx4);
+
+    const result = await program.account.trader.fetch(secondTrader);
+    assert.ok(result.active);
+  });
+
+  it("Is pause swaps!", async () => {
+    try {
+      let requestParams = {
+        swaps: true,
+        liquidity: false,
+        flashLoans: false,
+        orders: false,
+      };
+
+      const tx = await program.methods
+        .setPaused(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          dexConfigs: dexConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // rejected while swaps are paused
+    let requestParams = {
+      amountIn: toBaseUnits(1),
+      tokenIn: mintTokenA.publicKey,
+      minimumAmountOut: new anchor.BN(1),
+      deadline: null,
+    };
+
+    await assertDexError(
+      program.methods
+        .swap(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc(),
+      "Paused"
+    );
+
+    try {
+      let requestParams = {
+        swaps: false,
+        liquidity: false,
+        flashLoans: false,
+        orders: false,
+      };
+
+      const tx = await program.methods
+        .setPaused(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          dexConfigs: dexConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is circuit breaker!", async () => {
+    try {
+      let requestParams = {
+        maxPriceMoveBps: 100, // largest price move a single swap may cause
+      };
+
+      const tx = await program.methods
+        .setCircuitBreaker(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          dexConfigs: dexConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // rejected, moves the price by more than 1%
+    let requestParams = {
+      amountIn: toBaseUnits(10),
+      tokenIn: mintTokenA.publicKey,
+      minimumAmountOut: new anchor.BN(1),
+      deadline: null,
+    };
+
+    await assertDexError(
+      program.methods
+        .swap(requestParams)
+        .accounts({
+          owner: firstTraderOwner.publicKey,
+          dexConfigs: dexConfigs,
+          liquidityPool: liquidityPool,
+          allowlistEntry: null,
+          observations: observations,
+          trader: firstTrader,
+          senderTokens: firstTraderOwnerATA.publicKey,
+          recipientTokens: firstTraderOwnerATATokenB.address,
+          poolTokensIn: poolVaultA,
+          poolTokensOut: poolVaultB,
+          mintTokenIn: mintTokenA.publicKey,
+          mintTokenOut: mintTokenB.publicKey,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          associateTokenProgram: associateTokenProgram,
+          systemProgram: anchor.web3.SystemProgram.programId,
+        })
+        .signers([firstTraderOwner])
+        .rpc(),
+      "PriceMoveExceeded"
+    );
+
+    try {
+      let requestParams = {
+        maxPriceMoveBps: 0, // largest price move a single swap may cause
+      };
+
+      const tx = await program.methods
+        .setCircuitBreaker(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          dexConfigs: dexConfigs,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is swap slippage and deadline!", async () => {
+    const swapAccounts = {
+      owner: firstTraderOwner.publicKey,
+      dexConfigs: dexConfigs,
+      liquidityPool: liquidityPool,
+      allowlistEntry: null,
+      observations: observations,
+      trader: firstTrader,
+      senderTokens: firstTraderOwnerATA.publicKey,
+      recipientTokens: firstTraderOwnerATATokenB.address,
+      poolTokensIn: poolVaultA,
+      poolTokensOut: poolVaultB,
+      mintTokenIn: mintTokenA.publicKey,
+      mintTokenOut: mintTokenB.publicKey,
+      tokenProgram: TOKEN_PROGRAM_ID,
+      associateTokenProgram: associateTokenProgram,
+      systemProgram: anchor.web3.SystemProgram.programId,
+    };
+
+    // rejected, no swap can pay out the largest amount of token B
+    await assertDexError(
+      program.methods
+        .swap({
+          amountIn: new anchor.BN(100_000_000),
+          tokenIn: mintTokenA.publicKey,
+          minimumAmountOut: new anchor.BN("18446744073709551615"),
+          deadline: null,
+        })
+        .accounts(swapAccounts)
+        .signers([firstTraderOwner])
+        .rpc(),
+      "SlippageExceeded"
+    );
+
+    // rejected, the deadline has passed
+    await assertDexError(
+      program.methods
+        .swap({
+          amountIn: new anchor.BN(100_000_000),
+          tokenIn: mintTokenA.publicKey,
+          minimumAmountOut: new anchor.BN(1),
+          deadline: new anchor.BN(Math.floor(Date.now() / 1000) - 60),
+        })
+        .accounts(swapAccounts)
+        .signers([firstTraderOwner])
+        .rpc(),
+      "DeadlineExceeded"
+    );
+  });
+
+  it("Is propose and accept pool admin!", async () => {
+    const newPoolAdmin = anchor.web3.Keypair.generate();
+
+    try {
+      let requestParams = {
+        newOwner: newPoolAdmin.publicKey,
+      };
+
+      const tx = await program.methods
+        .proposeAdmin(requestParams)
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const tx = await program.methods
+        .acceptAdmin()
+        .accounts({
+          pendingOwner: newPoolAdmin.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([newPoolAdmin])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    // the new pool admin collects the protocol fees into the dex treasury
+    let pool = await program.account.pool.fetch(liquidityPool);
+    assert.ok(pool.owner.equals(newPoolAdmin.publicKey));
+    const treasuryBefore = (
+      await getAccount(provider.connection, treasuryVaultATATokenB.address)
+    ).amount;
+
+    const tx = await program.methods
+      .collectProtocolFees()
+      .accounts({
+        owner: newPoolAdmin.publicKey,
+        liquidityPool: liquidityPool,
+        depositAccount: depositAccount.publicKey,
+        pdaAuth: pdaAuth,
+        treasuryVault: treasuryVault,
+        poolTokensA: poolVaultA,
+        mintTokenA: mintTokenA.publicKey,
+        poolTokensB: poolVaultB,
+        mintTokenB: mintTokenB.publicKey,
+        treasuryTokensA: treasuryVaultATA.address,
+        treasuryTokensB: treasuryVaultATATokenB.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([newPoolAdmin])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const treasuryAfter = (
+      await getAccount(provider.connection, treasuryVaultATATokenB.address)
+    ).amount;
+    assert.equal(treasuryAfter - treasuryBefore, BigInt(pool.protocolFeesB.toString()));
+    pool = await program.account.pool.fetch(liquidityPool);
+    assert.ok(pool.protocolFeesA.eqn(0) && pool.protocolFeesB.eqn(0));
+
+    // hand the pool back to the admin
+    try {
+      let requestParams = {
+        newOwner: adminOwner.publicKey,
+      };
+
+      const tx = await program.methods
+        .proposeAdmin(requestParams)
+        .accounts({
+          owner: newPoolAdmin.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([newPoolAdmin])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+
+    try {
+      const tx = await program.methods
+        .acceptAdmin()
+        .accounts({
+          pendingOwner: adminOwner.publicKey,
+          liquidityPool: liquidityPool,
+        })
+        .signers([adminOwner])
+        .rpc();
+      console.log("Your transaction signature", tx);
+    } catch (error) {
+      console.log(error);
+    }
+  });
+
+  it("Is stable swap pool!", async () => {
+    let requestParams = {
+      feeBps: stableFeeBps,
+      protocolFeeBps: 1667,
+      curveType: { stableSwap: {} },
+      amp: new anchor.BN(100),
+    };
+
+    const tx = await program.methods
+      .createPool(requestParams)
+      .accounts({
+        owner: adminOwner.publicKey,
+        poolRegistry: poolRegistry,
+        liquidityPool: stablePool,
+        lpMint: stableLpMint,
+        observations: stableObservations,
+        mintTokenA: mintTokenA.publicKey,
+        mintTokenB: mintTokenB.publicKey,
+        vaultA: stableVaultA,
+        vaultB: stableVaultB,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associatedTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+        rent: anchor.web3.SYSVAR_RENT_PUBKEY,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    const stableLpTokens = await getOrCreateAssociatedTokenAccount(
+      provider.connection,
+      payer,
+      stableLpMint,
+      firstLiquidityProviderOwner.publicKey
+    );
+
+    const tx2 = await program.methods
+      .addLiquidity({ amountA: toBaseUnits(5), amountB: toBaseUnits(5) })
+      .accounts({
+        owner: firstLiquidityProviderOwner.publicKey,
+        dexConfigs: dexConfigs,
+        liquidityPool: stablePool,
+        allowlistEntry: null,
+        observations: stableObservations,
+        liquidityProvider: firstLiquidityProvider,
+        senderTokensA: firstLiquidityProviderOwnerATA.publicKey,
+        senderTokensB: firstLiquidityProviderOwnerATATokenB.publicKey,
+        poolTokensA: stableVaultA,
+        poolTokensB: stableVaultB,
+        mintTokenA: mintTokenA.publicKey,
+        mintTokenB: mintTokenB.publicKey,
+        lpMint: stableLpMint,
+        recipientLpTokens: stableLpTokens.address,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associateTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstLiquidityProviderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+  });
+
+  it("Is stable swap - first trader!", async () => {
+    const balanceBefore = (
+      await getAccount(provider.connection, firstTraderOwnerATATokenB.address)
+    ).amount;
+
+    // 0.1 token A, a constant product pool of 5/5 would pay out less than 0.098
+    let requestParams = {
+      amountIn: new anchor.BN(100_000_000),
+      tokenIn: mintTokenA.publicKey,
+      minimumAmountOut: new anchor.BN(99_900_000),
+      deadline: null,
+    };
+
+    const tx = await program.methods
+      .swap(requestParams)
+      .accounts({
+        owner: firstTraderOwner.publicKey,
+        dexConfigs: dexConfigs,
+        liquidityPool: stablePool,
+        allowlistEntry: null,
+        observations: stableObservations,
+        trader: firstTrader,
+        senderTokens: firstTraderOwnerATA.publicKey,
+        recipientTokens: firstTraderOwnerATATokenB.address,
+        poolTokensIn: stableVaultA,
+        poolTokensOut: stableVaultB,
+        mintTokenIn: mintTokenA.publicKey,
+        mintTokenOut: mintTokenB.publicKey,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        associateTokenProgram: associateTokenProgram,
+        systemProgram: anchor.web3.SystemProgram.programId,
+      })
+      .signers([firstTraderOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    // near the peg the output is the input less the 0.04% fee, never more than the input
+    const amountOut =
+      (await getAccount(provider.connection, firstTraderOwnerATATokenB.address))
+        .amount - balanceBefore;
+    assert.ok(amountOut >= BigInt(99_900_000));
+    assert.ok(amountOut < BigInt(100_000_000));
+
+    let pool = await program.account.pool.fetch(stablePool);
+    assert.ok(pool.reserveA.gt(pool.reserveB));
+  });
+
+  it("Is ramp amp!", async () => {
+    const now = Math.floor(Date.now() / 1000);
+
+    // more than 10x in a single ramp is rejected
+    await assertDexError(
+      program.methods
+        .rampAmp({
+          futureAmp: new anchor.BN(2_000),
+          futureAmpTime: new anchor.BN(now + 2 * 86_400),
+        })
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: stablePool,
+        })
+        .signers([adminOwner])
+        .rpc(),
+      "InvalidAmpRamp"
+    );
+
+    // so is a ramp shorter than a day
+    await assertDexError(
+      program.methods
+        .rampAmp({
+          futureAmp: new anchor.BN(1_000),
+          futureAmpTime: new anchor.BN(now + 60),
+        })
+        .accounts({
+          owner: adminOwner.publicKey,
+          liquidityPool: stablePool,
+        })
+        .signers([adminOwner])
+        .rpc(),
+      "InvalidAmpRamp"
+    );
+
+    const tx = await program.methods
+      .rampAmp({
+        futureAmp: new anchor.BN(1_000),
+        futureAmpTime: new anchor.BN(now + 2 * 86_400),
+      })
+      .accounts({
+        owner: adminOwner.publicKey,
+        liquidityPool: stablePool,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx);
+
+    let pool = await program.account.pool.fetch(stablePool);
+    assert.ok(pool.initialAmp.eqn(100));
+    assert.ok(pool.futureAmp.eqn(1_000));
+
+    // stopping freezes the coefficient where the ramp is, just above 100 after a few seconds
+    const tx2 = await program.methods
+      .stopRampAmp()
+      .accounts({
+        owner: adminOwner.publicKey,
+        liquidityPool: stablePool,
+      })
+      .signers([adminOwner])
+      .rpc();
+    console.log("Your transaction signature", tx2);
+
+    pool = await program.account.pool.fetch(stablePool);
+    assert.ok(pool.futureAmp.eq(pool.initialAmp));
+    assert.ok(pool.futureAmp.gten(100) && pool.futureAmp.ltn(110));
+  });
 });